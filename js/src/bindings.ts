@@ -11,14 +11,56 @@ import {
   sendMessageGroupInstruction,
   deleteMessageInstruction,
   deleteGroupMessageInstruction,
+  sendTipInstruction,
   createSubscriptionInstruction,
+  reactToMessageInstruction,
+  editMessageInstruction,
+  editGroupMessageInstruction,
+  closeThreadInstruction,
+  closeProfileInstruction,
+  closeGroupThreadInstruction,
+  closeGroupIndexInstruction,
+  deleteSubscriptionInstruction,
+  joinGroupInstruction,
+  leaveGroupInstruction,
+  createInviteInstruction,
+  redeemInviteInstruction,
+  banMemberInstruction,
+  unbanMemberInstruction,
+  muteMemberInstruction,
+  blockUserInstruction,
+  unblockUserInstruction,
+  acceptThreadInstruction,
+  declineThreadInstruction,
+  addContactInstruction,
+  removeContactInstruction,
+  refundEscrowInstruction,
+  initConfigInstruction,
+  updateConfigInstruction,
+  withdrawFeesInstruction,
+  registerReferrerInstruction,
+  proposeGroupOwnerInstruction,
+  acceptGroupOwnerInstruction,
+  grantGroupPermissionsInstruction,
+  revokeGroupPermissionsInstruction,
+  renameGroupInstruction,
+  deleteBucketMessageInstruction,
+  migrateAccountInstruction,
+  revokeInviteInstruction,
+  OptionalPubkey,
 } from "./raw_instructions";
 import {
   Connection,
   MemcmpFilter,
   PublicKey,
   SystemProgram,
+  SYSVAR_RENT_PUBKEY,
 } from "@solana/web3.js";
+import {
+  Token,
+  TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import BN from "bn.js";
 import {
   Profile,
@@ -28,15 +70,61 @@ import {
   GroupThread,
   GroupThreadIndex,
   Subscription,
+  StorageMode,
+  ReactionType,
+  GroupRole,
+  GroupAdmin,
+  GroupMember,
+  GroupModeration,
+  Invite,
+  Block,
+  Contact,
+  Escrow,
+  Config,
+  Referrer,
+  MessageBucket,
+  Reaction,
 } from "./state";
 
 export const JAB_ID = new PublicKey(
   "2iKLjPgcL3cwEGwJeXj3bEbYFkWEPQ4UqpueL1iSXZZ9"
 );
-export const SOL_VAULT = new PublicKey(
-  "GcWEQ9K78FV7LEHteFVciYApERk5YvQuFDQPk1yYJVXi"
+export const BPF_LOADER_UPGRADEABLE_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
 );
 
+const getTokenAccount = (mint: PublicKey, owner: PublicKey) => {
+  return Token.getAssociatedTokenAddress(
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+    TOKEN_PROGRAM_ID,
+    mint,
+    owner,
+    true
+  );
+};
+
+// Accounts appended to the payment instructions: the token accounts when paying
+// in tokens, then the referrer wallet (or token account) and its `Referrer` account
+const getPaymentAccounts = async (
+  payer: PublicKey,
+  destination: PublicKey,
+  priceMint?: PublicKey,
+  referrer?: PublicKey
+) => {
+  const vault = await Config.getVaultKey();
+  const tokenAccount = async (owner: PublicKey) => {
+    return priceMint ? await getTokenAccount(priceMint, owner) : undefined;
+  };
+  return {
+    splTokenProgram: priceMint ? TOKEN_PROGRAM_ID : undefined,
+    tokenSource: await tokenAccount(payer),
+    tokenDestination: await tokenAccount(destination),
+    vaultTokenAccount: await tokenAccount(vault),
+    referrer: referrer && ((await tokenAccount(referrer)) || referrer),
+    referrerStats: referrer && (await Referrer.getKey(referrer)),
+  };
+};
+
 /**
  *
  * @param profileOwner Owner of the profile
@@ -106,6 +194,11 @@ export const createThread = async (
  * @param lamportsPerMessage lamports per message
  * @param allowDm If the user allows DM
  * @param profileOwner Profile owner
+ * @param escrowEnabled If message payments are held in escrow until the user replies
+ * @param escrowPeriod Time after which an unanswered message payment can be refunded (seconds)
+ * @param priceMint Mint of the token messages are priced in, lamports are used when undefined
+ * @param priceAmount Price per message in `priceMint` tokens
+ * @param editWindow Time during which messages sent to the user can be edited (seconds)
  * @returns
  */
 export const setUserProfile = async (
@@ -114,7 +207,12 @@ export const setUserProfile = async (
   bio: string,
   lamportsPerMessage: number,
   allowDm: boolean,
-  profileOwner: PublicKey
+  profileOwner: PublicKey,
+  escrowEnabled = false,
+  escrowPeriod = 0,
+  priceMint?: PublicKey,
+  priceAmount = 0,
+  editWindow?: number
 ) => {
   const [profile] = await PublicKey.findProgramAddress(
     Profile.generateSeeds(profileOwner),
@@ -126,8 +224,13 @@ export const setUserProfile = async (
     displayDomainName,
     bio,
     lamportsPerMessage: new BN(lamportsPerMessage),
+    priceMint: priceMint?.toBuffer(),
+    priceAmount: new BN(priceAmount),
     allowDm: allowDm ? 1 : 0,
-  }).getInstruction(JAB_ID, profileOwner, profile);
+    escrowEnabled: escrowEnabled ? 1 : 0,
+    escrowPeriod: new BN(escrowPeriod),
+    editWindow: editWindow !== undefined ? new BN(editWindow) : undefined,
+  }).getInstruction(JAB_ID, SystemProgram.programId, profileOwner, profile);
 
   return instruction;
 };
@@ -140,6 +243,7 @@ export const setUserProfile = async (
  * @param message The message
  * @param kind The message kind
  * @param repliesTo If the message is a replie to another message (if not PublicKey.default())
+ * @param referrer Wallet of the referrer of the payment
  * @returns
  */
 export const sendMessage = async (
@@ -148,7 +252,8 @@ export const sendMessage = async (
   receiver: PublicKey,
   message: Uint8Array,
   kind: MessageType,
-  repliesTo: PublicKey
+  repliesTo: PublicKey,
+  referrer?: PublicKey
 ) => {
   const [receiverProfile] = await PublicKey.findProgramAddress(
     Profile.generateSeeds(receiver),
@@ -166,6 +271,18 @@ export const sendMessage = async (
     JAB_ID
   );
 
+  const profileInfo = await connection.getAccountInfo(receiverProfile);
+  const priceMint = profileInfo?.data
+    ? Profile.deserialize(profileInfo.data).priceMint
+    : undefined;
+
+  const payment = await getPaymentAccounts(
+    sender,
+    receiver,
+    priceMint,
+    referrer
+  );
+
   const instruction = new sendMessageInstruction({
     kind: kind,
     message: Array.from(message),
@@ -178,7 +295,18 @@ export const sendMessage = async (
     threadAccount,
     receiverProfile,
     messageAccount,
-    SOL_VAULT
+    await Config.getVaultKey(),
+    await Config.getKey(),
+    await Block.getKey(receiver, sender),
+    await Contact.getKey(receiver, sender),
+    await Escrow.getKey(messageAccount),
+    await Escrow.getKey(repliesTo),
+    payment.splTokenProgram,
+    payment.tokenSource,
+    payment.tokenDestination,
+    payment.vaultTokenAccount,
+    payment.referrer,
+    payment.referrerStats
   );
 
   return instruction;
@@ -221,73 +349,110 @@ export const retrieveUserThread = async (
 
 /**
  *
+ * @param groupId Stable identifier of the group (32 bytes), e.g random bytes
  * @param groupName Name of the group
  * @param destinationWallet Wallet that will receive the fees
  * @param lamportsPerMessage SOL fee per message
- * @param admins Admins of the group
  * @param owner Owner of the group (only address that will be able to edit the group)
  * @param mediaEnabled Is it possible to send media (images, videos and audios)?
+ * @param adminOnly If only admins can post messages
  * @param feePayer Fee payer of the instruction
  * @param visible If the group can be visible for others to join. Only used for the app, at the end of the day everything is visible on-chain
+ * @param membersOnly If a membership account is required to post messages
+ * @param isPrivate If the group can only be joined with an invite
+ * @param storageMode If messages are stored in their own account or in shared buckets
+ * @param priceMint Mint of the token messages are priced in, lamports are used when undefined
+ * @param priceAmount Price per message in `priceMint` tokens
  * @returns
  */
 export const createGroupThread = async (
+  groupId: Uint8Array,
   groupName: string,
   destinationWallet: PublicKey,
   lamportsPerMessage: BN,
-  admins: PublicKey[],
   owner: PublicKey,
   mediaEnabled: boolean,
   adminOnly: boolean,
   feePayer: PublicKey,
-  visible: boolean
+  visible: boolean,
+  membersOnly = false,
+  isPrivate = false,
+  storageMode = StorageMode.Accounts,
+  priceMint?: PublicKey,
+  priceAmount = new BN(0)
 ) => {
-  const groupThread = await GroupThread.getKey(groupName, owner);
+  const groupThread = await GroupThread.getKey(groupId, owner);
 
   const instruction = new createGroupThreadInstruction({
+    visible: visible ? 1 : 0,
+    groupId,
     groupName,
     destinationWallet: destinationWallet.toBuffer(),
     lamportsPerMessage,
-    admins: admins.map((e) => e.toBuffer()),
+    priceMint: priceMint?.toBuffer(),
+    priceAmount,
     owner: owner.toBuffer(),
     mediaEnabled: mediaEnabled ? 1 : 0,
     adminOnly: adminOnly ? 1 : 0,
-    visible: visible ? 1 : 0,
+    membersOnly: membersOnly ? 1 : 0,
+    private: isPrivate ? 1 : 0,
+    storageMode,
   }).getInstruction(JAB_ID, SystemProgram.programId, groupThread, feePayer);
 
   return instruction;
 };
 
 /**
+ * Only the settings that are defined are changed
  *
- * @param groupName Name of the group
- * @param owner Owner of the group
- * @param destinationWallet allet that will receive the fees
- * @param lamportsPerMessage SOL fee per message
- * @param mediaEnabled Is it possible to send media (images, videos and audios)?
+ * @param groupThread Address of the group thread
+ * @param editor Owner or admin of the group
+ * @param settings New settings of the group, a `null` price mint prices messages in lamports
  * @returns
  */
 export const editGroupThread = async (
-  groupName: string,
-  owner: PublicKey,
-  destinationWallet: PublicKey,
-  lamportsPerMessage: BN,
-  mediaEnabled: boolean,
-  adminOnly: boolean,
-  groupPicHash: string,
-  visible: boolean
+  groupThread: PublicKey,
+  editor: PublicKey,
+  settings: {
+    visible?: boolean;
+    destinationWallet?: PublicKey;
+    lamportsPerMessage?: BN;
+    priceMint?: PublicKey | null;
+    priceAmount?: BN;
+    mediaEnabled?: boolean;
+    adminOnly?: boolean;
+    membersOnly?: boolean;
+    isPrivate?: boolean;
+    editWindow?: BN;
+    groupPicHash?: string;
+  }
 ) => {
-  const groupThread = await GroupThread.getKey(groupName, owner);
+  const flag = (value?: boolean) => {
+    return value === undefined ? undefined : value ? 1 : 0;
+  };
 
   const instruction = new editGroupThreadInstruction({
-    destinationWallet: destinationWallet.toBuffer(),
-    lamportsPerMessage,
-    owner: owner.toBuffer(),
-    mediaEnabled: mediaEnabled ? 1 : 0,
-    adminOnly: adminOnly ? 1 : 0,
-    groupPicHash,
-    visible: visible ? 1 : 0,
-  }).getInstruction(JAB_ID, owner, groupThread);
+    visible: flag(settings.visible),
+    destinationWallet: settings.destinationWallet?.toBuffer(),
+    lamportsPerMessage: settings.lamportsPerMessage,
+    priceMint:
+      settings.priceMint === undefined
+        ? undefined
+        : new OptionalPubkey(settings.priceMint?.toBuffer()),
+    priceAmount: settings.priceAmount,
+    mediaEnabled: flag(settings.mediaEnabled),
+    adminOnly: flag(settings.adminOnly),
+    membersOnly: flag(settings.membersOnly),
+    private: flag(settings.isPrivate),
+    editWindow: settings.editWindow,
+    groupPicHash: settings.groupPicHash,
+  }).getInstruction(
+    JAB_ID,
+    editor,
+    groupThread,
+    await GroupMember.getKey(groupThread, editor),
+    await GroupAdmin.getKey(groupThread, editor)
+  );
 
   return instruction;
 };
@@ -299,14 +464,22 @@ export const editGroupThread = async (
  * @param groupOwner Owner of the group
  * @returns
  */
-export const addAdminToGroup = (
+export const addAdminToGroup = async (
   groupKey: PublicKey,
   adminToAdd: PublicKey,
   groupOwner: PublicKey
 ) => {
+  const groupAdmin = await GroupAdmin.getKey(groupKey, adminToAdd);
+
   const instruction = new addAdminToGroupInstruction({
     adminAddress: adminToAdd.toBuffer(),
-  }).getInstruction(JAB_ID, groupKey, groupOwner);
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    groupKey,
+    groupOwner,
+    groupAdmin
+  );
 
   return instruction;
 };
@@ -315,28 +488,36 @@ export const addAdminToGroup = (
  *
  * @param groupKey Address of the group thread
  * @param adminToRemove Address of the admin to remove
- * @param adminIndex Index of the admin in the Vec<Pubkey> of admins (cf GroupThread state)
  * @param groupOwner Owner of the group
  * @returns
  */
-export const removeAdminFromGroup = (
+export const removeAdminFromGroup = async (
   groupKey: PublicKey,
   adminToRemove: PublicKey,
-  adminIndex: number,
   groupOwner: PublicKey
 ) => {
+  const groupAdmin = await GroupAdmin.getKey(groupKey, adminToRemove);
+
   const instruction = new removeAdminFromGroupInstruction({
     adminAddress: adminToRemove.toBuffer(),
-    adminIndex: new BN(adminIndex),
-  }).getInstruction(JAB_ID, groupKey, groupOwner);
+  }).getInstruction(JAB_ID, groupKey, groupOwner, groupAdmin);
 
   return instruction;
 };
 
+/**
+ *
+ * @param groupName Name of the group
+ * @param owner User indexing the group (must sign)
+ * @param groupThread Key of the group thread
+ * @param feePayer Fee payer of the instruction
+ * @returns
+ */
 export const createGroupIndex = async (
   groupName: string,
   owner: PublicKey,
-  groupThread: PublicKey
+  groupThread: PublicKey,
+  feePayer = owner
 ) => {
   const groupIndex = await GroupThreadIndex.getKey(
     groupName,
@@ -346,48 +527,81 @@ export const createGroupIndex = async (
   const instruction = new createGroupIndexInstruction({
     groupName,
     groupThreadKey: groupThread.toBuffer(),
-    owner: owner.toBuffer(),
-  }).getInstruction(JAB_ID, SystemProgram.programId, groupIndex, owner);
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    groupIndex,
+    feePayer,
+    owner
+  );
 
   return instruction;
 };
 
 /**
  *
+ * @param connection The solana connection object to the RPC node
  * @param kind Message type
  * @param message Message to send
- * @param groupName Name of the group
  * @param sender User sending the message
  * @param groupThread Key of the group thread
- * @param destinationWallet Destination wallet of the group
- * @param messageAccount Account of the message
- * @param adminIndex Admin index
+ * @param nonce Nonce of the message, unique per sender (ignored in bucketed groups)
+ * @param repliesTo If the message is a reply to another message
+ * @param referrer Wallet of the referrer of the payment
+ * @param nextBucket Whether to append to the next bucket when the current one is full
+ * @returns
  */
 export const sendMessageGroup = async (
+  connection: Connection,
   kind: MessageType,
   message: Uint8Array,
-  groupName: string,
   sender: PublicKey,
   groupThread: PublicKey,
-  destinationWallet: PublicKey,
-  messageAccount: PublicKey,
-  adminIndex?: number,
-  repliesTo?: PublicKey
+  nonce: number,
+  repliesTo?: PublicKey,
+  referrer?: PublicKey,
+  nextBucket = false
 ) => {
+  const group = await GroupThread.retrieveFromKey(connection, groupThread);
+  const isBucketed = group.storageMode === StorageMode.Buckets;
+
+  const messageAccount = isBucketed
+    ? await MessageBucket.getKey(
+        groupThread,
+        group.currentBucket + (nextBucket ? 1 : 0)
+      )
+    : await Message.getGroupKey(groupThread, sender, nonce);
+
+  const payment = await getPaymentAccounts(
+    sender,
+    group.destinationWallet,
+    group.priceMint,
+    referrer
+  );
+
   const instruction = new sendMessageGroupInstruction({
     kind: kind as number,
     message: Array.from(message),
-    groupName,
-    adminIndex: adminIndex ? new BN(adminIndex) : undefined,
     repliesTo: repliesTo ? repliesTo.toBuffer() : PublicKey.default.toBuffer(),
+    nonce,
   }).getInstruction(
     JAB_ID,
     SystemProgram.programId,
     sender,
     groupThread,
-    destinationWallet,
+    group.destinationWallet,
     messageAccount,
-    SOL_VAULT
+    await Config.getVaultKey(),
+    await Config.getKey(),
+    await GroupMember.getKey(groupThread, sender),
+    await GroupModeration.getKey(groupThread, sender),
+    await GroupAdmin.getKey(groupThread, sender),
+    payment.splTokenProgram,
+    payment.tokenSource,
+    payment.tokenDestination,
+    payment.vaultTokenAccount,
+    payment.referrer,
+    payment.referrerStats
   );
 
   return instruction;
@@ -436,9 +650,11 @@ export const deleteMessage = async (
   message: PublicKey,
   messageIndex: number
 ) => {
+  const thread = await Thread.getKeys(sender, receiver);
+
   const instruction = new deleteMessageInstruction({
     messageIndex,
-  }).getInstruction(JAB_ID, sender, receiver, message);
+  }).getInstruction(JAB_ID, sender, receiver, message, thread);
 
   return instruction;
 };
@@ -447,28 +663,73 @@ export const deleteMessage = async (
  *
  * @param groupThread Group thread address
  * @param message Account of the message to delete
- * @param feePayer Fee payer (either owner, admin or original sender)
- * @param messageIndex Index of the message in the thread
- * @param owner Owner of the group
- * @param groupName Name of the group
- * @param adminIndex The index of the admin in the list of admins (if feePayer is an admin) | undefined
+ * @param feePayer Fee payer (either owner, admin, moderator or original sender)
+ * @param nonce Nonce of the message, or its index for messages sent before nonces
+ * @param sender Original sender of the message, refunded the rent of the message
  * @returns
  */
 export const deleteGroupMessage = async (
   groupThread: PublicKey,
   message: PublicKey,
   feePayer: PublicKey,
-  messageIndex: number,
-  owner: PublicKey,
-  groupName: string,
-  adminIndex: number
+  nonce: number,
+  sender: PublicKey
 ) => {
+  const isLegacy = !message.equals(
+    await Message.getGroupKey(groupThread, sender, nonce)
+  );
   const instruction = new deleteGroupMessageInstruction({
-    messageIndex,
-    owner: owner.toBuffer(),
-    adminIndex: adminIndex ? new BN(adminIndex) : undefined,
-    groupName,
-  }).getInstruction(JAB_ID, groupThread, message, feePayer);
+    nonce,
+  }).getInstruction(
+    JAB_ID,
+    groupThread,
+    message,
+    feePayer,
+    sender,
+    await GroupMember.getKey(groupThread, feePayer),
+    await GroupAdmin.getKey(groupThread, feePayer),
+    // Messages keyed by the legacy group counter are counted in the group thread
+    isLegacy ? groupThread : undefined
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param sender Sender of the tip
+ * @param receiver Receiver of the tip
+ * @param mint Mint of the tipped tokens
+ * @param amount Amount of tokens to tip
+ * @param referrer Wallet of the referrer of the payment
+ * @returns
+ */
+export const sendTip = async (
+  sender: PublicKey,
+  receiver: PublicKey,
+  mint: PublicKey,
+  amount: BN,
+  referrer?: PublicKey
+) => {
+  const payment = await getPaymentAccounts(sender, receiver, mint, referrer);
+
+  const instruction = new sendTipInstruction({
+    amount,
+  }).getInstruction(
+    JAB_ID,
+    TOKEN_PROGRAM_ID,
+    SystemProgram.programId,
+    await Profile.getKey(sender),
+    sender,
+    await Profile.getKey(receiver),
+    receiver,
+    await getTokenAccount(mint, sender),
+    await getTokenAccount(mint, receiver),
+    await Config.getKey(),
+    await getTokenAccount(mint, await Config.getVaultKey()),
+    payment.referrer,
+    payment.referrerStats
+  );
 
   return instruction;
 };
@@ -486,10 +747,43 @@ export const createSubscription = async (
   const subscription = await Subscription.getKey(subscriber, subscribedTo);
   const ix = new createSubscriptionInstruction({
     subscribedTo: subscribedTo.toBuffer(),
-  }).getInstruction(JAB_ID, subscription, subscriber, SystemProgram.programId);
+  }).getInstruction(
+    JAB_ID,
+    subscription,
+    subscriber,
+    SystemProgram.programId,
+    await Profile.getKey(subscribedTo)
+  );
+  return ix;
+};
+
+/**
+ *
+ * @param subscribedTo The key the user is subscribed to
+ * @param subscriber The subscribed user
+ * @returns
+ */
+export const deleteSubscription = async (
+  subscribedTo: PublicKey,
+  subscriber: PublicKey
+) => {
+  const subscription = await Subscription.getKey(subscriber, subscribedTo);
+  const ix = new deleteSubscriptionInstruction().getInstruction(
+    JAB_ID,
+    subscription,
+    subscriber,
+    await Profile.getKey(subscribedTo)
+  );
   return ix;
 };
 
+/**
+ * Members are listed from their `GroupMember` accounts, owners and admins do not need one
+ *
+ * @param connection The solana connection object to the RPC node
+ * @param group The group thread to fetch the members of
+ * @returns
+ */
 export const retrieveGroupMembers = async (
   connection: Connection,
   group: PublicKey
@@ -504,15 +798,13 @@ export const retrieveGroupMembers = async (
     {
       memcmp: {
         offset: 0,
-        bytes: "7",
+        bytes: "A",
       },
     },
   ];
   const result = await connection.getProgramAccounts(JAB_ID, { filters });
 
-  return result.map(
-    (acc) => GroupThreadIndex.deserialize(acc.account.data).owner
-  );
+  return result.map((acc) => GroupMember.deserialize(acc.account.data).member);
 };
 
 export const retrieveUserSubscription = async (
@@ -522,7 +814,7 @@ export const retrieveUserSubscription = async (
   let filters: MemcmpFilter[] = [
     {
       memcmp: {
-        offset: 1,
+        offset: 1 + 1,
         bytes: user.toBase58(),
       },
     },
@@ -536,3 +828,838 @@ export const retrieveUserSubscription = async (
   const result = await connection.getProgramAccounts(JAB_ID, { filters });
   return result.map((acc) => Subscription.deserialize(acc.account.data));
 };
+
+/**
+ *
+ * @param reactor User reacting to the message
+ * @param thread Thread or group thread of the message
+ * @param message Account of the message
+ * @param messageIndex Index of the message, or its sender nonce in groups
+ * @param reaction Reaction to add, undefined removes the existing reaction
+ * @param isGroup If `thread` is a group thread
+ * @returns
+ */
+export const reactToMessage = async (
+  reactor: PublicKey,
+  thread: PublicKey,
+  message: PublicKey,
+  messageIndex: number,
+  reaction?: ReactionType,
+  isGroup = false
+) => {
+  const instruction = new reactToMessageInstruction({
+    messageIndex,
+    reaction,
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    reactor,
+    thread,
+    message,
+    await Reaction.getKey(message, reactor),
+    isGroup ? await GroupMember.getKey(thread, reactor) : undefined,
+    isGroup ? await GroupModeration.getKey(thread, reactor) : undefined,
+    isGroup ? await GroupAdmin.getKey(thread, reactor) : undefined
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param sender Sender of the message
+ * @param receiver Receiver of the message
+ * @param messageIndex Index of the message in the thread
+ * @param message New content of the message
+ * @returns
+ */
+export const editMessage = async (
+  sender: PublicKey,
+  receiver: PublicKey,
+  messageIndex: number,
+  message: Uint8Array
+) => {
+  const [messageAccount] = await PublicKey.findProgramAddress(
+    Message.generateSeeds(messageIndex, sender, receiver),
+    JAB_ID
+  );
+
+  const instruction = new editMessageInstruction({
+    messageIndex,
+    message: Array.from(message),
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    sender,
+    receiver,
+    messageAccount,
+    await Profile.getKey(receiver)
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param sender Sender of the message
+ * @param groupThread Key of the group thread
+ * @param message Account of the message
+ * @param nonce Nonce of the message, or its index for messages sent before nonces
+ * @param content New content of the message
+ * @returns
+ */
+export const editGroupMessage = async (
+  sender: PublicKey,
+  groupThread: PublicKey,
+  message: PublicKey,
+  nonce: number,
+  content: Uint8Array
+) => {
+  const instruction = new editGroupMessageInstruction({
+    nonce,
+    message: Array.from(content),
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    sender,
+    groupThread,
+    message
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param participant One of the users of the thread
+ * @param otherUser The other user of the thread
+ * @returns
+ */
+export const closeThread = async (
+  participant: PublicKey,
+  otherUser: PublicKey
+) => {
+  const thread = await Thread.getKeys(participant, otherUser);
+
+  return new closeThreadInstruction().getInstruction(
+    JAB_ID,
+    participant,
+    thread
+  );
+};
+
+/**
+ *
+ * @param profileOwner Owner of the profile
+ * @returns
+ */
+export const closeProfile = async (profileOwner: PublicKey) => {
+  const profile = await Profile.getKey(profileOwner);
+
+  return new closeProfileInstruction().getInstruction(
+    JAB_ID,
+    profileOwner,
+    profile
+  );
+};
+
+/**
+ *
+ * @param groupOwner Owner of the group
+ * @param groupThread Key of the group thread
+ * @returns
+ */
+export const closeGroupThread = async (
+  groupOwner: PublicKey,
+  groupThread: PublicKey
+) => {
+  return new closeGroupThreadInstruction().getInstruction(
+    JAB_ID,
+    groupOwner,
+    groupThread
+  );
+};
+
+/**
+ *
+ * @param owner Owner of the group index
+ * @param groupName Name of the group
+ * @param groupThread Key of the group thread
+ * @returns
+ */
+export const closeGroupIndex = async (
+  owner: PublicKey,
+  groupName: string,
+  groupThread: PublicKey
+) => {
+  const groupIndex = await GroupThreadIndex.getKey(
+    groupName,
+    owner,
+    groupThread
+  );
+
+  return new closeGroupIndexInstruction().getInstruction(
+    JAB_ID,
+    owner,
+    groupIndex
+  );
+};
+
+/**
+ *
+ * @param member User joining the group
+ * @param groupThread Key of the group thread
+ * @returns
+ */
+export const joinGroup = async (member: PublicKey, groupThread: PublicKey) => {
+  return new joinGroupInstruction().getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    member,
+    groupThread,
+    await GroupMember.getKey(groupThread, member),
+    await GroupModeration.getKey(groupThread, member)
+  );
+};
+
+/**
+ *
+ * @param member User leaving the group
+ * @param groupThread Key of the group thread
+ * @returns
+ */
+export const leaveGroup = async (member: PublicKey, groupThread: PublicKey) => {
+  return new leaveGroupInstruction().getInstruction(
+    JAB_ID,
+    member,
+    await GroupMember.getKey(groupThread, member),
+    groupThread
+  );
+};
+
+/**
+ *
+ * @param creator Owner, admin or member allowed to invite
+ * @param groupThread Key of the group thread
+ * @param codeKey Public key of the invite code, the code is the keypair shared with invitees
+ * @param expiresAt Time after which the invite can no longer be redeemed, must be in the future
+ * @param maxUses Maximum number of times the invite can be redeemed, at least 1
+ * @returns
+ */
+export const createInvite = async (
+  creator: PublicKey,
+  groupThread: PublicKey,
+  codeKey: PublicKey,
+  expiresAt: BN,
+  maxUses: number
+) => {
+  const instruction = new createInviteInstruction({
+    codeKey: codeKey.toBuffer(),
+    expiresAt,
+    maxUses,
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    creator,
+    groupThread,
+    await Invite.getKey(groupThread, codeKey),
+    await GroupMember.getKey(groupThread, creator),
+    await GroupAdmin.getKey(groupThread, creator)
+  );
+
+  return instruction;
+};
+
+/**
+ * The transaction must be signed by the invite code keypair
+ *
+ * @param member User redeeming the invite
+ * @param groupThread Key of the group thread
+ * @param inviteCode Public key of the invite code
+ * @returns
+ */
+export const redeemInvite = async (
+  member: PublicKey,
+  groupThread: PublicKey,
+  inviteCode: PublicKey
+) => {
+  return new redeemInviteInstruction().getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    member,
+    groupThread,
+    await Invite.getKey(groupThread, inviteCode),
+    inviteCode,
+    await GroupMember.getKey(groupThread, member),
+    await GroupModeration.getKey(groupThread, member)
+  );
+};
+
+/**
+ *
+ * @param revoker Creator of the invite or member allowed to invite
+ * @param groupThread Key of the group thread
+ * @param codeKey Public key of the invite code
+ * @param creator Creator of the invite, refunded the rent of the invite
+ * @returns
+ */
+export const revokeInvite = async (
+  revoker: PublicKey,
+  groupThread: PublicKey,
+  codeKey: PublicKey,
+  creator: PublicKey
+) => {
+  return new revokeInviteInstruction().getInstruction(
+    JAB_ID,
+    revoker,
+    groupThread,
+    await Invite.getKey(groupThread, codeKey),
+    creator,
+    await GroupMember.getKey(groupThread, revoker),
+    await GroupAdmin.getKey(groupThread, revoker)
+  );
+};
+
+/**
+ *
+ * @param moderator Owner, admin or member allowed to ban
+ * @param groupThread Key of the group thread
+ * @param user User to ban
+ * @returns
+ */
+export const banMember = async (
+  moderator: PublicKey,
+  groupThread: PublicKey,
+  user: PublicKey
+) => {
+  const instruction = new banMemberInstruction({
+    user: user.toBuffer(),
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    moderator,
+    groupThread,
+    await GroupModeration.getKey(groupThread, user),
+    await GroupMember.getKey(groupThread, moderator),
+    await GroupAdmin.getKey(groupThread, moderator),
+    user,
+    await GroupMember.getKey(groupThread, user),
+    await GroupAdmin.getKey(groupThread, user)
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param connection The solana connection object to the RPC node
+ * @param moderator Owner, admin or member allowed to ban
+ * @param groupThread Key of the group thread
+ * @param user User to unban
+ * @returns
+ */
+export const unbanMember = async (
+  connection: Connection,
+  moderator: PublicKey,
+  groupThread: PublicKey,
+  user: PublicKey
+) => {
+  const moderation = await GroupModeration.retrieve(
+    connection,
+    groupThread,
+    user
+  );
+
+  return new unbanMemberInstruction().getInstruction(
+    JAB_ID,
+    moderator,
+    groupThread,
+    await GroupModeration.getKey(groupThread, user),
+    await GroupMember.getKey(groupThread, moderator),
+    await GroupAdmin.getKey(groupThread, moderator),
+    moderation.payer
+  );
+};
+
+/**
+ *
+ * @param moderator Owner, admin or member allowed to ban
+ * @param groupThread Key of the group thread
+ * @param user User to mute
+ * @param mutedUntil Time until which the user cannot post messages
+ * @returns
+ */
+export const muteMember = async (
+  moderator: PublicKey,
+  groupThread: PublicKey,
+  user: PublicKey,
+  mutedUntil: BN
+) => {
+  const instruction = new muteMemberInstruction({
+    user: user.toBuffer(),
+    mutedUntil,
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    moderator,
+    groupThread,
+    await GroupModeration.getKey(groupThread, user),
+    await GroupMember.getKey(groupThread, moderator),
+    await GroupAdmin.getKey(groupThread, moderator),
+    await GroupMember.getKey(groupThread, user),
+    await GroupAdmin.getKey(groupThread, user)
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param blocker User blocking
+ * @param blocked User to block
+ * @returns
+ */
+export const blockUser = async (blocker: PublicKey, blocked: PublicKey) => {
+  const instruction = new blockUserInstruction({
+    blocked: blocked.toBuffer(),
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    blocker,
+    await Block.getKey(blocker, blocked)
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param blocker User who blocked
+ * @param blocked User to unblock
+ * @returns
+ */
+export const unblockUser = async (blocker: PublicKey, blocked: PublicKey) => {
+  return new unblockUserInstruction().getInstruction(
+    JAB_ID,
+    blocker,
+    await Block.getKey(blocker, blocked)
+  );
+};
+
+/**
+ *
+ * @param receiver Receiver of the request
+ * @param requester Sender of the request
+ * @returns
+ */
+export const acceptThread = async (
+  receiver: PublicKey,
+  requester: PublicKey
+) => {
+  return new acceptThreadInstruction().getInstruction(
+    JAB_ID,
+    receiver,
+    await Thread.getKeys(receiver, requester)
+  );
+};
+
+/**
+ *
+ * @param connection The solana connection object to the RPC node
+ * @param receiver Receiver of the request
+ * @param requester Sender of the request
+ * @returns
+ */
+export const declineThread = async (
+  connection: Connection,
+  receiver: PublicKey,
+  requester: PublicKey
+) => {
+  const thread = await Thread.retrieve(connection, receiver, requester);
+  const [message] = await PublicKey.findProgramAddress(
+    Message.generateSeeds(0, receiver, requester),
+    JAB_ID
+  );
+
+  return new declineThreadInstruction().getInstruction(
+    JAB_ID,
+    receiver,
+    await Thread.getKeys(receiver, requester),
+    thread.payer,
+    message,
+    requester,
+    await Escrow.getKey(message)
+  );
+};
+
+/**
+ *
+ * @param owner Owner of the contact list
+ * @param contact User to add to the contact list
+ * @returns
+ */
+export const addContact = async (owner: PublicKey, contact: PublicKey) => {
+  const instruction = new addContactInstruction({
+    contact: contact.toBuffer(),
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    owner,
+    await Contact.getKey(owner, contact),
+    await Contact.getKey(contact, owner)
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param owner Owner of the contact list
+ * @param contact User to remove from the contact list
+ * @returns
+ */
+export const removeContact = async (owner: PublicKey, contact: PublicKey) => {
+  return new removeContactInstruction().getInstruction(
+    JAB_ID,
+    owner,
+    await Contact.getKey(owner, contact),
+    await Contact.getKey(contact, owner)
+  );
+};
+
+/**
+ *
+ * @param sender Sender of the unanswered message
+ * @param message Account of the message
+ * @returns
+ */
+export const refundEscrow = async (sender: PublicKey, message: PublicKey) => {
+  return new refundEscrowInstruction().getInstruction(
+    JAB_ID,
+    sender,
+    await Escrow.getKey(message)
+  );
+};
+
+/**
+ *
+ * @param feePayer Fee payer of the instruction
+ * @param upgradeAuthority Upgrade authority of the program (must sign)
+ * @param admin Authority allowed to update the config
+ * @param feeBps Protocol fee taken on message payments and tips (basis points)
+ * @param feeAuthority Authority allowed to withdraw the protocol fees
+ * @param referrerShareBps Share of the protocol fee paid to referrers (basis points)
+ * @returns
+ */
+export const initConfig = async (
+  feePayer: PublicKey,
+  upgradeAuthority: PublicKey,
+  admin: PublicKey,
+  feeBps: number,
+  feeAuthority: PublicKey,
+  referrerShareBps: number
+) => {
+  const [programData] = await PublicKey.findProgramAddress(
+    [JAB_ID.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_ID
+  );
+
+  const instruction = new initConfigInstruction({
+    admin: admin.toBuffer(),
+    feeBps,
+    feeAuthority: feeAuthority.toBuffer(),
+    referrerShareBps,
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    feePayer,
+    await Config.getKey(),
+    upgradeAuthority,
+    programData,
+    await Config.getVaultKey()
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param admin Current config admin
+ * @param newAdmin Authority allowed to update the config
+ * @param feeBps Protocol fee taken on message payments and tips (basis points)
+ * @param feeAuthority Authority allowed to withdraw the protocol fees
+ * @param referrerShareBps Share of the protocol fee paid to referrers (basis points)
+ * @param paused Whether messages and tips are disabled
+ * @param mint Mint to create the vault token account of, required before the mint can be used for payments
+ * @returns
+ */
+export const updateConfig = async (
+  admin: PublicKey,
+  newAdmin: PublicKey,
+  feeBps: number,
+  feeAuthority: PublicKey,
+  referrerShareBps: number,
+  paused: boolean,
+  mint?: PublicKey
+) => {
+  const vault = await Config.getVaultKey();
+  // The vault token account is only created when a mint is given
+  const withMint = (key: PublicKey) => (mint ? key : undefined);
+
+  const instruction = new updateConfigInstruction({
+    admin: newAdmin.toBuffer(),
+    feeBps,
+    feeAuthority: feeAuthority.toBuffer(),
+    referrerShareBps,
+    paused: paused ? 1 : 0,
+  }).getInstruction(
+    JAB_ID,
+    admin,
+    await Config.getKey(),
+    withMint(SystemProgram.programId),
+    withMint(TOKEN_PROGRAM_ID),
+    withMint(ASSOCIATED_TOKEN_PROGRAM_ID),
+    withMint(SYSVAR_RENT_PUBKEY),
+    withMint(vault),
+    mint,
+    mint && (await getTokenAccount(mint, vault))
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param feeAuthority Authority allowed to withdraw the protocol fees
+ * @param amount Amount to withdraw
+ * @param recipients Recipient wallets, or token accounts when withdrawing tokens
+ * @param sharesBps Share of each recipient (basis points, must sum to 10,000)
+ * @param mint Mint of the withdrawn tokens, SOL is withdrawn when undefined
+ * @returns
+ */
+export const withdrawFees = async (
+  feeAuthority: PublicKey,
+  amount: BN,
+  recipients: PublicKey[],
+  sharesBps: number[],
+  mint?: PublicKey
+) => {
+  const vault = await Config.getVaultKey();
+
+  const instruction = new withdrawFeesInstruction({
+    mint: mint?.toBuffer(),
+    amount,
+    sharesBps,
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    TOKEN_PROGRAM_ID,
+    feeAuthority,
+    await Config.getKey(),
+    vault,
+    mint ? await getTokenAccount(mint, vault) : vault,
+    recipients
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param wallet Wallet receiving the referral fees
+ * @returns
+ */
+export const registerReferrer = async (wallet: PublicKey) => {
+  return new registerReferrerInstruction().getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    wallet,
+    await Referrer.getKey(wallet)
+  );
+};
+
+/**
+ *
+ * @param groupOwner Owner of the group
+ * @param groupThread Key of the group thread
+ * @param newOwner Proposed owner, undefined cancels the pending transfer
+ * @returns
+ */
+export const proposeGroupOwner = async (
+  groupOwner: PublicKey,
+  groupThread: PublicKey,
+  newOwner?: PublicKey
+) => {
+  const instruction = new proposeGroupOwnerInstruction({
+    newOwner: newOwner?.toBuffer(),
+  }).getInstruction(JAB_ID, groupOwner, groupThread);
+
+  return instruction;
+};
+
+/**
+ *
+ * @param newOwner Pending owner of the group
+ * @param groupThread Key of the group thread
+ * @returns
+ */
+export const acceptGroupOwner = async (
+  newOwner: PublicKey,
+  groupThread: PublicKey
+) => {
+  return new acceptGroupOwnerInstruction().getInstruction(
+    JAB_ID,
+    newOwner,
+    groupThread
+  );
+};
+
+/**
+ *
+ * @param groupOwner Owner of the group
+ * @param groupThread Key of the group thread
+ * @param user User to grant the permissions to
+ * @param role Role of the user, admins are added with `addAdminToGroup`
+ * @param permissions Permissions granted on top of the role ones (`GroupPermission` flags)
+ * @returns
+ */
+export const grantGroupPermissions = async (
+  groupOwner: PublicKey,
+  groupThread: PublicKey,
+  user: PublicKey,
+  role: GroupRole,
+  permissions: number
+) => {
+  const instruction = new grantGroupPermissionsInstruction({
+    user: user.toBuffer(),
+    role,
+    permissions,
+  }).getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    groupOwner,
+    groupThread,
+    await GroupMember.getKey(groupThread, user)
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param groupOwner Owner of the group
+ * @param groupThread Key of the group thread
+ * @param user User to revoke the permissions of
+ * @param permissions Permissions to revoke (`GroupPermission` flags)
+ * @returns
+ */
+export const revokeGroupPermissions = async (
+  groupOwner: PublicKey,
+  groupThread: PublicKey,
+  user: PublicKey,
+  permissions: number
+) => {
+  const instruction = new revokeGroupPermissionsInstruction({
+    user: user.toBuffer(),
+    permissions,
+  }).getInstruction(
+    JAB_ID,
+    groupOwner,
+    groupThread,
+    await GroupMember.getKey(groupThread, user)
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param editor Owner or admin of the group
+ * @param groupThread Key of the group thread
+ * @param groupName New name of the group
+ * @returns
+ */
+export const renameGroup = async (
+  editor: PublicKey,
+  groupThread: PublicKey,
+  groupName: string
+) => {
+  const instruction = new renameGroupInstruction({
+    groupName,
+  }).getInstruction(
+    JAB_ID,
+    editor,
+    groupThread,
+    await GroupMember.getKey(groupThread, editor),
+    await GroupAdmin.getKey(groupThread, editor)
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param connection The solana connection object to the RPC node
+ * @param feePayer Fee payer (either owner, admin, moderator or original sender)
+ * @param groupThread Key of the group thread
+ * @param bucketIndex Index of the bucket of the message
+ * @param slot Slot of the message in the bucket
+ * @returns
+ */
+export const deleteBucketMessage = async (
+  connection: Connection,
+  feePayer: PublicKey,
+  groupThread: PublicKey,
+  bucketIndex: number,
+  slot: number
+) => {
+  const bucket = await MessageBucket.retrieve(
+    connection,
+    groupThread,
+    bucketIndex
+  );
+
+  const instruction = new deleteBucketMessageInstruction({
+    bucketIndex,
+    slot,
+  }).getInstruction(
+    JAB_ID,
+    groupThread,
+    await MessageBucket.getKey(groupThread, bucketIndex),
+    feePayer,
+    bucket.payer,
+    await GroupMember.getKey(groupThread, feePayer),
+    await GroupAdmin.getKey(groupThread, feePayer)
+  );
+
+  return instruction;
+};
+
+/**
+ *
+ * @param feePayer Fee payer of the instruction
+ * @param account Account to migrate to the current layout
+ * @param groupOwner Owner of the group, when migrating a group
+ * @param legacyAdmins Admins stored in the legacy group in order, when migrating a group
+ * @returns
+ */
+export const migrateAccount = async (
+  feePayer: PublicKey,
+  account: PublicKey,
+  groupOwner?: PublicKey,
+  legacyAdmins: PublicKey[] = []
+) => {
+  let groupAdmins: PublicKey[] = [];
+  for (let admin of legacyAdmins) {
+    groupAdmins.push(await GroupAdmin.getKey(account, admin));
+  }
+
+  return new migrateAccountInstruction().getInstruction(
+    JAB_ID,
+    SystemProgram.programId,
+    feePayer,
+    account,
+    groupOwner,
+    groupAdmins
+  );
+};
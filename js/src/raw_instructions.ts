@@ -34,7 +34,8 @@ export class deleteMessageInstruction {
     programId: PublicKey,
     sender: PublicKey,
     receiver: PublicKey,
-    message: PublicKey
+    message: PublicKey,
+    thread: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -53,6 +54,11 @@ export class deleteMessageInstruction {
       isSigner: false,
       isWritable: true,
     });
+    keys.push({
+      pubkey: thread,
+      isSigner: false,
+      isWritable: true,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -64,9 +70,8 @@ export class sendMessageGroupInstruction {
   tag: number;
   kind: number;
   repliesTo: Uint8Array;
-  adminIndex: BN | undefined;
-  groupName: string;
   message: number[];
+  nonce: number;
   static schema: Schema = new Map([
     [
       sendMessageGroupInstruction,
@@ -76,9 +81,8 @@ export class sendMessageGroupInstruction {
           ["tag", "u8"],
           ["kind", "u8"],
           ["repliesTo", [32]],
-          ["adminIndex", "u8"],
-          ["groupName", "string"],
           ["message", ["u8"]],
+          ["nonce", "u32"],
         ],
       },
     ],
@@ -86,16 +90,14 @@ export class sendMessageGroupInstruction {
   constructor(obj: {
     kind: number;
     repliesTo: Uint8Array;
-    adminIndex: BN | undefined;
-    groupName: string;
     message: number[];
+    nonce: number;
   }) {
     this.tag = 6;
     this.kind = obj.kind;
     this.repliesTo = obj.repliesTo;
-    this.adminIndex = obj.adminIndex;
-    this.groupName = obj.groupName;
     this.message = obj.message;
+    this.nonce = obj.nonce;
   }
   serialize(): Uint8Array {
     return serialize(sendMessageGroupInstruction.schema, this);
@@ -107,7 +109,17 @@ export class sendMessageGroupInstruction {
     groupThread: PublicKey,
     destinationWallet: PublicKey,
     message: PublicKey,
-    solVault: PublicKey
+    solVault: PublicKey,
+    config: PublicKey,
+    groupMember: PublicKey,
+    groupModeration: PublicKey,
+    groupAdmin: PublicKey,
+    splTokenProgram?: PublicKey,
+    tokenSource?: PublicKey,
+    tokenDestination?: PublicKey,
+    vaultTokenAccount?: PublicKey,
+    referrer?: PublicKey,
+    referrerStats?: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -141,6 +153,68 @@ export class sendMessageGroupInstruction {
       isSigner: false,
       isWritable: true,
     });
+    keys.push({
+      pubkey: config,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupModeration,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    if (!!splTokenProgram) {
+      keys.push({
+        pubkey: splTokenProgram,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!tokenSource) {
+      keys.push({
+        pubkey: tokenSource,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    if (!!tokenDestination) {
+      keys.push({
+        pubkey: tokenDestination,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    if (!!vaultTokenAccount) {
+      keys.push({
+        pubkey: vaultTokenAccount,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    if (!!referrer) {
+      keys.push({
+        pubkey: referrer,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    if (!!referrerStats) {
+      keys.push({
+        pubkey: referrerStats,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
@@ -184,7 +258,18 @@ export class sendMessageInstruction {
     thread: PublicKey,
     receiverProfile: PublicKey,
     message: PublicKey,
-    solVault: PublicKey
+    solVault: PublicKey,
+    config: PublicKey,
+    block: PublicKey,
+    contact: PublicKey,
+    escrow: PublicKey,
+    replyEscrow: PublicKey,
+    splTokenProgram?: PublicKey,
+    tokenSource?: PublicKey,
+    tokenDestination?: PublicKey,
+    vaultTokenAccount?: PublicKey,
+    referrer?: PublicKey,
+    referrerStats?: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -223,6 +308,73 @@ export class sendMessageInstruction {
       isSigner: false,
       isWritable: true,
     });
+    keys.push({
+      pubkey: config,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: block,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: contact,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: escrow,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: replyEscrow,
+      isSigner: false,
+      isWritable: true,
+    });
+    if (!!splTokenProgram) {
+      keys.push({
+        pubkey: splTokenProgram,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!tokenSource) {
+      keys.push({
+        pubkey: tokenSource,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    if (!!tokenDestination) {
+      keys.push({
+        pubkey: tokenDestination,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    if (!!vaultTokenAccount) {
+      keys.push({
+        pubkey: vaultTokenAccount,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    if (!!referrer) {
+      keys.push({
+        pubkey: referrer,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    if (!!referrerStats) {
+      keys.push({
+        pubkey: referrerStats,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
@@ -304,10 +456,7 @@ export class createProfileInstruction {
 }
 export class deleteGroupMessageInstruction {
   tag: number;
-  messageIndex: number;
-  owner: Uint8Array;
-  adminIndex: BN | undefined;
-  groupName: string;
+  nonce: number;
   static schema: Schema = new Map([
     [
       deleteGroupMessageInstruction,
@@ -315,25 +464,14 @@ export class deleteGroupMessageInstruction {
         kind: "struct",
         fields: [
           ["tag", "u8"],
-          ["messageIndex", "u32"],
-          ["owner", [32]],
-          ["adminIndex", "u8"],
-          ["groupName", "string"],
+          ["nonce", "u32"],
         ],
       },
     ],
   ]);
-  constructor(obj: {
-    messageIndex: number;
-    owner: Uint8Array;
-    adminIndex: BN | undefined;
-    groupName: string;
-  }) {
+  constructor(obj: { nonce: number }) {
     this.tag = 11;
-    this.messageIndex = obj.messageIndex;
-    this.owner = obj.owner;
-    this.adminIndex = obj.adminIndex;
-    this.groupName = obj.groupName;
+    this.nonce = obj.nonce;
   }
   serialize(): Uint8Array {
     return serialize(deleteGroupMessageInstruction.schema, this);
@@ -342,7 +480,11 @@ export class deleteGroupMessageInstruction {
     programId: PublicKey,
     groupThread: PublicKey,
     message: PublicKey,
-    feePayer: PublicKey
+    feePayer: PublicKey,
+    sender: PublicKey,
+    groupMember: PublicKey,
+    groupAdmin: PublicKey,
+    legacyGroupThread?: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -361,6 +503,28 @@ export class deleteGroupMessageInstruction {
       isSigner: true,
       isWritable: true,
     });
+    keys.push({
+      pubkey: sender,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    if (!!legacyGroupThread) {
+      keys.push({
+        pubkey: legacyGroupThread,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
@@ -393,12 +557,17 @@ export class sendTipInstruction {
   getInstruction(
     programId: PublicKey,
     splTokenProgram: PublicKey,
+    systemProgram: PublicKey,
     senderProfile: PublicKey,
     sender: PublicKey,
     receiverProfile: PublicKey,
     receiver: PublicKey,
     tokenSource: PublicKey,
-    tokenDestination: PublicKey
+    tokenDestination: PublicKey,
+    config: PublicKey,
+    vaultTokenAccount: PublicKey,
+    referrer?: PublicKey,
+    referrerStats?: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -407,6 +576,11 @@ export class sendTipInstruction {
       isSigner: false,
       isWritable: false,
     });
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
     keys.push({
       pubkey: senderProfile,
       isSigner: false,
@@ -437,6 +611,30 @@ export class sendTipInstruction {
       isSigner: false,
       isWritable: true,
     });
+    keys.push({
+      pubkey: config,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: vaultTokenAccount,
+      isSigner: false,
+      isWritable: true,
+    });
+    if (!!referrer) {
+      keys.push({
+        pubkey: referrer,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    if (!!referrerStats) {
+      keys.push({
+        pubkey: referrerStats,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
     return new TransactionInstruction({
       keys,
       programId,
@@ -523,11 +721,18 @@ export class addAdminToGroupInstruction {
   }
   getInstruction(
     programId: PublicKey,
+    systemProgram: PublicKey,
     groupThread: PublicKey,
-    groupOwner: PublicKey
+    groupOwner: PublicKey,
+    groupAdmin: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
     keys.push({
       pubkey: groupThread,
       isSigner: false,
@@ -538,6 +743,11 @@ export class addAdminToGroupInstruction {
       isSigner: true,
       isWritable: true,
     });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: true,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -545,15 +755,25 @@ export class addAdminToGroupInstruction {
     });
   }
 }
+export class OptionalPubkey {
+  value: Uint8Array | undefined;
+  constructor(value: Uint8Array | undefined) {
+    this.value = value;
+  }
+}
 export class editGroupThreadInstruction {
   tag: number;
-  visible: number;
-  destinationWallet: Uint8Array;
-  lamportsPerMessage: BN;
-  owner: Uint8Array;
-  mediaEnabled: number;
-  adminOnly: number;
-  groupPicHash: string;
+  visible: number | undefined;
+  destinationWallet: Uint8Array | undefined;
+  lamportsPerMessage: BN | undefined;
+  priceMint: OptionalPubkey | undefined;
+  priceAmount: BN | undefined;
+  mediaEnabled: number | undefined;
+  adminOnly: number | undefined;
+  membersOnly: number | undefined;
+  private: number | undefined;
+  editWindow: BN | undefined;
+  groupPicHash: string | undefined;
   static schema: Schema = new Map([
     [
       editGroupThreadInstruction,
@@ -561,33 +781,52 @@ export class editGroupThreadInstruction {
         kind: "struct",
         fields: [
           ["tag", "u8"],
-          ["visible", "u8"],
-          ["destinationWallet", [32]],
-          ["lamportsPerMessage", "u64"],
-          ["owner", [32]],
-          ["mediaEnabled", "u8"],
-          ["adminOnly", "u8"],
-          ["groupPicHash", "string"],
+          ["visible", { kind: "option", type: "u8" }],
+          ["destinationWallet", { kind: "option", type: [32] }],
+          ["lamportsPerMessage", { kind: "option", type: "u64" }],
+          ["priceMint", { kind: "option", type: OptionalPubkey }],
+          ["priceAmount", { kind: "option", type: "u64" }],
+          ["mediaEnabled", { kind: "option", type: "u8" }],
+          ["adminOnly", { kind: "option", type: "u8" }],
+          ["membersOnly", { kind: "option", type: "u8" }],
+          ["private", { kind: "option", type: "u8" }],
+          ["editWindow", { kind: "option", type: "u64" }],
+          ["groupPicHash", { kind: "option", type: "string" }],
         ],
       },
     ],
+    [
+      OptionalPubkey,
+      {
+        kind: "struct",
+        fields: [["value", { kind: "option", type: [32] }]],
+      },
+    ],
   ]);
   constructor(obj: {
-    visible: number;
-    destinationWallet: Uint8Array;
-    lamportsPerMessage: BN;
-    owner: Uint8Array;
-    mediaEnabled: number;
-    adminOnly: number;
-    groupPicHash: string;
+    visible: number | undefined;
+    destinationWallet: Uint8Array | undefined;
+    lamportsPerMessage: BN | undefined;
+    priceMint: OptionalPubkey | undefined;
+    priceAmount: BN | undefined;
+    mediaEnabled: number | undefined;
+    adminOnly: number | undefined;
+    membersOnly: number | undefined;
+    private: number | undefined;
+    editWindow: BN | undefined;
+    groupPicHash: string | undefined;
   }) {
     this.tag = 5;
     this.visible = obj.visible;
     this.destinationWallet = obj.destinationWallet;
     this.lamportsPerMessage = obj.lamportsPerMessage;
-    this.owner = obj.owner;
+    this.priceMint = obj.priceMint;
+    this.priceAmount = obj.priceAmount;
     this.mediaEnabled = obj.mediaEnabled;
     this.adminOnly = obj.adminOnly;
+    this.membersOnly = obj.membersOnly;
+    this.private = obj.private;
+    this.editWindow = obj.editWindow;
     this.groupPicHash = obj.groupPicHash;
   }
   serialize(): Uint8Array {
@@ -595,13 +834,15 @@ export class editGroupThreadInstruction {
   }
   getInstruction(
     programId: PublicKey,
-    groupOwner: PublicKey,
-    groupThread: PublicKey
+    editor: PublicKey,
+    groupThread: PublicKey,
+    groupMember: PublicKey,
+    groupAdmin: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
     keys.push({
-      pubkey: groupOwner,
+      pubkey: editor,
       isSigner: true,
       isWritable: true,
     });
@@ -610,6 +851,16 @@ export class editGroupThreadInstruction {
       isSigner: false,
       isWritable: true,
     });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -621,7 +872,6 @@ export class createGroupIndexInstruction {
   tag: number;
   groupName: string;
   groupThreadKey: Uint8Array;
-  owner: Uint8Array;
   static schema: Schema = new Map([
     [
       createGroupIndexInstruction,
@@ -631,20 +881,14 @@ export class createGroupIndexInstruction {
           ["tag", "u8"],
           ["groupName", "string"],
           ["groupThreadKey", [32]],
-          ["owner", [32]],
         ],
       },
     ],
   ]);
-  constructor(obj: {
-    groupName: string;
-    groupThreadKey: Uint8Array;
-    owner: Uint8Array;
-  }) {
+  constructor(obj: { groupName: string; groupThreadKey: Uint8Array }) {
     this.tag = 9;
     this.groupName = obj.groupName;
     this.groupThreadKey = obj.groupThreadKey;
-    this.owner = obj.owner;
   }
   serialize(): Uint8Array {
     return serialize(createGroupIndexInstruction.schema, this);
@@ -653,7 +897,8 @@ export class createGroupIndexInstruction {
     programId: PublicKey,
     systemProgram: PublicKey,
     groupThreadIndex: PublicKey,
-    feePayer: PublicKey
+    feePayer: PublicKey,
+    owner: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -672,6 +917,11 @@ export class createGroupIndexInstruction {
       isSigner: true,
       isWritable: true,
     });
+    keys.push({
+      pubkey: owner,
+      isSigner: true,
+      isWritable: false,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -685,7 +935,12 @@ export class setUserProfileInstruction {
   displayDomainName: string;
   bio: string;
   lamportsPerMessage: BN;
+  priceMint: Uint8Array | undefined;
+  priceAmount: BN;
   allowDm: number;
+  escrowEnabled: number;
+  escrowPeriod: BN;
+  editWindow: BN | undefined;
   static schema: Schema = new Map([
     [
       setUserProfileInstruction,
@@ -697,7 +952,12 @@ export class setUserProfileInstruction {
           ["displayDomainName", "string"],
           ["bio", "string"],
           ["lamportsPerMessage", "u64"],
+          ["priceMint", { kind: "option", type: [32] }],
+          ["priceAmount", "u64"],
           ["allowDm", "u8"],
+          ["escrowEnabled", "u8"],
+          ["escrowPeriod", "u64"],
+          ["editWindow", { kind: "option", type: "u64" }],
         ],
       },
     ],
@@ -707,25 +967,41 @@ export class setUserProfileInstruction {
     displayDomainName: string;
     bio: string;
     lamportsPerMessage: BN;
+    priceMint: Uint8Array | undefined;
+    priceAmount: BN;
     allowDm: number;
+    escrowEnabled: number;
+    escrowPeriod: BN;
+    editWindow: BN | undefined;
   }) {
     this.tag = 2;
     this.pictureHash = obj.pictureHash;
     this.displayDomainName = obj.displayDomainName;
     this.bio = obj.bio;
     this.lamportsPerMessage = obj.lamportsPerMessage;
+    this.priceMint = obj.priceMint;
+    this.priceAmount = obj.priceAmount;
     this.allowDm = obj.allowDm;
+    this.escrowEnabled = obj.escrowEnabled;
+    this.escrowPeriod = obj.escrowPeriod;
+    this.editWindow = obj.editWindow;
   }
   serialize(): Uint8Array {
     return serialize(setUserProfileInstruction.schema, this);
   }
   getInstruction(
     programId: PublicKey,
+    systemProgram: PublicKey,
     profileOwner: PublicKey,
     profile: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
     keys.push({
       pubkey: profileOwner,
       isSigner: true,
@@ -769,7 +1045,8 @@ export class createSubscriptionInstruction {
     programId: PublicKey,
     subscription: PublicKey,
     subscriber: PublicKey,
-    systemProgram: PublicKey
+    systemProgram: PublicKey,
+    subscribedToProfile: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -788,6 +1065,11 @@ export class createSubscriptionInstruction {
       isSigner: false,
       isWritable: false,
     });
+    keys.push({
+      pubkey: subscribedToProfile,
+      isSigner: false,
+      isWritable: true,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -798,7 +1080,6 @@ export class createSubscriptionInstruction {
 export class removeAdminFromGroupInstruction {
   tag: number;
   adminAddress: Uint8Array;
-  adminIndex: BN;
   static schema: Schema = new Map([
     [
       removeAdminFromGroupInstruction,
@@ -807,15 +1088,13 @@ export class removeAdminFromGroupInstruction {
         fields: [
           ["tag", "u8"],
           ["adminAddress", [32]],
-          ["adminIndex", "u64"],
         ],
       },
     ],
   ]);
-  constructor(obj: { adminAddress: Uint8Array; adminIndex: BN }) {
+  constructor(obj: { adminAddress: Uint8Array }) {
     this.tag = 8;
     this.adminAddress = obj.adminAddress;
-    this.adminIndex = obj.adminIndex;
   }
   serialize(): Uint8Array {
     return serialize(removeAdminFromGroupInstruction.schema, this);
@@ -823,7 +1102,8 @@ export class removeAdminFromGroupInstruction {
   getInstruction(
     programId: PublicKey,
     groupThread: PublicKey,
-    groupOwner: PublicKey
+    groupOwner: PublicKey,
+    groupAdmin: PublicKey
   ): TransactionInstruction {
     const data = Buffer.from(this.serialize());
     let keys: AccountKey[] = [];
@@ -837,6 +1117,11 @@ export class removeAdminFromGroupInstruction {
       isSigner: true,
       isWritable: true,
     });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: true,
+    });
     return new TransactionInstruction({
       keys,
       programId,
@@ -847,13 +1132,18 @@ export class removeAdminFromGroupInstruction {
 export class createGroupThreadInstruction {
   tag: number;
   visible: number;
+  groupId: Uint8Array;
   groupName: string;
   destinationWallet: Uint8Array;
   lamportsPerMessage: BN;
-  admins: Uint8Array[];
+  priceMint: Uint8Array | undefined;
+  priceAmount: BN;
   owner: Uint8Array;
   mediaEnabled: number;
   adminOnly: number;
+  membersOnly: number;
+  private: number;
+  storageMode: number;
   static schema: Schema = new Map([
     [
       createGroupThreadInstruction,
@@ -862,36 +1152,51 @@ export class createGroupThreadInstruction {
         fields: [
           ["tag", "u8"],
           ["visible", "u8"],
+          ["groupId", [32]],
           ["groupName", "string"],
           ["destinationWallet", [32]],
           ["lamportsPerMessage", "u64"],
-          ["admins", [[32]]],
+          ["priceMint", { kind: "option", type: [32] }],
+          ["priceAmount", "u64"],
           ["owner", [32]],
           ["mediaEnabled", "u8"],
           ["adminOnly", "u8"],
+          ["membersOnly", "u8"],
+          ["private", "u8"],
+          ["storageMode", "u8"],
         ],
       },
     ],
   ]);
   constructor(obj: {
     visible: number;
+    groupId: Uint8Array;
     groupName: string;
     destinationWallet: Uint8Array;
     lamportsPerMessage: BN;
-    admins: Uint8Array[];
+    priceMint: Uint8Array | undefined;
+    priceAmount: BN;
     owner: Uint8Array;
     mediaEnabled: number;
     adminOnly: number;
+    membersOnly: number;
+    private: number;
+    storageMode: number;
   }) {
     this.tag = 4;
     this.visible = obj.visible;
+    this.groupId = obj.groupId;
     this.groupName = obj.groupName;
     this.destinationWallet = obj.destinationWallet;
     this.lamportsPerMessage = obj.lamportsPerMessage;
-    this.admins = obj.admins;
+    this.priceMint = obj.priceMint;
+    this.priceAmount = obj.priceAmount;
     this.owner = obj.owner;
     this.mediaEnabled = obj.mediaEnabled;
     this.adminOnly = obj.adminOnly;
+    this.membersOnly = obj.membersOnly;
+    this.private = obj.private;
+    this.storageMode = obj.storageMode;
   }
   serialize(): Uint8Array {
     return serialize(createGroupThreadInstruction.schema, this);
@@ -926,3 +1231,2073 @@ export class createGroupThreadInstruction {
     });
   }
 }
+export class reactToMessageInstruction {
+  tag: number;
+  messageIndex: number;
+  reaction: number | undefined;
+  static schema: Schema = new Map([
+    [
+      reactToMessageInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["messageIndex", "u32"],
+          ["reaction", { kind: "option", type: "u8" }],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { messageIndex: number; reaction: number | undefined }) {
+    this.tag = 14;
+    this.messageIndex = obj.messageIndex;
+    this.reaction = obj.reaction;
+  }
+  serialize(): Uint8Array {
+    return serialize(reactToMessageInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    reactor: PublicKey,
+    thread: PublicKey,
+    message: PublicKey,
+    reaction: PublicKey,
+    groupMember?: PublicKey,
+    groupModeration?: PublicKey,
+    groupAdmin?: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: reactor,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: thread,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: message,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: reaction,
+      isSigner: false,
+      isWritable: true,
+    });
+    if (!!groupMember) {
+      keys.push({
+        pubkey: groupMember,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!groupModeration) {
+      keys.push({
+        pubkey: groupModeration,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!groupAdmin) {
+      keys.push({
+        pubkey: groupAdmin,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class editMessageInstruction {
+  tag: number;
+  messageIndex: number;
+  message: number[];
+  static schema: Schema = new Map([
+    [
+      editMessageInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["messageIndex", "u32"],
+          ["message", ["u8"]],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { messageIndex: number; message: number[] }) {
+    this.tag = 15;
+    this.messageIndex = obj.messageIndex;
+    this.message = obj.message;
+  }
+  serialize(): Uint8Array {
+    return serialize(editMessageInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    sender: PublicKey,
+    receiver: PublicKey,
+    message: PublicKey,
+    receiverProfile: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: sender,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: receiver,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: message,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: receiverProfile,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class editGroupMessageInstruction {
+  tag: number;
+  nonce: number;
+  message: number[];
+  static schema: Schema = new Map([
+    [
+      editGroupMessageInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["nonce", "u32"],
+          ["message", ["u8"]],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { nonce: number; message: number[] }) {
+    this.tag = 16;
+    this.nonce = obj.nonce;
+    this.message = obj.message;
+  }
+  serialize(): Uint8Array {
+    return serialize(editGroupMessageInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    sender: PublicKey,
+    groupThread: PublicKey,
+    message: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: sender,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: message,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class closeThreadInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      closeThreadInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 17;
+  }
+  serialize(): Uint8Array {
+    return serialize(closeThreadInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    participant: PublicKey,
+    thread: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: participant,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: thread,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class closeProfileInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      closeProfileInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 18;
+  }
+  serialize(): Uint8Array {
+    return serialize(closeProfileInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    profileOwner: PublicKey,
+    profile: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: profileOwner,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: profile,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class closeGroupThreadInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      closeGroupThreadInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 19;
+  }
+  serialize(): Uint8Array {
+    return serialize(closeGroupThreadInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    groupOwner: PublicKey,
+    groupThread: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: groupOwner,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class closeGroupIndexInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      closeGroupIndexInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 20;
+  }
+  serialize(): Uint8Array {
+    return serialize(closeGroupIndexInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    owner: PublicKey,
+    groupThreadIndex: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: owner,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThreadIndex,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class deleteSubscriptionInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      deleteSubscriptionInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 21;
+  }
+  serialize(): Uint8Array {
+    return serialize(deleteSubscriptionInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    subscription: PublicKey,
+    subscriber: PublicKey,
+    subscribedToProfile: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: subscription,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: subscriber,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: subscribedToProfile,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class joinGroupInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      joinGroupInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 22;
+  }
+  serialize(): Uint8Array {
+    return serialize(joinGroupInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    member: PublicKey,
+    groupThread: PublicKey,
+    groupMember: PublicKey,
+    groupModeration: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: member,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupModeration,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class leaveGroupInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      leaveGroupInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 23;
+  }
+  serialize(): Uint8Array {
+    return serialize(leaveGroupInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    member: PublicKey,
+    groupMember: PublicKey,
+    groupThread: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: member,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class createInviteInstruction {
+  tag: number;
+  codeKey: Uint8Array;
+  expiresAt: BN;
+  maxUses: number;
+  static schema: Schema = new Map([
+    [
+      createInviteInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["codeKey", [32]],
+          ["expiresAt", "u64"],
+          ["maxUses", "u32"],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { codeKey: Uint8Array; expiresAt: BN; maxUses: number }) {
+    this.tag = 24;
+    this.codeKey = obj.codeKey;
+    this.expiresAt = obj.expiresAt;
+    this.maxUses = obj.maxUses;
+  }
+  serialize(): Uint8Array {
+    return serialize(createInviteInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    creator: PublicKey,
+    groupThread: PublicKey,
+    invite: PublicKey,
+    groupMember: PublicKey,
+    groupAdmin: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: creator,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: invite,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class redeemInviteInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      redeemInviteInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 25;
+  }
+  serialize(): Uint8Array {
+    return serialize(redeemInviteInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    member: PublicKey,
+    groupThread: PublicKey,
+    invite: PublicKey,
+    inviteCode: PublicKey,
+    groupMember: PublicKey,
+    groupModeration: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: member,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: invite,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: inviteCode,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupModeration,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class banMemberInstruction {
+  tag: number;
+  user: Uint8Array;
+  static schema: Schema = new Map([
+    [
+      banMemberInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["user", [32]],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { user: Uint8Array }) {
+    this.tag = 26;
+    this.user = obj.user;
+  }
+  serialize(): Uint8Array {
+    return serialize(banMemberInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    moderator: PublicKey,
+    groupThread: PublicKey,
+    groupModeration: PublicKey,
+    groupMember: PublicKey,
+    groupAdmin: PublicKey,
+    user: PublicKey,
+    userGroupMember: PublicKey,
+    userGroupAdmin: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: moderator,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupModeration,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: user,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: userGroupMember,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: userGroupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class unbanMemberInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      unbanMemberInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 27;
+  }
+  serialize(): Uint8Array {
+    return serialize(unbanMemberInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    moderator: PublicKey,
+    groupThread: PublicKey,
+    groupModeration: PublicKey,
+    groupMember: PublicKey,
+    groupAdmin: PublicKey,
+    moderationPayer: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: moderator,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupModeration,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: moderationPayer,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class muteMemberInstruction {
+  tag: number;
+  user: Uint8Array;
+  mutedUntil: BN;
+  static schema: Schema = new Map([
+    [
+      muteMemberInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["user", [32]],
+          ["mutedUntil", "u64"],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { user: Uint8Array; mutedUntil: BN }) {
+    this.tag = 28;
+    this.user = obj.user;
+    this.mutedUntil = obj.mutedUntil;
+  }
+  serialize(): Uint8Array {
+    return serialize(muteMemberInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    moderator: PublicKey,
+    groupThread: PublicKey,
+    groupModeration: PublicKey,
+    groupMember: PublicKey,
+    groupAdmin: PublicKey,
+    userGroupMember: PublicKey,
+    userGroupAdmin: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: moderator,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupModeration,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: userGroupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: userGroupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class blockUserInstruction {
+  tag: number;
+  blocked: Uint8Array;
+  static schema: Schema = new Map([
+    [
+      blockUserInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["blocked", [32]],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { blocked: Uint8Array }) {
+    this.tag = 29;
+    this.blocked = obj.blocked;
+  }
+  serialize(): Uint8Array {
+    return serialize(blockUserInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    blocker: PublicKey,
+    block: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: blocker,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: block,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class unblockUserInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      unblockUserInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 30;
+  }
+  serialize(): Uint8Array {
+    return serialize(unblockUserInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    blocker: PublicKey,
+    block: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: blocker,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: block,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class acceptThreadInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      acceptThreadInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 31;
+  }
+  serialize(): Uint8Array {
+    return serialize(acceptThreadInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    receiver: PublicKey,
+    thread: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: receiver,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: thread,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class declineThreadInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      declineThreadInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 32;
+  }
+  serialize(): Uint8Array {
+    return serialize(declineThreadInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    receiver: PublicKey,
+    thread: PublicKey,
+    threadPayer: PublicKey,
+    message: PublicKey,
+    requester: PublicKey,
+    escrow: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: receiver,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: thread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: threadPayer,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: message,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: requester,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: escrow,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class addContactInstruction {
+  tag: number;
+  contact: Uint8Array;
+  static schema: Schema = new Map([
+    [
+      addContactInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["contact", [32]],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { contact: Uint8Array }) {
+    this.tag = 33;
+    this.contact = obj.contact;
+  }
+  serialize(): Uint8Array {
+    return serialize(addContactInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    owner: PublicKey,
+    contact: PublicKey,
+    reverseContact: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: owner,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: contact,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: reverseContact,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class removeContactInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      removeContactInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 34;
+  }
+  serialize(): Uint8Array {
+    return serialize(removeContactInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    owner: PublicKey,
+    contact: PublicKey,
+    reverseContact: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: owner,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: contact,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: reverseContact,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class refundEscrowInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      refundEscrowInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 35;
+  }
+  serialize(): Uint8Array {
+    return serialize(refundEscrowInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    sender: PublicKey,
+    escrow: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: sender,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: escrow,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class initConfigInstruction {
+  tag: number;
+  admin: Uint8Array;
+  feeBps: number;
+  feeAuthority: Uint8Array;
+  referrerShareBps: number;
+  static schema: Schema = new Map([
+    [
+      initConfigInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["admin", [32]],
+          ["feeBps", "u16"],
+          ["feeAuthority", [32]],
+          ["referrerShareBps", "u16"],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: {
+    admin: Uint8Array;
+    feeBps: number;
+    feeAuthority: Uint8Array;
+    referrerShareBps: number;
+  }) {
+    this.tag = 36;
+    this.admin = obj.admin;
+    this.feeBps = obj.feeBps;
+    this.feeAuthority = obj.feeAuthority;
+    this.referrerShareBps = obj.referrerShareBps;
+  }
+  serialize(): Uint8Array {
+    return serialize(initConfigInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    feePayer: PublicKey,
+    config: PublicKey,
+    upgradeAuthority: PublicKey,
+    programData: PublicKey,
+    vault: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: config,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: upgradeAuthority,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: programData,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: vault,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class updateConfigInstruction {
+  tag: number;
+  admin: Uint8Array;
+  feeBps: number;
+  feeAuthority: Uint8Array;
+  referrerShareBps: number;
+  paused: number;
+  static schema: Schema = new Map([
+    [
+      updateConfigInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["admin", [32]],
+          ["feeBps", "u16"],
+          ["feeAuthority", [32]],
+          ["referrerShareBps", "u16"],
+          ["paused", "u8"],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: {
+    admin: Uint8Array;
+    feeBps: number;
+    feeAuthority: Uint8Array;
+    referrerShareBps: number;
+    paused: number;
+  }) {
+    this.tag = 37;
+    this.admin = obj.admin;
+    this.feeBps = obj.feeBps;
+    this.feeAuthority = obj.feeAuthority;
+    this.referrerShareBps = obj.referrerShareBps;
+    this.paused = obj.paused;
+  }
+  serialize(): Uint8Array {
+    return serialize(updateConfigInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    admin: PublicKey,
+    config: PublicKey,
+    systemProgram?: PublicKey,
+    splTokenProgram?: PublicKey,
+    associatedTokenProgram?: PublicKey,
+    rentSysvar?: PublicKey,
+    vault?: PublicKey,
+    mint?: PublicKey,
+    vaultTokenAccount?: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: admin,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: config,
+      isSigner: false,
+      isWritable: true,
+    });
+    if (!!systemProgram) {
+      keys.push({
+        pubkey: systemProgram,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!splTokenProgram) {
+      keys.push({
+        pubkey: splTokenProgram,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!associatedTokenProgram) {
+      keys.push({
+        pubkey: associatedTokenProgram,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!rentSysvar) {
+      keys.push({
+        pubkey: rentSysvar,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!vault) {
+      keys.push({
+        pubkey: vault,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!mint) {
+      keys.push({
+        pubkey: mint,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    if (!!vaultTokenAccount) {
+      keys.push({
+        pubkey: vaultTokenAccount,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class withdrawFeesInstruction {
+  tag: number;
+  mint: Uint8Array | undefined;
+  amount: BN;
+  sharesBps: number[];
+  static schema: Schema = new Map([
+    [
+      withdrawFeesInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["mint", { kind: "option", type: [32] }],
+          ["amount", "u64"],
+          ["sharesBps", ["u16"]],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: {
+    mint: Uint8Array | undefined;
+    amount: BN;
+    sharesBps: number[];
+  }) {
+    this.tag = 38;
+    this.mint = obj.mint;
+    this.amount = obj.amount;
+    this.sharesBps = obj.sharesBps;
+  }
+  serialize(): Uint8Array {
+    return serialize(withdrawFeesInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    splTokenProgram: PublicKey,
+    feeAuthority: PublicKey,
+    config: PublicKey,
+    vault: PublicKey,
+    source: PublicKey,
+    recipients: PublicKey[]
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: splTokenProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: feeAuthority,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: config,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: vault,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: source,
+      isSigner: false,
+      isWritable: true,
+    });
+    for (let k of recipients) {
+      keys.push({
+        pubkey: k,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class registerReferrerInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      registerReferrerInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 39;
+  }
+  serialize(): Uint8Array {
+    return serialize(registerReferrerInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    wallet: PublicKey,
+    referrer: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: wallet,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: referrer,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class proposeGroupOwnerInstruction {
+  tag: number;
+  newOwner: Uint8Array | undefined;
+  static schema: Schema = new Map([
+    [
+      proposeGroupOwnerInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["newOwner", { kind: "option", type: [32] }],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { newOwner: Uint8Array | undefined }) {
+    this.tag = 40;
+    this.newOwner = obj.newOwner;
+  }
+  serialize(): Uint8Array {
+    return serialize(proposeGroupOwnerInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    groupOwner: PublicKey,
+    groupThread: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: groupOwner,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class acceptGroupOwnerInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      acceptGroupOwnerInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 41;
+  }
+  serialize(): Uint8Array {
+    return serialize(acceptGroupOwnerInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    newOwner: PublicKey,
+    groupThread: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: newOwner,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class grantGroupPermissionsInstruction {
+  tag: number;
+  user: Uint8Array;
+  role: number;
+  permissions: number;
+  static schema: Schema = new Map([
+    [
+      grantGroupPermissionsInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["user", [32]],
+          ["role", "u8"],
+          ["permissions", "u8"],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { user: Uint8Array; role: number; permissions: number }) {
+    this.tag = 42;
+    this.user = obj.user;
+    this.role = obj.role;
+    this.permissions = obj.permissions;
+  }
+  serialize(): Uint8Array {
+    return serialize(grantGroupPermissionsInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    groupOwner: PublicKey,
+    groupThread: PublicKey,
+    groupMember: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupOwner,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class revokeGroupPermissionsInstruction {
+  tag: number;
+  user: Uint8Array;
+  permissions: number;
+  static schema: Schema = new Map([
+    [
+      revokeGroupPermissionsInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["user", [32]],
+          ["permissions", "u8"],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { user: Uint8Array; permissions: number }) {
+    this.tag = 43;
+    this.user = obj.user;
+    this.permissions = obj.permissions;
+  }
+  serialize(): Uint8Array {
+    return serialize(revokeGroupPermissionsInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    groupOwner: PublicKey,
+    groupThread: PublicKey,
+    groupMember: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: groupOwner,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: true,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class renameGroupInstruction {
+  tag: number;
+  groupName: string;
+  static schema: Schema = new Map([
+    [
+      renameGroupInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["groupName", "string"],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { groupName: string }) {
+    this.tag = 44;
+    this.groupName = obj.groupName;
+  }
+  serialize(): Uint8Array {
+    return serialize(renameGroupInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    editor: PublicKey,
+    groupThread: PublicKey,
+    groupMember: PublicKey,
+    groupAdmin: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: editor,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class deleteBucketMessageInstruction {
+  tag: number;
+  bucketIndex: number;
+  slot: number;
+  static schema: Schema = new Map([
+    [
+      deleteBucketMessageInstruction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["bucketIndex", "u32"],
+          ["slot", "u16"],
+        ],
+      },
+    ],
+  ]);
+  constructor(obj: { bucketIndex: number; slot: number }) {
+    this.tag = 45;
+    this.bucketIndex = obj.bucketIndex;
+    this.slot = obj.slot;
+  }
+  serialize(): Uint8Array {
+    return serialize(deleteBucketMessageInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    groupThread: PublicKey,
+    messageBucket: PublicKey,
+    feePayer: PublicKey,
+    bucketPayer: PublicKey,
+    groupMember: PublicKey,
+    groupAdmin: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: messageBucket,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: bucketPayer,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class migrateAccountInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      migrateAccountInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 46;
+  }
+  serialize(): Uint8Array {
+    return serialize(migrateAccountInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    systemProgram: PublicKey,
+    feePayer: PublicKey,
+    account: PublicKey,
+    groupOwner: PublicKey | undefined,
+    groupAdmins: PublicKey[]
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: systemProgram,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: feePayer,
+      isSigner: true,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: account,
+      isSigner: false,
+      isWritable: true,
+    });
+    if (!!groupOwner) {
+      keys.push({
+        pubkey: groupOwner,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    for (let k of groupAdmins) {
+      keys.push({
+        pubkey: k,
+        isSigner: false,
+        isWritable: true,
+      });
+    }
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
+export class revokeInviteInstruction {
+  tag: number;
+  static schema: Schema = new Map([
+    [
+      revokeInviteInstruction,
+      {
+        kind: "struct",
+        fields: [["tag", "u8"]],
+      },
+    ],
+  ]);
+  constructor() {
+    this.tag = 47;
+  }
+  serialize(): Uint8Array {
+    return serialize(revokeInviteInstruction.schema, this);
+  }
+  getInstruction(
+    programId: PublicKey,
+    revoker: PublicKey,
+    groupThread: PublicKey,
+    invite: PublicKey,
+    creator: PublicKey,
+    groupMember: PublicKey,
+    groupAdmin: PublicKey
+  ): TransactionInstruction {
+    const data = Buffer.from(this.serialize());
+    let keys: AccountKey[] = [];
+    keys.push({
+      pubkey: revoker,
+      isSigner: true,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupThread,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: invite,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: creator,
+      isSigner: false,
+      isWritable: true,
+    });
+    keys.push({
+      pubkey: groupMember,
+      isSigner: false,
+      isWritable: false,
+    });
+    keys.push({
+      pubkey: groupAdmin,
+      isSigner: false,
+      isWritable: false,
+    });
+    return new TransactionInstruction({
+      keys,
+      programId,
+      data,
+    });
+  }
+}
@@ -2,7 +2,7 @@ import { PublicKey, Connection } from "@solana/web3.js";
 import BN from "bn.js";
 import { Schema, deserializeUnchecked, deserialize } from "borsh";
 import { JAB_ID } from "./bindings";
-import { orderKeys } from "./utils";
+import { groupNameSeed, orderKeys, u32ToLeBytes } from "./utils";
 
 export enum Tag {
   Uninitialized = 0,
@@ -13,6 +13,17 @@ export enum Tag {
   GroupThread = 5,
   GroupThreadIndex = 6,
   Subscription = 7,
+  Reaction = 8,
+  GroupMember = 9,
+  Invite = 10,
+  GroupModeration = 11,
+  Block = 12,
+  Contact = 13,
+  Escrow = 14,
+  Config = 15,
+  Referrer = 16,
+  GroupAdmin = 17,
+  MessageBucket = 18,
 }
 
 export enum MessageType {
@@ -20,18 +31,64 @@ export enum MessageType {
   Unencrypted = 1,
   EncryptedImage = 2,
   UnencryptedImage = 3,
+  // Tombstone of a message deleted from a bucket
+  Deleted = 4,
+}
+
+export enum ThreadState {
+  Pending = 0,
+  Accepted = 1,
+}
+
+export enum StorageMode {
+  Accounts = 0,
+  Buckets = 1,
+}
+
+export enum SeedVersion {
+  V1 = 0,
+  V3 = 2,
+}
+
+export enum GroupRole {
+  Member = 0,
+  Poster = 1,
+  Moderator = 2,
+  Admin = 3,
+  Owner = 4,
+}
+
+export enum GroupPermission {
+  DeleteMessages = 1,
+  Ban = 1 << 1,
+  EditSettings = 1 << 2,
+  EditPicture = 1 << 3,
+  PostAdminOnly = 1 << 4,
+  FeeExempt = 1 << 5,
+  Invite = 1 << 6,
+}
+
+export enum ReactionType {
+  Like = 0,
+  Dislike = 1,
 }
 
 export class Profile {
   tag: Tag;
   bump: number;
-  pictureHash: String;
-  displayDomainName: String;
-  bio: String;
+  pictureHash: string;
+  displayDomainName: string;
+  bio: string;
   lamportsPerMessage: BN;
   allowDm: boolean;
   tipsSent: number;
   tipsReceived: number;
+  subscriberCount: number;
+  escrowEnabled: boolean;
+  escrowPeriod: BN;
+  priceMint: PublicKey | undefined;
+  priceAmount: BN;
+  editWindow: BN | undefined;
 
   static schema: Schema = new Map([
     [
@@ -46,23 +103,34 @@ export class Profile {
           ["bio", "string"],
           ["lamportsPerMessage", "u64"],
           ["allowDm", "u8"],
-          ["tipsSent", "u64"],
-          ["tipsReceived", "u64"],
+          ["tipsSent", "u32"],
+          ["tipsReceived", "u32"],
+          ["subscriberCount", "u32"],
+          ["escrowEnabled", "u8"],
+          ["escrowPeriod", "u64"],
+          ["priceMint", { kind: "option", type: [32] }],
+          ["priceAmount", "u64"],
+          ["editWindow", { kind: "option", type: "u64" }],
         ],
       },
     ],
   ]);
 
   constructor(obj: {
-    tag: Tag;
     bump: number;
-    pictureHash: String;
-    displayDomainName: String;
-    bio: String;
+    pictureHash: string;
+    displayDomainName: string;
+    bio: string;
     lamportsPerMessage: BN;
-    allowDm: boolean;
+    allowDm: number;
     tipsSent: number;
     tipsReceived: number;
+    subscriberCount: number;
+    escrowEnabled: number;
+    escrowPeriod: BN;
+    priceMint: Uint8Array | undefined;
+    priceAmount: BN;
+    editWindow: BN | undefined;
   }) {
     this.tag = Tag.Profile;
     this.bump = obj.bump;
@@ -70,9 +138,15 @@ export class Profile {
     this.displayDomainName = obj.displayDomainName;
     this.bio = obj.bio;
     this.lamportsPerMessage = obj.lamportsPerMessage;
-    this.allowDm = obj.allowDm;
+    this.allowDm = !!obj.allowDm;
     this.tipsSent = obj.tipsSent;
     this.tipsReceived = obj.tipsReceived;
+    this.subscriberCount = obj.subscriberCount;
+    this.escrowEnabled = !!obj.escrowEnabled;
+    this.escrowPeriod = obj.escrowPeriod;
+    this.priceMint = obj.priceMint ? new PublicKey(obj.priceMint) : undefined;
+    this.priceAmount = obj.priceAmount;
+    this.editWindow = obj.editWindow;
   }
 
   static deserialize(data: Buffer) {
@@ -97,6 +171,14 @@ export class Profile {
   static generateSeeds(profileOwner: PublicKey) {
     return [Buffer.from("profile"), profileOwner.toBuffer()];
   }
+
+  static async getKey(profileOwner: PublicKey) {
+    const [profile] = await PublicKey.findProgramAddress(
+      Profile.generateSeeds(profileOwner),
+      JAB_ID
+    );
+    return profile;
+  }
 }
 
 export class Thread {
@@ -106,6 +188,10 @@ export class Thread {
   user2: PublicKey;
   lastMessageTime: BN;
   bump: number;
+  openMsgCount: number;
+  state: ThreadState;
+  requester: PublicKey;
+  payer: PublicKey;
 
   static schema: Schema = new Map([
     [
@@ -119,6 +205,10 @@ export class Thread {
           ["user2", [32]],
           ["lastMessageTime", "u64"],
           ["bump", "u8"],
+          ["openMsgCount", "u32"],
+          ["state", "u8"],
+          ["requester", [32]],
+          ["payer", [32]],
         ],
       },
     ],
@@ -130,6 +220,10 @@ export class Thread {
     user2: Uint8Array;
     lastMessageTime: BN;
     bump: number;
+    openMsgCount: number;
+    state: ThreadState;
+    requester: Uint8Array;
+    payer: Uint8Array;
   }) {
     this.tag = Tag.Thread;
     this.msgCount = obj.msgCount;
@@ -137,6 +231,10 @@ export class Thread {
     this.user2 = new PublicKey(obj.user2);
     this.lastMessageTime = obj.lastMessageTime;
     this.bump = obj.bump;
+    this.openMsgCount = obj.openMsgCount;
+    this.state = obj.state;
+    this.requester = new PublicKey(obj.requester);
+    this.payer = new PublicKey(obj.payer);
   }
 
   static deserialize(data: Buffer) {
@@ -194,6 +292,7 @@ export class Message {
   likesCount: number;
   dislikesCount: number;
   msg: Uint8Array;
+  editedAt: BN;
 
   static schema: Schema = new Map([
     [
@@ -209,13 +308,13 @@ export class Message {
           ["likesCount", "u16"],
           ["dislikesCount", "u16"],
           ["msg", ["u8"]],
+          ["editedAt", "u64"],
         ],
       },
     ],
   ]);
 
   constructor(obj: {
-    tag: Tag;
     kind: MessageType;
     timestamp: BN;
     sender: Uint8Array;
@@ -223,6 +322,7 @@ export class Message {
     likesCount: number;
     dislikesCount: number;
     msg: Uint8Array;
+    editedAt: BN;
   }) {
     this.tag = Tag.Message;
     this.kind = obj.kind;
@@ -232,6 +332,7 @@ export class Message {
     this.likesCount = obj.likesCount;
     this.dislikesCount = obj.dislikesCount;
     this.msg = obj.msg;
+    this.editedAt = obj.editedAt;
   }
 
   static deserialize(data: Buffer) {
@@ -292,6 +393,32 @@ export class Message {
         : undefined
     );
   }
+
+  // Group messages are keyed by the sender nonce
+  static generateGroupSeeds(
+    groupThread: PublicKey,
+    sender: PublicKey,
+    nonce: number
+  ) {
+    return [
+      Buffer.from("group_message"),
+      groupThread.toBuffer(),
+      sender.toBuffer(),
+      u32ToLeBytes(nonce),
+    ];
+  }
+
+  static async getGroupKey(
+    groupThread: PublicKey,
+    sender: PublicKey,
+    nonce: number
+  ) {
+    const [key] = await PublicKey.findProgramAddress(
+      Message.generateGroupSeeds(groupThread, sender, nonce),
+      JAB_ID
+    );
+    return key;
+  }
 }
 
 export class GroupThread {
@@ -299,15 +426,27 @@ export class GroupThread {
   bump: number;
   visible: boolean;
   owner: PublicKey;
+  creator: PublicKey;
+  pendingOwner: PublicKey | undefined;
   lastMessageTime: BN;
   destinationWallet: PublicKey;
   msgCount: number;
+  openMsgCount: number;
   lamportsPerMessage: BN;
+  priceMint: PublicKey | undefined;
+  priceAmount: BN;
   mediaEnabled: boolean;
   adminOnly: boolean;
-  groupPicHash: String;
+  membersOnly: boolean;
+  private: boolean;
+  editWindow: BN;
+  groupPicHash: string;
   groupName: string;
-  admins: PublicKey[];
+  seedVersion: SeedVersion;
+  groupId: Uint8Array;
+  storageMode: StorageMode;
+  currentBucket: number;
+  linkedAccountCount: number;
 
   static schema: Schema = new Map([
     [
@@ -319,78 +458,114 @@ export class GroupThread {
           ["bump", "u8"],
           ["visible", "u8"],
           ["owner", [32]],
+          ["creator", [32]],
+          ["pendingOwner", { kind: "option", type: [32] }],
           ["lastMessageTime", "u64"],
           ["destinationWallet", [32]],
           ["msgCount", "u32"],
+          ["openMsgCount", "u32"],
           ["lamportsPerMessage", "u64"],
+          ["priceMint", { kind: "option", type: [32] }],
+          ["priceAmount", "u64"],
           ["mediaEnabled", "u8"],
           ["adminOnly", "u8"],
+          ["membersOnly", "u8"],
+          ["private", "u8"],
+          ["editWindow", "u64"],
           ["groupPicHash", "string"],
           ["groupName", "string"],
-          ["admins", [[32]]],
+          ["seedVersion", "u8"],
+          ["groupId", [32]],
+          ["storageMode", "u8"],
+          ["currentBucket", "u32"],
+          ["linkedAccountCount", "u32"],
         ],
       },
     ],
   ]);
 
   constructor(obj: {
-    tag: Tag;
     bump: number;
-    visible: boolean;
-    owner: PublicKey;
+    visible: number;
+    owner: Uint8Array;
+    creator: Uint8Array;
+    pendingOwner: Uint8Array | undefined;
     lastMessageTime: BN;
-    destinationWallet: PublicKey;
+    destinationWallet: Uint8Array;
     msgCount: number;
+    openMsgCount: number;
     lamportsPerMessage: BN;
-    mediaEnabled: boolean;
-    adminOnly: boolean;
-    groupPicHash: String;
+    priceMint: Uint8Array | undefined;
+    priceAmount: BN;
+    mediaEnabled: number;
+    adminOnly: number;
+    membersOnly: number;
+    private: number;
+    editWindow: BN;
+    groupPicHash: string;
     groupName: string;
-    admins: PublicKey[];
+    seedVersion: SeedVersion;
+    groupId: Uint8Array;
+    storageMode: StorageMode;
+    currentBucket: number;
+    linkedAccountCount: number;
   }) {
     this.tag = Tag.GroupThread;
     this.bump = obj.bump;
-    this.visible = obj.visible;
+    this.visible = !!obj.visible;
     this.owner = new PublicKey(obj.owner);
+    this.creator = new PublicKey(obj.creator);
+    this.pendingOwner = obj.pendingOwner
+      ? new PublicKey(obj.pendingOwner)
+      : undefined;
     this.lastMessageTime = obj.lastMessageTime;
     this.destinationWallet = new PublicKey(obj.destinationWallet);
     this.msgCount = obj.msgCount;
+    this.openMsgCount = obj.openMsgCount;
     this.lamportsPerMessage = obj.lamportsPerMessage;
+    this.priceMint = obj.priceMint ? new PublicKey(obj.priceMint) : undefined;
+    this.priceAmount = obj.priceAmount;
     this.mediaEnabled = !!obj.mediaEnabled;
     this.adminOnly = !!obj.adminOnly;
+    this.membersOnly = !!obj.membersOnly;
+    this.private = !!obj.private;
+    this.editWindow = obj.editWindow;
     this.groupPicHash = obj.groupPicHash;
     this.groupName = obj.groupName;
-    this.admins = obj.admins.map((e) => new PublicKey(e));
+    this.seedVersion = obj.seedVersion;
+    this.groupId = obj.groupId;
+    this.storageMode = obj.storageMode;
+    this.currentBucket = obj.currentBucket;
+    this.linkedAccountCount = obj.linkedAccountCount;
   }
 
   static deserialize(data: Buffer) {
     return deserializeUnchecked(this.schema, GroupThread, data);
   }
 
-  static generateSeeds(groupName: string, owner: PublicKey) {
+  static generateSeeds(groupId: Uint8Array, creator: PublicKey) {
     return [
       Buffer.from("group_thread"),
-      Buffer.from(groupName),
-      owner.toBuffer(),
+      Buffer.from(groupId),
+      creator.toBuffer(),
     ];
   }
 
-  static async getKey(groupName: string, owner: PublicKey) {
-    const [groupThread] = await PublicKey.findProgramAddress(
-      GroupThread.generateSeeds(groupName, owner),
+  static async getKey(groupId: Uint8Array, creator: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      GroupThread.generateSeeds(groupId, creator),
       JAB_ID
     );
-    return groupThread;
+    return key;
   }
 
   static async retrieve(
     connection: Connection,
-    groupName: string,
-    owner: PublicKey
+    groupId: Uint8Array,
+    creator: PublicKey
   ) {
-    const groupThreadKey = await GroupThread.getKey(groupName, owner);
-
-    const accountInfo = await connection.getAccountInfo(groupThreadKey);
+    const key = await GroupThread.getKey(groupId, creator);
+    const accountInfo = await connection.getAccountInfo(key);
 
     if (!accountInfo?.data) {
       throw new Error("Group thread not found");
@@ -411,7 +586,7 @@ export class GroupThread {
 }
 
 export class GroupThreadIndex {
-  tag: number;
+  tag: Tag;
   groupThreadKey: Uint8Array;
   owner: Uint8Array;
   groupName: string;
@@ -432,14 +607,14 @@ export class GroupThreadIndex {
   ]);
 
   constructor(obj: {
-    groupName: string;
     groupThreadKey: Uint8Array;
     owner: Uint8Array;
+    groupName: string;
   }) {
     this.tag = Tag.GroupThreadIndex;
-    this.groupName = obj.groupName;
     this.groupThreadKey = obj.groupThreadKey;
     this.owner = obj.owner;
+    this.groupName = obj.groupName;
   }
 
   static deserialize(data: Buffer) {
@@ -453,7 +628,7 @@ export class GroupThreadIndex {
   ) {
     return [
       Buffer.from("group_thread_index"),
-      Buffer.from(groupName),
+      groupNameSeed(groupName),
       owner.toBuffer(),
       groupThreadKey.toBuffer(),
     ];
@@ -504,7 +679,8 @@ export class GroupThreadIndex {
 }
 
 export class Subscription {
-  tag: number;
+  tag: Tag;
+  version: number;
   subscriber: Uint8Array;
   subscribedTo: Uint8Array;
 
@@ -515,6 +691,7 @@ export class Subscription {
         kind: "struct",
         fields: [
           ["tag", "u8"],
+          ["version", "u8"],
           ["subscriber", [32]],
           ["subscribedTo", [32]],
         ],
@@ -522,8 +699,13 @@ export class Subscription {
     ],
   ]);
 
-  constructor(obj: { subscriber: Uint8Array; subscribedTo: Uint8Array }) {
+  constructor(obj: {
+    version: number;
+    subscriber: Uint8Array;
+    subscribedTo: Uint8Array;
+  }) {
     this.tag = Tag.Subscription;
+    this.version = obj.version;
     this.subscriber = obj.subscriber;
     this.subscribedTo = obj.subscribedTo;
   }
@@ -548,3 +730,797 @@ export class Subscription {
     return subscriptionKey;
   }
 }
+
+export class Reaction {
+  tag: Tag;
+  kind: ReactionType;
+  message: PublicKey;
+  reactor: PublicKey;
+
+  static schema: Schema = new Map([
+    [
+      Reaction,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["kind", "u8"],
+          ["message", [32]],
+          ["reactor", [32]],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    kind: ReactionType;
+    message: Uint8Array;
+    reactor: Uint8Array;
+  }) {
+    this.tag = Tag.Reaction;
+    this.kind = obj.kind;
+    this.message = new PublicKey(obj.message);
+    this.reactor = new PublicKey(obj.reactor);
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, Reaction, data);
+  }
+
+  static generateSeeds(message: PublicKey, reactor: PublicKey) {
+    return [Buffer.from("reaction"), message.toBuffer(), reactor.toBuffer()];
+  }
+
+  static async getKey(message: PublicKey, reactor: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      Reaction.generateSeeds(message, reactor),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(
+    connection: Connection,
+    message: PublicKey,
+    reactor: PublicKey
+  ) {
+    const key = await Reaction.getKey(message, reactor);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Reaction not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
+
+export class GroupMember {
+  tag: Tag;
+  groupThread: PublicKey;
+  member: PublicKey;
+  joinedAt: BN;
+  role: GroupRole;
+  permissions: number;
+
+  static schema: Schema = new Map([
+    [
+      GroupMember,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["groupThread", [32]],
+          ["member", [32]],
+          ["joinedAt", "u64"],
+          ["role", "u8"],
+          ["permissions", "u8"],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    groupThread: Uint8Array;
+    member: Uint8Array;
+    joinedAt: BN;
+    role: GroupRole;
+    permissions: number;
+  }) {
+    this.tag = Tag.GroupMember;
+    this.groupThread = new PublicKey(obj.groupThread);
+    this.member = new PublicKey(obj.member);
+    this.joinedAt = obj.joinedAt;
+    this.role = obj.role;
+    this.permissions = obj.permissions;
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, GroupMember, data);
+  }
+
+  static generateSeeds(groupThread: PublicKey, member: PublicKey) {
+    return [
+      Buffer.from("group_member"),
+      groupThread.toBuffer(),
+      member.toBuffer(),
+    ];
+  }
+
+  static async getKey(groupThread: PublicKey, member: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      GroupMember.generateSeeds(groupThread, member),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(
+    connection: Connection,
+    groupThread: PublicKey,
+    member: PublicKey
+  ) {
+    const key = await GroupMember.getKey(groupThread, member);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Group member not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
+
+export class Invite {
+  tag: Tag;
+  groupThread: PublicKey;
+  codeKey: PublicKey;
+  creator: PublicKey;
+  expiresAt: BN;
+  maxUses: number;
+  uses: number;
+
+  static schema: Schema = new Map([
+    [
+      Invite,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["groupThread", [32]],
+          ["codeKey", [32]],
+          ["creator", [32]],
+          ["expiresAt", "u64"],
+          ["maxUses", "u32"],
+          ["uses", "u32"],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    groupThread: Uint8Array;
+    codeKey: Uint8Array;
+    creator: Uint8Array;
+    expiresAt: BN;
+    maxUses: number;
+    uses: number;
+  }) {
+    this.tag = Tag.Invite;
+    this.groupThread = new PublicKey(obj.groupThread);
+    this.codeKey = new PublicKey(obj.codeKey);
+    this.creator = new PublicKey(obj.creator);
+    this.expiresAt = obj.expiresAt;
+    this.maxUses = obj.maxUses;
+    this.uses = obj.uses;
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, Invite, data);
+  }
+
+  static generateSeeds(groupThread: PublicKey, codeKey: PublicKey) {
+    return [Buffer.from("invite"), groupThread.toBuffer(), codeKey.toBuffer()];
+  }
+
+  static async getKey(groupThread: PublicKey, codeKey: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      Invite.generateSeeds(groupThread, codeKey),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(
+    connection: Connection,
+    groupThread: PublicKey,
+    codeKey: PublicKey
+  ) {
+    const key = await Invite.getKey(groupThread, codeKey);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Invite not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
+
+export class GroupModeration {
+  tag: Tag;
+  groupThread: PublicKey;
+  user: PublicKey;
+  banned: boolean;
+  mutedUntil: BN;
+  payer: PublicKey;
+
+  static schema: Schema = new Map([
+    [
+      GroupModeration,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["groupThread", [32]],
+          ["user", [32]],
+          ["banned", "u8"],
+          ["mutedUntil", "u64"],
+          ["payer", [32]],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    groupThread: Uint8Array;
+    user: Uint8Array;
+    banned: number;
+    mutedUntil: BN;
+    payer: Uint8Array;
+  }) {
+    this.tag = Tag.GroupModeration;
+    this.groupThread = new PublicKey(obj.groupThread);
+    this.user = new PublicKey(obj.user);
+    this.banned = !!obj.banned;
+    this.mutedUntil = obj.mutedUntil;
+    this.payer = new PublicKey(obj.payer);
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, GroupModeration, data);
+  }
+
+  static generateSeeds(groupThread: PublicKey, user: PublicKey) {
+    return [
+      Buffer.from("group_moderation"),
+      groupThread.toBuffer(),
+      user.toBuffer(),
+    ];
+  }
+
+  static async getKey(groupThread: PublicKey, user: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      GroupModeration.generateSeeds(groupThread, user),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(
+    connection: Connection,
+    groupThread: PublicKey,
+    user: PublicKey
+  ) {
+    const key = await GroupModeration.getKey(groupThread, user);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Group moderation not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
+
+export class Block {
+  tag: Tag;
+  blocker: PublicKey;
+  blocked: PublicKey;
+
+  static schema: Schema = new Map([
+    [
+      Block,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["blocker", [32]],
+          ["blocked", [32]],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: { blocker: Uint8Array; blocked: Uint8Array }) {
+    this.tag = Tag.Block;
+    this.blocker = new PublicKey(obj.blocker);
+    this.blocked = new PublicKey(obj.blocked);
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, Block, data);
+  }
+
+  static generateSeeds(blocker: PublicKey, blocked: PublicKey) {
+    return [Buffer.from("block"), blocker.toBuffer(), blocked.toBuffer()];
+  }
+
+  static async getKey(blocker: PublicKey, blocked: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      Block.generateSeeds(blocker, blocked),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(
+    connection: Connection,
+    blocker: PublicKey,
+    blocked: PublicKey
+  ) {
+    const key = await Block.getKey(blocker, blocked);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Block not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
+
+export class Contact {
+  tag: Tag;
+  owner: PublicKey;
+  contact: PublicKey;
+  mutual: boolean;
+
+  static schema: Schema = new Map([
+    [
+      Contact,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["owner", [32]],
+          ["contact", [32]],
+          ["mutual", "u8"],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: { owner: Uint8Array; contact: Uint8Array; mutual: number }) {
+    this.tag = Tag.Contact;
+    this.owner = new PublicKey(obj.owner);
+    this.contact = new PublicKey(obj.contact);
+    this.mutual = !!obj.mutual;
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, Contact, data);
+  }
+
+  static generateSeeds(owner: PublicKey, contact: PublicKey) {
+    return [Buffer.from("contact"), owner.toBuffer(), contact.toBuffer()];
+  }
+
+  static async getKey(owner: PublicKey, contact: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      Contact.generateSeeds(owner, contact),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(
+    connection: Connection,
+    owner: PublicKey,
+    contact: PublicKey
+  ) {
+    const key = await Contact.getKey(owner, contact);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Contact not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
+
+export class Escrow {
+  tag: Tag;
+  message: PublicKey;
+  sender: PublicKey;
+  receiver: PublicKey;
+  amount: BN;
+  deadline: BN;
+
+  static schema: Schema = new Map([
+    [
+      Escrow,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["message", [32]],
+          ["sender", [32]],
+          ["receiver", [32]],
+          ["amount", "u64"],
+          ["deadline", "u64"],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    message: Uint8Array;
+    sender: Uint8Array;
+    receiver: Uint8Array;
+    amount: BN;
+    deadline: BN;
+  }) {
+    this.tag = Tag.Escrow;
+    this.message = new PublicKey(obj.message);
+    this.sender = new PublicKey(obj.sender);
+    this.receiver = new PublicKey(obj.receiver);
+    this.amount = obj.amount;
+    this.deadline = obj.deadline;
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, Escrow, data);
+  }
+
+  static generateSeeds(message: PublicKey) {
+    return [Buffer.from("escrow"), message.toBuffer()];
+  }
+
+  static async getKey(message: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      Escrow.generateSeeds(message),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(connection: Connection, message: PublicKey) {
+    const key = await Escrow.getKey(message);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Escrow not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
+
+export class Config {
+  tag: Tag;
+  admin: PublicKey;
+  feeBps: number;
+  vault: PublicKey;
+  vaultBump: number;
+  feeAuthority: PublicKey;
+  referrerShareBps: number;
+  paused: boolean;
+
+  static schema: Schema = new Map([
+    [
+      Config,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["admin", [32]],
+          ["feeBps", "u16"],
+          ["vault", [32]],
+          ["vaultBump", "u8"],
+          ["feeAuthority", [32]],
+          ["referrerShareBps", "u16"],
+          ["paused", "u8"],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    admin: Uint8Array;
+    feeBps: number;
+    vault: Uint8Array;
+    vaultBump: number;
+    feeAuthority: Uint8Array;
+    referrerShareBps: number;
+    paused: number;
+  }) {
+    this.tag = Tag.Config;
+    this.admin = new PublicKey(obj.admin);
+    this.feeBps = obj.feeBps;
+    this.vault = new PublicKey(obj.vault);
+    this.vaultBump = obj.vaultBump;
+    this.feeAuthority = new PublicKey(obj.feeAuthority);
+    this.referrerShareBps = obj.referrerShareBps;
+    this.paused = !!obj.paused;
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, Config, data);
+  }
+
+  static generateSeeds() {
+    return [Buffer.from("config")];
+  }
+
+  static async getKey() {
+    const [key] = await PublicKey.findProgramAddress(
+      Config.generateSeeds(),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(connection: Connection) {
+    const key = await Config.getKey();
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Config not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+
+  // System account holding the SOL fees and owning the token fee accounts
+  static async getVaultKey() {
+    const [key] = await PublicKey.findProgramAddress(
+      [Buffer.from("vault")],
+      JAB_ID
+    );
+    return key;
+  }
+}
+
+export class Referrer {
+  tag: Tag;
+  wallet: PublicKey;
+  referralCount: BN;
+  lamportsEarned: BN;
+
+  static schema: Schema = new Map([
+    [
+      Referrer,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["wallet", [32]],
+          ["referralCount", "u64"],
+          ["lamportsEarned", "u64"],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    wallet: Uint8Array;
+    referralCount: BN;
+    lamportsEarned: BN;
+  }) {
+    this.tag = Tag.Referrer;
+    this.wallet = new PublicKey(obj.wallet);
+    this.referralCount = obj.referralCount;
+    this.lamportsEarned = obj.lamportsEarned;
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, Referrer, data);
+  }
+
+  static generateSeeds(wallet: PublicKey) {
+    return [Buffer.from("referrer"), wallet.toBuffer()];
+  }
+
+  static async getKey(wallet: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      Referrer.generateSeeds(wallet),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(connection: Connection, wallet: PublicKey) {
+    const key = await Referrer.getKey(wallet);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Referrer not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
+
+export class GroupAdmin {
+  tag: Tag;
+  groupThread: PublicKey;
+  admin: PublicKey;
+
+  static schema: Schema = new Map([
+    [
+      GroupAdmin,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["groupThread", [32]],
+          ["admin", [32]],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: { groupThread: Uint8Array; admin: Uint8Array }) {
+    this.tag = Tag.GroupAdmin;
+    this.groupThread = new PublicKey(obj.groupThread);
+    this.admin = new PublicKey(obj.admin);
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, GroupAdmin, data);
+  }
+
+  static generateSeeds(groupThread: PublicKey, admin: PublicKey) {
+    return [
+      Buffer.from("group_admin"),
+      groupThread.toBuffer(),
+      admin.toBuffer(),
+    ];
+  }
+
+  static async getKey(groupThread: PublicKey, admin: PublicKey) {
+    const [key] = await PublicKey.findProgramAddress(
+      GroupAdmin.generateSeeds(groupThread, admin),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(
+    connection: Connection,
+    groupThread: PublicKey,
+    admin: PublicKey
+  ) {
+    const key = await GroupAdmin.getKey(groupThread, admin);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Group admin not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
+
+export class BucketMessage {
+  kind: MessageType;
+  timestamp: BN;
+  sender: PublicKey;
+  repliesTo: PublicKey;
+  msg: Uint8Array;
+
+  static schema: Schema = new Map([
+    [
+      BucketMessage,
+      {
+        kind: "struct",
+        fields: [
+          ["kind", "u8"],
+          ["timestamp", "u64"],
+          ["sender", [32]],
+          ["repliesTo", [32]],
+          ["msg", ["u8"]],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    kind: MessageType;
+    timestamp: BN;
+    sender: Uint8Array;
+    repliesTo: Uint8Array;
+    msg: Uint8Array;
+  }) {
+    this.kind = obj.kind;
+    this.timestamp = obj.timestamp;
+    this.sender = new PublicKey(obj.sender);
+    this.repliesTo = new PublicKey(obj.repliesTo);
+    this.msg = obj.msg;
+  }
+}
+
+export class MessageBucket {
+  tag: Tag;
+  groupThread: PublicKey;
+  payer: PublicKey;
+  liveCount: number;
+  messages: BucketMessage[];
+
+  static schema: Schema = new Map([
+    ...BucketMessage.schema,
+    [
+      MessageBucket,
+      {
+        kind: "struct",
+        fields: [
+          ["tag", "u8"],
+          ["groupThread", [32]],
+          ["payer", [32]],
+          ["liveCount", "u16"],
+          ["messages", [BucketMessage]],
+        ],
+      },
+    ],
+  ]);
+
+  constructor(obj: {
+    groupThread: Uint8Array;
+    payer: Uint8Array;
+    liveCount: number;
+    messages: BucketMessage[];
+  }) {
+    this.tag = Tag.MessageBucket;
+    this.groupThread = new PublicKey(obj.groupThread);
+    this.payer = new PublicKey(obj.payer);
+    this.liveCount = obj.liveCount;
+    this.messages = obj.messages;
+  }
+
+  static deserialize(data: Buffer) {
+    return deserializeUnchecked(this.schema, MessageBucket, data);
+  }
+
+  static generateSeeds(groupThread: PublicKey, index: number) {
+    return [
+      Buffer.from("message_bucket"),
+      groupThread.toBuffer(),
+      u32ToLeBytes(index),
+    ];
+  }
+
+  static async getKey(groupThread: PublicKey, index: number) {
+    const [key] = await PublicKey.findProgramAddress(
+      MessageBucket.generateSeeds(groupThread, index),
+      JAB_ID
+    );
+    return key;
+  }
+
+  static async retrieve(
+    connection: Connection,
+    groupThread: PublicKey,
+    index: number
+  ) {
+    const key = await MessageBucket.getKey(groupThread, index);
+    const accountInfo = await connection.getAccountInfo(key);
+
+    if (!accountInfo?.data) {
+      throw new Error("Message bucket not found");
+    }
+
+    return this.deserialize(accountInfo.data);
+  }
+}
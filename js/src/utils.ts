@@ -6,6 +6,7 @@ import {
   PublicKey,
 } from "@solana/web3.js";
 
+import { createHash } from "crypto";
 import ed2curve from "./ed2curve";
 import nacl from "tweetnacl";
 
@@ -32,6 +33,19 @@ export const orderKeys = (key1: PublicKey, key2: PublicKey) => {
   return [key2, key1];
 };
 
+// Seed of a group name, names differing only by case or surrounding spaces share it
+export const groupNameSeed = (groupName: string) => {
+  return createHash("sha256")
+    .update(Buffer.from(groupName.trim().toLowerCase()))
+    .digest();
+};
+
+export const u32ToLeBytes = (value: number) => {
+  const buffer = Buffer.alloc(4);
+  buffer.writeUInt32LE(value);
+  return buffer;
+};
+
 export const encryptMessage = (
   msg: Uint8Array,
   dhKeys: { publicKey: Buffer; secretKey: Buffer },
@@ -97,6 +97,102 @@ impl PrintProgramError for JabError {
             JabError::WrongOwner => {
                 msg!("Error: Wrong account owner")
             }
+            JabError::MessageDeleted => {
+                msg!("Error: Message has been deleted")
+            }
+            JabError::EditWindowExpired => {
+                msg!("Error: Message edit window has expired")
+            }
+            JabError::ThreadNotEmpty => {
+                msg!("Error: Thread still has open messages")
+            }
+            JabError::NotGroupMember => {
+                msg!("Error: Sender is not a member of the group")
+            }
+            JabError::PrivateGroup => {
+                msg!("Error: Group can only be joined with an invite")
+            }
+            JabError::InviteExpired => {
+                msg!("Error: Invite has expired")
+            }
+            JabError::InviteExhausted => {
+                msg!("Error: Invite has no uses left")
+            }
+            JabError::MemberBanned => {
+                msg!("Error: User is banned from the group")
+            }
+            JabError::MemberMuted => {
+                msg!("Error: User is muted in the group")
+            }
+            JabError::SenderBlocked => {
+                msg!("Error: Sender is blocked by the receiver")
+            }
+            JabError::ThreadPending => {
+                msg!("Error: Thread is waiting to be accepted")
+            }
+            JabError::ThreadNotPending => {
+                msg!("Error: Thread is not pending")
+            }
+            JabError::EscrowNotExpired => {
+                msg!("Error: Escrow deadline has not passed")
+            }
+            JabError::MissingTokenAccounts => {
+                msg!("Error: Token accounts are required to pay in SPL tokens")
+            }
+            JabError::WrongTokenAccount => {
+                msg!("Error: Wrong token account")
+            }
+            JabError::ProgramPaused => {
+                msg!("Error: Program is paused")
+            }
+            JabError::InvalidFee => {
+                msg!("Error: Fee cannot exceed 100%")
+            }
+            JabError::InvalidFeeSplit => {
+                msg!("Error: Fee split must add up to 100%")
+            }
+            JabError::WrongReferrer => {
+                msg!("Error: Wrong referrer account")
+            }
+            JabError::NotPendingOwner => {
+                msg!("Error: Account is not the pending owner of the group")
+            }
+            JabError::GroupNotRenamable => {
+                msg!("Error: Groups addressed by their raw name cannot be renamed")
+            }
+            JabError::BucketFull => {
+                msg!("Error: Message bucket is full")
+            }
+            JabError::AccountNeedsMigration => {
+                msg!("Error: Account uses a legacy layout and must be migrated")
+            }
+            JabError::InvalidEditWindow => {
+                msg!("Error: Edit window cannot be negative")
+            }
+            JabError::InvalidEscrowPeriod => {
+                msg!("Error: Escrow period must be positive and at most 30 days")
+            }
+            JabError::EscrowNotSupported => {
+                msg!("Error: Escrow is only supported for lamport prices")
+            }
+            JabError::MissingVaultTokenAccount => {
+                msg!("Error: Vault token account does not exist")
+            }
+            JabError::NotSupportedForBuckets => {
+                msg!("Error: Operation is not supported for bucketed groups")
+            }
+            JabError::GroupNotEmpty => {
+                msg!("Error: Group still has members, admins, moderations or invites")
+            }
+            JabError::ProfileHasSubscribers => {
+                msg!("Error: Profile still has subscribers")
+            }
+            JabError::InvalidInviteExpiry => {
+                msg!("Error: Invite must expire in the future")
+            }
+            JabError::InvalidInviteMaxUses => {
+                msg!("Error: Invite must allow at least one use")
+            }
         }
     }
 }
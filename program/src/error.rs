@@ -50,6 +50,70 @@ pub enum JabError {
     DmClosed,
     #[error("Wrong account owner")]
     WrongOwner,
+    #[error("Message has been deleted")]
+    MessageDeleted,
+    #[error("Message edit window has expired")]
+    EditWindowExpired,
+    #[error("Thread still has open messages")]
+    ThreadNotEmpty,
+    #[error("Sender is not a member of the group")]
+    NotGroupMember,
+    #[error("Group can only be joined with an invite")]
+    PrivateGroup,
+    #[error("Invite has expired")]
+    InviteExpired,
+    #[error("Invite has no uses left")]
+    InviteExhausted,
+    #[error("User is banned from the group")]
+    MemberBanned,
+    #[error("User is muted in the group")]
+    MemberMuted,
+    #[error("Sender is blocked by the receiver")]
+    SenderBlocked,
+    #[error("Thread is waiting to be accepted")]
+    ThreadPending,
+    #[error("Thread is not pending")]
+    ThreadNotPending,
+    #[error("Escrow deadline has not passed")]
+    EscrowNotExpired,
+    #[error("Token accounts are required to pay in SPL tokens")]
+    MissingTokenAccounts,
+    #[error("Wrong token account")]
+    WrongTokenAccount,
+    #[error("Program is paused")]
+    ProgramPaused,
+    #[error("Fee cannot exceed 100%")]
+    InvalidFee,
+    #[error("Fee split must add up to 100%")]
+    InvalidFeeSplit,
+    #[error("Wrong referrer account")]
+    WrongReferrer,
+    #[error("Account is not the pending owner of the group")]
+    NotPendingOwner,
+    #[error("Groups addressed by their raw name cannot be renamed")]
+    GroupNotRenamable,
+    #[error("Message bucket is full")]
+    BucketFull,
+    #[error("Account uses a legacy layout and must be migrated")]
+    AccountNeedsMigration,
+    #[error("Edit window cannot be negative")]
+    InvalidEditWindow,
+    #[error("Escrow period must be positive and at most 30 days")]
+    InvalidEscrowPeriod,
+    #[error("Escrow is only supported for lamport prices")]
+    EscrowNotSupported,
+    #[error("Vault token account does not exist")]
+    MissingVaultTokenAccount,
+    #[error("Operation is not supported for bucketed groups")]
+    NotSupportedForBuckets,
+    #[error("Group still has members, admins, moderations or invites")]
+    GroupNotEmpty,
+    #[error("Profile still has subscribers")]
+    ProfileHasSubscribers,
+    #[error("Invite must expire in the future")]
+    InvalidInviteExpiry,
+    #[error("Invite must allow at least one use")]
+    InvalidInviteMaxUses,
 }
 impl From<JabError> for ProgramError {
     fn from(e: JabError) -> Self {
@@ -0,0 +1,67 @@
+//! Events emitted in the program logs
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{clock::UnixTimestamp, log::sol_log_data, pubkey::Pubkey};
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MessageDeleted {
+    // Closed message account
+    pub message: Pubkey,
+    // Original sender of the message
+    pub sender: Pubkey,
+    // Account that signed the deletion
+    pub deleted_by: Pubkey,
+    // Time of deletion
+    pub timestamp: UnixTimestamp,
+}
+
+impl MessageDeleted {
+    pub const NAME: &'static str = "MessageDeleted";
+
+    pub fn emit(&self) {
+        sol_log_data(&[Self::NAME.as_bytes(), &self.try_to_vec().unwrap()]);
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct GroupMessageSent {
+    // Created message account
+    pub message: Pubkey,
+    // Group thread the message was sent to
+    pub group_thread: Pubkey,
+    // Sender of the message
+    pub sender: Pubkey,
+    // Sender chosen nonce of the message
+    pub nonce: u32,
+    // Time at which the message was sent, messages are ordered by (timestamp, sender, nonce)
+    pub timestamp: UnixTimestamp,
+    // Bucket index and slot of the message in bucketed groups
+    pub bucket_slot: Option<(u32, u16)>,
+}
+
+impl GroupMessageSent {
+    pub const NAME: &'static str = "GroupMessageSent";
+
+    pub fn emit(&self) {
+        sol_log_data(&[Self::NAME.as_bytes(), &self.try_to_vec().unwrap()]);
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReferralPaid {
+    // Wallet of the referrer
+    pub referrer: Pubkey,
+    // Account that paid the referred payment
+    pub payer: Pubkey,
+    // Mint of the share, `None` for lamports
+    pub mint: Option<Pubkey>,
+    // Share of the protocol fee paid to the referrer
+    pub amount: u64,
+}
+
+impl ReferralPaid {
+    pub const NAME: &'static str = "ReferralPaid";
+
+    pub fn emit(&self) {
+        sol_log_data(&[Self::NAME.as_bytes(), &self.try_to_vec().unwrap()]);
+    }
+}
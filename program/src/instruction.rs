@@ -1,7 +1,14 @@
 pub use crate::processor::{
-    add_admin_to_group, create_group_index, create_group_thread, create_profile,
-    create_subscription, create_thread, delete_group_message, delete_message, edit_group_thread,
-    remove_admin_from_group, send_message, send_message_group, send_tip, set_user_profile,
+    accept_group_owner, accept_thread, add_admin_to_group, add_contact, ban_member, block_user,
+    close_group_index, close_group_thread, close_profile, close_thread, create_group_index,
+    create_group_thread, create_invite, create_profile, create_subscription, create_thread,
+    decline_thread, delete_bucket_message, delete_group_message, delete_message,
+    delete_subscription, edit_group_message, edit_group_thread, edit_message,
+    grant_group_permissions, init_config, join_group, leave_group, migrate_account, mute_member,
+    propose_group_owner, react_to_message, redeem_invite, refund_escrow, register_referrer,
+    remove_admin_from_group, remove_contact, rename_group, revoke_group_permissions, revoke_invite,
+    send_message, send_message_group, send_tip, set_user_profile, unban_member, unblock_user,
+    update_config, withdraw_fees,
 };
 use bonfida_utils::InstructionsAccount;
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -27,22 +34,34 @@ pub enum JabInstruction {
     CreateThread,
     /// Edit a Jab profile information
     ///
-    /// | Index | Writable | Signer | Description               |
-    /// | ----------------------------------------------------- |
-    /// | 0     | ✅        | ✅      | The profile owner account |
-    /// | 1     | ✅        | ❌      | The profile account       |
+    /// | Index | Writable | Signer | Description                |
+    /// | ------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The system program account |
+    /// | 1     | ✅        | ✅      | The profile owner account  |
+    /// | 2     | ✅        | ❌      | The profile account        |
     SetUserProfile,
     /// Send a message (DM)
     ///
-    /// | Index | Writable | Signer | Description                  |
-    /// | -------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The system program account   |
-    /// | 1     | ✅        | ✅      | The sender account           |
-    /// | 2     | ✅        | ❌      | The receiver account         |
-    /// | 3     | ✅        | ❌      | The thread account           |
-    /// | 4     | ❌        | ❌      | The receiver profile account |
-    /// | 5     | ✅        | ❌      | The message account          |
-    /// | 6     | ✅        | ❌      | The SOL vault account        |
+    /// | Index | Writable | Signer | Description                                                            |
+    /// | -------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                             |
+    /// | 1     | ✅        | ✅      | The sender account                                                     |
+    /// | 2     | ✅        | ❌      | The receiver account                                                   |
+    /// | 3     | ✅        | ❌      | The thread account                                                     |
+    /// | 4     | ❌        | ❌      | The receiver profile account                                           |
+    /// | 5     | ✅        | ❌      | The message account                                                    |
+    /// | 6     | ✅        | ❌      | The SOL vault account                                                  |
+    /// | 7     | ❌        | ❌      | The program config account                                             |
+    /// | 8     | ❌        | ❌      | The block account of the sender by the receiver                        |
+    /// | 9     | ❌        | ❌      | The contact account of the sender in the receiver list                 |
+    /// | 10    | ✅        | ❌      | The escrow account of the message                                      |
+    /// | 11    | ✅        | ❌      | The escrow account of the message replied to                           |
+    /// | 12    | ❌        | ❌      | The SPL token program account (optional)                               |
+    /// | 13    | ✅        | ❌      | The sender token account (optional)                                    |
+    /// | 14    | ✅        | ❌      | The receiver token account (optional)                                  |
+    /// | 15    | ✅        | ❌      | The vault token account (optional)                                     |
+    /// | 16    | ✅        | ❌      | The referrer wallet, or token account when paying in tokens (optional) |
+    /// | 17    | ✅        | ❌      | The referrer account (optional)                                        |
     SendMessage,
     /// Create a group thread
     ///
@@ -54,43 +73,59 @@ pub enum JabInstruction {
     CreateGroupThread,
     /// Edit a group thread information
     ///
-    /// | Index | Writable | Signer | Description              |
-    /// | ---------------------------------------------------- |
-    /// | 0     | ✅        | ✅      | The group owner account  |
-    /// | 1     | ✅        | ❌      | The group thread account |
+    /// | Index | Writable | Signer | Description                                        |
+    /// | ------------------------------------------------------------------------------ |
+    /// | 0     | ✅        | ✅      | The group owner or admin account                   |
+    /// | 1     | ✅        | ❌      | The group thread account                           |
+    /// | 2     | ❌        | ❌      | The editor group membership account (may be empty) |
+    /// | 3     | ❌        | ❌      | The editor group admin account (may be empty)      |
     EditGroupThread,
     /// Send a message to a group
     ///
-    /// | Index | Writable | Signer | Description                |
-    /// | ------------------------------------------------------ |
-    /// | 0     | ❌        | ❌      | The system program account |
-    /// | 1     | ✅        | ✅      | The sender account         |
-    /// | 2     | ✅        | ❌      | The group thread account   |
-    /// | 3     | ✅        | ❌      | The destination wallet     |
-    /// | 4     | ✅        | ❌      | The message account        |
-    /// | 5     | ✅        | ❌      | The SOL vault account      |
+    /// | Index | Writable | Signer | Description                                                                           |
+    /// | ----------------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                                            |
+    /// | 1     | ✅        | ✅      | The sender account                                                                    |
+    /// | 2     | ❌        | ❌      | The group thread account, only written to (and passed as writable) in bucketed groups |
+    /// | 3     | ✅        | ❌      | The destination wallet                                                                |
+    /// | 4     | ✅        | ❌      | The message account, or the current message bucket in bucketed groups                 |
+    /// | 5     | ✅        | ❌      | The SOL vault account                                                                 |
+    /// | 6     | ❌        | ❌      | The program config account                                                            |
+    /// | 7     | ❌        | ❌      | The sender group membership account                                                   |
+    /// | 8     | ❌        | ❌      | The sender group moderation account                                                   |
+    /// | 9     | ❌        | ❌      | The sender group admin account (may be empty)                                         |
+    /// | 10    | ❌        | ❌      | The SPL token program account (optional)                                              |
+    /// | 11    | ✅        | ❌      | The sender token account (optional)                                                   |
+    /// | 12    | ✅        | ❌      | The destination wallet token account (optional)                                       |
+    /// | 13    | ✅        | ❌      | The vault token account (optional)                                                    |
+    /// | 14    | ✅        | ❌      | The referrer wallet, or token account when paying in tokens (optional)                |
+    /// | 15    | ✅        | ❌      | The referrer account (optional)                                                       |
     SendMessageGroup,
     /// Add an admin to the group
     ///
-    /// | Index | Writable | Signer | Description              |
-    /// | ---------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The group thread account |
-    /// | 1     | ✅        | ✅      | The group owner account  |
+    /// | Index | Writable | Signer | Description                |
+    /// | ------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The system program account |
+    /// | 1     | ❌        | ❌      | The group thread account   |
+    /// | 2     | ✅        | ✅      | The group owner account    |
+    /// | 3     | ✅        | ❌      | The group admin account    |
     AddAdminToGroup,
     /// Remove an admin from the group
     ///
     /// | Index | Writable | Signer | Description              |
     /// | ---------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The group thread account |
+    /// | 0     | ❌        | ❌      | The group thread account |
     /// | 1     | ✅        | ✅      | The group owner account  |
+    /// | 2     | ✅        | ❌      | The group admin account  |
     RemoveAdminFromGroup,
     /// Create a group index for a user
     ///
-    /// | Index | Writable | Signer | Description                    |
-    /// | ---------------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The system program account     |
-    /// | 1     | ✅        | ❌      | The group thread index account |
-    /// | 2     | ✅        | ✅      | The fee payer account          |
+    /// | Index | Writable | Signer | Description                     |
+    /// | ----------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account      |
+    /// | 1     | ✅        | ❌      | The group thread index account  |
+    /// | 2     | ✅        | ✅      | The fee payer account           |
+    /// | 3     | ❌        | ✅      | The account of the indexed user |
     CreateGroupIndex,
     /// Delete a message (DM)
     ///
@@ -99,35 +134,362 @@ pub enum JabInstruction {
     /// | 0     | ✅        | ✅      | The message sender account   |
     /// | 1     | ❌        | ❌      | The message receiver account |
     /// | 2     | ✅        | ❌      | The message account          |
+    /// | 3     | ✅        | ❌      | The thread account           |
     DeleteMessage,
     /// Delete a message sent to a group
     ///
-    /// | Index | Writable | Signer | Description              |
-    /// | ---------------------------------------------------- |
-    /// | 0     | ❌        | ❌      | The group thread account |
-    /// | 1     | ✅        | ❌      | The message account      |
-    /// | 2     | ✅        | ✅      | The fee payer account    |
+    /// | Index | Writable | Signer | Description                                           |
+    /// | --------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The group thread account                              |
+    /// | 1     | ✅        | ❌      | The message account                                   |
+    /// | 2     | ✅        | ✅      | The fee payer account                                 |
+    /// | 3     | ✅        | ❌      | The message sender account                            |
+    /// | 4     | ❌        | ❌      | The fee payer group membership account (may be empty) |
+    /// | 5     | ❌        | ❌      | The fee payer group admin account (may be empty)      |
     DeleteGroupMessage,
     /// Send a tip
     ///
-    /// | Index | Writable | Signer | Description                      |
-    /// | ------------------------------------------------------------ |
-    /// | 0     | ❌        | ❌      | The SPL token program ID         |
-    /// | 1     | ✅        | ❌      | The tip sender profile account   |
-    /// | 2     | ✅        | ✅      | The tip sender account           |
-    /// | 3     | ✅        | ❌      | The tip receiver profile account |
-    /// | 4     | ❌        | ❌      | The tip receiver account         |
-    /// | 5     | ✅        | ❌      | The token source account         |
-    /// | 6     | ✅        | ❌      | The token destination account    |
+    /// | Index | Writable | Signer | Description                           |
+    /// | ----------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The SPL token program ID              |
+    /// | 1     | ❌        | ❌      | The system program account            |
+    /// | 2     | ✅        | ❌      | The tip sender profile account        |
+    /// | 3     | ✅        | ✅      | The tip sender account                |
+    /// | 4     | ✅        | ❌      | The tip receiver profile account      |
+    /// | 5     | ❌        | ❌      | The tip receiver account              |
+    /// | 6     | ✅        | ❌      | The token source account              |
+    /// | 7     | ✅        | ❌      | The token destination account         |
+    /// | 8     | ❌        | ❌      | The program config account            |
+    /// | 9     | ✅        | ❌      | The vault token account               |
+    /// | 10    | ✅        | ❌      | The referrer token account (optional) |
+    /// | 11    | ✅        | ❌      | The referrer account (optional)       |
     SendTip,
     /// Create a subscription
     ///
+    /// | Index | Writable | Signer | Description                                   |
+    /// | ------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The subscription account                      |
+    /// | 1     | ✅        | ✅      | Account to which the user subscribes          |
+    /// | 2     | ❌        | ❌      | The system program account                    |
+    /// | 3     | ✅        | ❌      | The profile account of the user subscribed to |
+    CreateSubscription,
+    /// React to a message (DM or group)
+    ///
+    /// | Index | Writable | Signer | Description                        |
+    /// | -------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account         |
+    /// | 1     | ✅        | ✅      | The reactor account                |
+    /// | 2     | ❌        | ❌      | The thread or group thread account |
+    /// | 3     | ✅        | ❌      | The message account                |
+    /// | 4     | ✅        | ❌      | The reaction account               |
+    ReactToMessage,
+    /// Edit a message (DM)
+    ///
+    /// | Index | Writable | Signer | Description                                         |
+    /// | ------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                          |
+    /// | 1     | ✅        | ✅      | The message sender account                          |
+    /// | 2     | ❌        | ❌      | The message receiver account                        |
+    /// | 3     | ✅        | ❌      | The message account                                 |
+    /// | 4     | ❌        | ❌      | The message receiver profile account (may be empty) |
+    EditMessage,
+    /// Edit a message sent to a group
+    ///
+    /// | Index | Writable | Signer | Description                |
+    /// | ------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The system program account |
+    /// | 1     | ✅        | ✅      | The message sender account |
+    /// | 2     | ❌        | ❌      | The group thread account   |
+    /// | 3     | ✅        | ❌      | The message account        |
+    EditGroupMessage,
+    /// Close a DM thread
+    ///
+    /// | Index | Writable | Signer | Description                    |
+    /// | ---------------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The thread participant account |
+    /// | 1     | ✅        | ❌      | The thread account             |
+    CloseThread,
+    /// Close a Jab profile
+    ///
+    /// | Index | Writable | Signer | Description               |
+    /// | ----------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The profile owner account |
+    /// | 1     | ✅        | ❌      | The profile account       |
+    CloseProfile,
+    /// Close a group thread
+    ///
+    /// | Index | Writable | Signer | Description              |
+    /// | ---------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The group owner account  |
+    /// | 1     | ✅        | ❌      | The group thread account |
+    CloseGroupThread,
+    /// Close a group index
+    ///
     /// | Index | Writable | Signer | Description                          |
     /// | ---------------------------------------------------------------- |
-    /// | 0     | ✅        | ❌      | The subscription account             |
-    /// | 1     | ✅        | ✅      | Account to which the user subscribes |
-    /// | 2     | ❌        | ❌      | The system program account           |
-    CreateSubscription,
+    /// | 0     | ✅        | ✅      | The group thread index owner account |
+    /// | 1     | ✅        | ❌      | The group thread index account       |
+    CloseGroupIndex,
+    /// Delete a subscription
+    ///
+    /// | Index | Writable | Signer | Description                                   |
+    /// | ------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The subscription account                      |
+    /// | 1     | ✅        | ✅      | The subscriber account                        |
+    /// | 2     | ✅        | ❌      | The profile account of the user subscribed to |
+    DeleteSubscription,
+    /// Join a group
+    ///
+    /// | Index | Writable | Signer | Description                         |
+    /// | --------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account          |
+    /// | 1     | ✅        | ✅      | The member account                  |
+    /// | 2     | ❌        | ❌      | The group thread account            |
+    /// | 3     | ✅        | ❌      | The group member account            |
+    /// | 4     | ❌        | ❌      | The member group moderation account |
+    JoinGroup,
+    /// Leave a group
+    ///
+    /// | Index | Writable | Signer | Description              |
+    /// | ---------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The member account       |
+    /// | 1     | ✅        | ❌      | The group member account |
+    LeaveGroup,
+    /// Create an invite to a group
+    ///
+    /// | Index | Writable | Signer | Description                                         |
+    /// | ------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                          |
+    /// | 1     | ✅        | ✅      | The group owner or admin account                    |
+    /// | 2     | ❌        | ❌      | The group thread account                            |
+    /// | 3     | ✅        | ❌      | The invite account                                  |
+    /// | 4     | ❌        | ❌      | The creator group membership account (may be empty) |
+    /// | 5     | ❌        | ❌      | The creator group admin account (may be empty)      |
+    CreateInvite,
+    /// Redeem an invite to join a group
+    ///
+    /// | Index | Writable | Signer | Description                                                               |
+    /// | ----------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                                |
+    /// | 1     | ✅        | ✅      | The member account                                                        |
+    /// | 2     | ❌        | ❌      | The group thread account                                                  |
+    /// | 3     | ✅        | ❌      | The invite account                                                        |
+    /// | 4     | ❌        | ✅      | The invite code account, its signature binds the redemption to the member |
+    /// | 5     | ✅        | ❌      | The group member account                                                  |
+    /// | 6     | ❌        | ❌      | The member group moderation account                                       |
+    RedeemInvite,
+    /// Ban a user from a group
+    ///
+    /// | Index | Writable | Signer | Description                                             |
+    /// | ----------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                              |
+    /// | 1     | ✅        | ✅      | The group owner or admin account                        |
+    /// | 2     | ❌        | ❌      | The group thread account                                |
+    /// | 3     | ✅        | ❌      | The group moderation account                            |
+    /// | 4     | ❌        | ❌      | The moderator group membership account (may be empty)   |
+    /// | 5     | ❌        | ❌      | The moderator group admin account (may be empty)        |
+    /// | 6     | ✅        | ❌      | The banned user account                                 |
+    /// | 7     | ✅        | ❌      | The banned user group membership account (may be empty) |
+    /// | 8     | ❌        | ❌      | The banned user group admin account (may be empty)      |
+    BanMember,
+    /// Unban a user from a group
+    ///
+    /// | Index | Writable | Signer | Description                                            |
+    /// | ---------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The group owner or admin account                       |
+    /// | 1     | ❌        | ❌      | The group thread account                               |
+    /// | 2     | ✅        | ❌      | The group moderation account                           |
+    /// | 3     | ❌        | ❌      | The moderator group membership account (may be empty)  |
+    /// | 4     | ❌        | ❌      | The moderator group admin account (may be empty)       |
+    /// | 5     | ✅        | ❌      | The account that paid for the group moderation account |
+    UnbanMember,
+    /// Mute a user in a group until a given time
+    ///
+    /// | Index | Writable | Signer | Description                                            |
+    /// | ---------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                             |
+    /// | 1     | ✅        | ✅      | The group owner or admin account                       |
+    /// | 2     | ❌        | ❌      | The group thread account                               |
+    /// | 3     | ✅        | ❌      | The group moderation account                           |
+    /// | 4     | ❌        | ❌      | The moderator group membership account (may be empty)  |
+    /// | 5     | ❌        | ❌      | The moderator group admin account (may be empty)       |
+    /// | 6     | ❌        | ❌      | The muted user group membership account (may be empty) |
+    /// | 7     | ❌        | ❌      | The muted user group admin account (may be empty)      |
+    MuteMember,
+    /// Block a user from sending direct messages
+    ///
+    /// | Index | Writable | Signer | Description                |
+    /// | ------------------------------------------------------ |
+    /// | 0     | ❌        | ❌      | The system program account |
+    /// | 1     | ✅        | ✅      | The blocker account        |
+    /// | 2     | ✅        | ❌      | The block account          |
+    BlockUser,
+    /// Unblock a user and close the block account
+    ///
+    /// | Index | Writable | Signer | Description         |
+    /// | ----------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The blocker account |
+    /// | 1     | ✅        | ❌      | The block account   |
+    UnblockUser,
+    /// Accept a pending thread
+    ///
+    /// | Index | Writable | Signer | Description          |
+    /// | ------------------------------------------------ |
+    /// | 0     | ❌        | ✅      | The receiver account |
+    /// | 1     | ✅        | ❌      | The thread account   |
+    AcceptThread,
+    /// Decline a pending thread, closing it, its request message and the escrow of the request
+    ///
+    /// | Index | Writable | Signer | Description                                       |
+    /// | ----------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The receiver account                              |
+    /// | 1     | ✅        | ❌      | The thread account                                |
+    /// | 2     | ✅        | ❌      | The account that paid for the thread              |
+    /// | 3     | ✅        | ❌      | The request message account (may be empty)        |
+    /// | 4     | ✅        | ❌      | The requester account                             |
+    /// | 5     | ✅        | ❌      | The request message escrow account (may be empty) |
+    DeclineThread,
+    /// Add a user to the contact list
+    ///
+    /// | Index | Writable | Signer | Description                                                         |
+    /// | ----------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                          |
+    /// | 1     | ✅        | ✅      | The contact list owner account                                      |
+    /// | 2     | ✅        | ❌      | The contact account                                                 |
+    /// | 3     | ✅        | ❌      | The contact account of the owner in the contact list (may be empty) |
+    AddContact,
+    /// Remove a user from the contact list
+    ///
+    /// | Index | Writable | Signer | Description                                                         |
+    /// | ----------------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The contact list owner account                                      |
+    /// | 1     | ✅        | ❌      | The contact account                                                 |
+    /// | 2     | ✅        | ❌      | The contact account of the owner in the contact list (may be empty) |
+    RemoveContact,
+    /// Refund the payment of an unanswered message after the escrow deadline
+    ///
+    /// | Index | Writable | Signer | Description                |
+    /// | ------------------------------------------------------ |
+    /// | 0     | ✅        | ✅      | The message sender account |
+    /// | 1     | ✅        | ❌      | The escrow account         |
+    RefundEscrow,
+    /// Initialize the program config, can only be called once by the program upgrade authority
+    ///
+    /// | Index | Writable | Signer | Description                             |
+    /// | ------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account              |
+    /// | 1     | ✅        | ✅      | The fee payer account                   |
+    /// | 2     | ✅        | ❌      | The program config account              |
+    /// | 3     | ❌        | ✅      | The program upgrade authority account   |
+    /// | 4     | ❌        | ❌      | The program data account of the program |
+    /// | 5     | ✅        | ❌      | The SOL vault account                   |
+    InitConfig,
+    /// Update the program config, optionally creating the vault token account of a mint
+    ///
+    /// | Index | Writable | Signer | Description                                                               |
+    /// | ----------------------------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The config admin account                                                  |
+    /// | 1     | ✅        | ❌      | The program config account                                                |
+    /// | 2     | ❌        | ❌      | The system program account, when creating a vault token account           |
+    /// | 3     | ❌        | ❌      | The SPL token program account, when creating a vault token account        |
+    /// | 4     | ❌        | ❌      | The associated token program account, when creating a vault token account |
+    /// | 5     | ❌        | ❌      | The rent sysvar account, when creating a vault token account              |
+    /// | 6     | ❌        | ❌      | The vault account, when creating a vault token account                    |
+    /// | 7     | ❌        | ❌      | The mint of the vault token account to create                             |
+    /// | 8     | ✅        | ❌      | The vault token account to create, skipped if it already exists           |
+    UpdateConfig,
+    /// Withdraw protocol fees from the vault, split between recipients
+    ///
+    /// | Index | Writable | Signer | Description                                                        |
+    /// | ---------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                         |
+    /// | 1     | ❌        | ❌      | The SPL token program account                                      |
+    /// | 2     | ❌        | ✅      | The fee authority account                                          |
+    /// | 3     | ❌        | ❌      | The program config account                                         |
+    /// | 4     | ✅        | ❌      | The vault account                                                  |
+    /// | 5     | ✅        | ❌      | The vault token account, or the vault account when withdrawing SOL |
+    /// | 6..   | ✅        | ❌      | The recipient wallets, or token accounts when withdrawing tokens   |
+    WithdrawFees,
+    /// Register a wallet as a referrer
+    ///
+    /// | Index | Writable | Signer | Description                 |
+    /// | ------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account  |
+    /// | 1     | ✅        | ✅      | The referrer wallet account |
+    /// | 2     | ✅        | ❌      | The referrer account        |
+    RegisterReferrer,
+    /// Propose a new owner for the group
+    ///
+    /// | Index | Writable | Signer | Description              |
+    /// | ---------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The group owner account  |
+    /// | 1     | ✅        | ❌      | The group thread account |
+    ProposeGroupOwner,
+    /// Accept the ownership of a group
+    ///
+    /// | Index | Writable | Signer | Description               |
+    /// | ----------------------------------------------------- |
+    /// | 0     | ✅        | ✅      | The pending owner account |
+    /// | 1     | ✅        | ❌      | The group thread account  |
+    AcceptGroupOwner,
+    /// Grant a role and permissions to a group member
+    ///
+    /// | Index | Writable | Signer | Description                                      |
+    /// | ---------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                       |
+    /// | 1     | ✅        | ✅      | The group owner account                          |
+    /// | 2     | ❌        | ❌      | The group thread account                         |
+    /// | 3     | ✅        | ❌      | The user group membership account (may be empty) |
+    GrantGroupPermissions,
+    /// Revoke permissions from a group member
+    ///
+    /// | Index | Writable | Signer | Description                       |
+    /// | ------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The group owner account           |
+    /// | 1     | ❌        | ❌      | The group thread account          |
+    /// | 2     | ✅        | ❌      | The user group membership account |
+    RevokeGroupPermissions,
+    /// Rename a group
+    ///
+    /// | Index | Writable | Signer | Description                                        |
+    /// | ------------------------------------------------------------------------------ |
+    /// | 0     | ❌        | ✅      | The group owner or admin account                   |
+    /// | 1     | ✅        | ❌      | The group thread account                           |
+    /// | 2     | ❌        | ❌      | The editor group membership account (may be empty) |
+    /// | 3     | ❌        | ❌      | The editor group admin account (may be empty)      |
+    RenameGroup,
+    /// Delete a message stored in a message bucket
+    ///
+    /// | Index | Writable | Signer | Description                                           |
+    /// | --------------------------------------------------------------------------------- |
+    /// | 0     | ✅        | ❌      | The group thread account                              |
+    /// | 1     | ✅        | ❌      | The message bucket account                            |
+    /// | 2     | ✅        | ✅      | The fee payer account                                 |
+    /// | 3     | ✅        | ❌      | The account that paid for the bucket                  |
+    /// | 4     | ❌        | ❌      | The fee payer group membership account (may be empty) |
+    /// | 5     | ❌        | ❌      | The fee payer group admin account (may be empty)      |
+    DeleteBucketMessage,
+    /// Convert an account created by the initial program version to the current layout
+    ///
+    /// | Index | Writable | Signer | Description                                                                    |
+    /// | ---------------------------------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ❌      | The system program account                                                     |
+    /// | 1     | ✅        | ✅      | The fee payer account                                                          |
+    /// | 2     | ✅        | ❌      | The account to migrate                                                         |
+    /// | 3     | ✅        | ❌      | The group owner account, when migrating a group (optional)                     |
+    /// | 4..   | ✅        | ❌      | The group admin accounts of the legacy admins in order, when migrating a group |
+    MigrateAccount,
+    /// Revoke an invite to a group
+    ///
+    /// | Index | Writable | Signer | Description                                         |
+    /// | ------------------------------------------------------------------------------- |
+    /// | 0     | ❌        | ✅      | The invite creator or a member allowed to invite    |
+    /// | 1     | ❌        | ❌      | The group thread account                            |
+    /// | 2     | ✅        | ❌      | The invite account                                  |
+    /// | 3     | ✅        | ❌      | The invite creator account                          |
+    /// | 4     | ❌        | ❌      | The revoker group membership account (may be empty) |
+    /// | 5     | ❌        | ❌      | The revoker group admin account (may be empty)      |
+    RevokeInvite,
 }
 pub fn create_profile(
     program_id: Pubkey,
@@ -231,3 +593,253 @@ pub fn create_subscription(
 ) -> Instruction {
     accounts.get_instruction(program_id, JabInstruction::CreateSubscription as u8, params)
 }
+pub fn react_to_message(
+    program_id: Pubkey,
+    accounts: react_to_message::Accounts<Pubkey>,
+    params: react_to_message::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::ReactToMessage as u8, params)
+}
+pub fn edit_message(
+    program_id: Pubkey,
+    accounts: edit_message::Accounts<Pubkey>,
+    params: edit_message::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::EditMessage as u8, params)
+}
+pub fn edit_group_message(
+    program_id: Pubkey,
+    accounts: edit_group_message::Accounts<Pubkey>,
+    params: edit_group_message::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::EditGroupMessage as u8, params)
+}
+pub fn close_thread(
+    program_id: Pubkey,
+    accounts: close_thread::Accounts<Pubkey>,
+    params: close_thread::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::CloseThread as u8, params)
+}
+pub fn close_profile(
+    program_id: Pubkey,
+    accounts: close_profile::Accounts<Pubkey>,
+    params: close_profile::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::CloseProfile as u8, params)
+}
+pub fn close_group_thread(
+    program_id: Pubkey,
+    accounts: close_group_thread::Accounts<Pubkey>,
+    params: close_group_thread::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::CloseGroupThread as u8, params)
+}
+pub fn close_group_index(
+    program_id: Pubkey,
+    accounts: close_group_index::Accounts<Pubkey>,
+    params: close_group_index::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::CloseGroupIndex as u8, params)
+}
+pub fn delete_subscription(
+    program_id: Pubkey,
+    accounts: delete_subscription::Accounts<Pubkey>,
+    params: delete_subscription::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::DeleteSubscription as u8, params)
+}
+pub fn join_group(
+    program_id: Pubkey,
+    accounts: join_group::Accounts<Pubkey>,
+    params: join_group::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::JoinGroup as u8, params)
+}
+pub fn leave_group(
+    program_id: Pubkey,
+    accounts: leave_group::Accounts<Pubkey>,
+    params: leave_group::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::LeaveGroup as u8, params)
+}
+pub fn create_invite(
+    program_id: Pubkey,
+    accounts: create_invite::Accounts<Pubkey>,
+    params: create_invite::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::CreateInvite as u8, params)
+}
+pub fn redeem_invite(
+    program_id: Pubkey,
+    accounts: redeem_invite::Accounts<Pubkey>,
+    params: redeem_invite::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::RedeemInvite as u8, params)
+}
+pub fn ban_member(
+    program_id: Pubkey,
+    accounts: ban_member::Accounts<Pubkey>,
+    params: ban_member::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::BanMember as u8, params)
+}
+pub fn unban_member(
+    program_id: Pubkey,
+    accounts: unban_member::Accounts<Pubkey>,
+    params: unban_member::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::UnbanMember as u8, params)
+}
+pub fn mute_member(
+    program_id: Pubkey,
+    accounts: mute_member::Accounts<Pubkey>,
+    params: mute_member::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::MuteMember as u8, params)
+}
+pub fn block_user(
+    program_id: Pubkey,
+    accounts: block_user::Accounts<Pubkey>,
+    params: block_user::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::BlockUser as u8, params)
+}
+pub fn unblock_user(
+    program_id: Pubkey,
+    accounts: unblock_user::Accounts<Pubkey>,
+    params: unblock_user::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::UnblockUser as u8, params)
+}
+pub fn accept_thread(
+    program_id: Pubkey,
+    accounts: accept_thread::Accounts<Pubkey>,
+    params: accept_thread::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::AcceptThread as u8, params)
+}
+pub fn decline_thread(
+    program_id: Pubkey,
+    accounts: decline_thread::Accounts<Pubkey>,
+    params: decline_thread::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::DeclineThread as u8, params)
+}
+pub fn add_contact(
+    program_id: Pubkey,
+    accounts: add_contact::Accounts<Pubkey>,
+    params: add_contact::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::AddContact as u8, params)
+}
+pub fn remove_contact(
+    program_id: Pubkey,
+    accounts: remove_contact::Accounts<Pubkey>,
+    params: remove_contact::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::RemoveContact as u8, params)
+}
+pub fn refund_escrow(
+    program_id: Pubkey,
+    accounts: refund_escrow::Accounts<Pubkey>,
+    params: refund_escrow::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::RefundEscrow as u8, params)
+}
+pub fn init_config(
+    program_id: Pubkey,
+    accounts: init_config::Accounts<Pubkey>,
+    params: init_config::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::InitConfig as u8, params)
+}
+pub fn update_config(
+    program_id: Pubkey,
+    accounts: update_config::Accounts<Pubkey>,
+    params: update_config::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::UpdateConfig as u8, params)
+}
+pub fn withdraw_fees(
+    program_id: Pubkey,
+    accounts: withdraw_fees::Accounts<Pubkey>,
+    params: withdraw_fees::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::WithdrawFees as u8, params)
+}
+pub fn register_referrer(
+    program_id: Pubkey,
+    accounts: register_referrer::Accounts<Pubkey>,
+    params: register_referrer::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::RegisterReferrer as u8, params)
+}
+pub fn propose_group_owner(
+    program_id: Pubkey,
+    accounts: propose_group_owner::Accounts<Pubkey>,
+    params: propose_group_owner::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::ProposeGroupOwner as u8, params)
+}
+pub fn accept_group_owner(
+    program_id: Pubkey,
+    accounts: accept_group_owner::Accounts<Pubkey>,
+    params: accept_group_owner::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::AcceptGroupOwner as u8, params)
+}
+pub fn grant_group_permissions(
+    program_id: Pubkey,
+    accounts: grant_group_permissions::Accounts<Pubkey>,
+    params: grant_group_permissions::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        JabInstruction::GrantGroupPermissions as u8,
+        params,
+    )
+}
+pub fn revoke_group_permissions(
+    program_id: Pubkey,
+    accounts: revoke_group_permissions::Accounts<Pubkey>,
+    params: revoke_group_permissions::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        JabInstruction::RevokeGroupPermissions as u8,
+        params,
+    )
+}
+pub fn rename_group(
+    program_id: Pubkey,
+    accounts: rename_group::Accounts<Pubkey>,
+    params: rename_group::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::RenameGroup as u8, params)
+}
+pub fn delete_bucket_message(
+    program_id: Pubkey,
+    accounts: delete_bucket_message::Accounts<Pubkey>,
+    params: delete_bucket_message::Params,
+) -> Instruction {
+    accounts.get_instruction(
+        program_id,
+        JabInstruction::DeleteBucketMessage as u8,
+        params,
+    )
+}
+pub fn migrate_account(
+    program_id: Pubkey,
+    accounts: migrate_account::Accounts<Pubkey>,
+    params: migrate_account::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::MigrateAccount as u8, params)
+}
+pub fn revoke_invite(
+    program_id: Pubkey,
+    accounts: revoke_invite::Accounts<Pubkey>,
+    params: revoke_invite::Params,
+) -> Instruction {
+    accounts.get_instruction(program_id, JabInstruction::RevokeInvite as u8, params)
+}
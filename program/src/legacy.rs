@@ -0,0 +1,205 @@
+//! Account layouts of the initial program version, converted by `MigrateAccount`
+use crate::state::{
+    GroupThread, Message, MessageType, Profile, SeedVersion, StorageMode, Tag, Thread, ThreadState,
+    DEFAULT_EDIT_WINDOW, MAX_BIO_LENGTH, MAX_GROUP_NAME_LEN, MAX_HASH_LEN, MAX_NAME_LENGTH,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{clock::UnixTimestamp, pubkey::Pubkey};
+use std::convert::TryInto;
+
+pub const MAX_ADMIN_LEN: usize = 10;
+
+pub const GROUP_THREAD_V0_LEN: usize = 1 // tag
+    + 1 // bump
+    + 1 // visible
+    + 32 // owner
+    + 8 // last message time
+    + 32 // destination_wallet
+    + 4 // msg_count
+    + 8 // lamports_per_message
+    + 1 // media_enabled
+    + 1 // admin_only
+    + (4 + MAX_HASH_LEN) // group_pic_hash
+    + (4 + MAX_GROUP_NAME_LEN) // group_name
+    + (4 + MAX_ADMIN_LEN * 32); // admins
+
+pub const PROFILE_V0_LEN: usize =
+    1 + 1 + MAX_HASH_LEN + MAX_NAME_LENGTH + MAX_BIO_LENGTH + 8 + 1 + 4 + 4;
+
+pub const SUBSCRIPTION_V0_LEN: usize = 32 + 32;
+
+pub const THREAD_V0_LEN: usize = 1 + 4 + 32 + 32 + 8 + 1;
+
+// Offset of the length of `msg`, shared by both message layouts
+const MESSAGE_MSG_OFFSET: usize = 1 + 1 + 8 + 32 + 32 + 2 + 2;
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ProfileV0 {
+    pub tag: Tag,
+    pub bump: u8,
+    pub picture_hash: String,
+    pub display_domain_name: String,
+    pub bio: String,
+    pub lamports_per_message: u64,
+    pub allow_dm: bool,
+    pub tips_sent: u32,
+    pub tips_received: u32,
+}
+
+impl ProfileV0 {
+    pub fn is_legacy(data: &[u8]) -> bool {
+        data.len() == PROFILE_V0_LEN
+    }
+
+    // The account is reallocated the next time the profile is written
+    pub fn migrate(self) -> Profile {
+        let mut profile = Profile::new(
+            self.picture_hash,
+            self.display_domain_name,
+            self.bio,
+            self.lamports_per_message,
+            self.bump,
+        );
+        profile.allow_dm = self.allow_dm;
+        profile.tips_sent = self.tips_sent;
+        profile.tips_received = self.tips_received;
+        profile
+    }
+}
+
+// Subscriptions were untagged and stored their own key in `subscribed_to`
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct SubscriptionV0 {
+    pub subscriber: Pubkey,
+    pub subscribed_to: Pubkey,
+}
+
+impl SubscriptionV0 {
+    pub fn is_legacy(data: &[u8]) -> bool {
+        data.len() == SUBSCRIPTION_V0_LEN
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ThreadV0 {
+    pub tag: Tag,
+    pub msg_count: u32,
+    pub user_1: Pubkey,
+    pub user_2: Pubkey,
+    pub last_message_time: UnixTimestamp,
+    pub bump: u8,
+}
+
+impl ThreadV0 {
+    pub fn is_legacy(data: &[u8]) -> bool {
+        data.len() == THREAD_V0_LEN
+    }
+
+    // Threads were accepted by default and could not delete messages
+    pub fn migrate(self) -> Thread {
+        Thread {
+            tag: Tag::Thread,
+            msg_count: self.msg_count,
+            user_1: self.user_1,
+            user_2: self.user_2,
+            last_message_time: self.last_message_time,
+            bump: self.bump,
+            open_msg_count: self.msg_count,
+            state: ThreadState::Accepted,
+            requester: Pubkey::default(),
+            payer: Pubkey::default(),
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MessageV0 {
+    pub tag: Tag,
+    pub kind: MessageType,
+    pub timestamp: UnixTimestamp,
+    pub sender: Pubkey,
+    pub replies_to: Pubkey,
+    pub likes_count: u16,
+    pub dislikes_count: u16,
+    pub msg: Vec<u8>,
+}
+
+impl MessageV0 {
+    // Messages are allocated to their exact size, the current layout has 8 more bytes
+    pub fn is_legacy(data: &[u8]) -> bool {
+        let len_bytes = match data.get(MESSAGE_MSG_OFFSET..MESSAGE_MSG_OFFSET + 4) {
+            Some(bytes) => bytes,
+            None => return false,
+        };
+        let msg_len = u32::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+        data.len() == MESSAGE_MSG_OFFSET + 4 + msg_len
+    }
+
+    pub fn migrate(self) -> Message {
+        Message {
+            tag: Tag::Message,
+            kind: self.kind,
+            timestamp: self.timestamp,
+            sender: self.sender,
+            replies_to: self.replies_to,
+            likes_count: self.likes_count,
+            dislikes_count: self.dislikes_count,
+            msg: self.msg,
+            edited_at: 0,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct GroupThreadV0 {
+    pub tag: Tag,
+    pub bump: u8,
+    pub visible: bool,
+    pub owner: Pubkey,
+    pub last_message_time: UnixTimestamp,
+    pub destination_wallet: Pubkey,
+    pub msg_count: u32,
+    pub lamports_per_message: u64,
+    pub media_enabled: bool,
+    pub admin_only: bool,
+    pub group_pic_hash: String,
+    pub group_name: String,
+    pub admins: Vec<Pubkey>,
+}
+
+impl GroupThreadV0 {
+    pub fn is_legacy(data: &[u8]) -> bool {
+        data.len() == GROUP_THREAD_V0_LEN
+    }
+
+    // Admins are migrated separately to `GroupAdmin` accounts
+    pub fn migrate(self) -> GroupThread {
+        GroupThread {
+            tag: Tag::GroupThread,
+            bump: self.bump,
+            visible: self.visible,
+            owner: self.owner,
+            creator: self.owner,
+            pending_owner: None,
+            last_message_time: self.last_message_time,
+            destination_wallet: self.destination_wallet,
+            msg_count: self.msg_count,
+            open_msg_count: self.msg_count,
+            lamports_per_message: self.lamports_per_message,
+            price_mint: None,
+            price_amount: 0,
+            media_enabled: self.media_enabled,
+            admin_only: self.admin_only,
+            members_only: false,
+            private: false,
+            edit_window: DEFAULT_EDIT_WINDOW,
+            group_pic_hash: self.group_pic_hash,
+            group_name: self.group_name,
+            seed_version: SeedVersion::V1,
+            group_id: [0; 32],
+            storage_mode: StorageMode::Accounts,
+            current_bucket: 0,
+            linked_account_count: 0,
+        }
+    }
+}
@@ -1,10 +1,14 @@
 pub mod entrypoint;
 pub mod error;
 
+pub mod events;
+
 pub mod instruction;
 
 pub mod state;
 
+pub mod legacy;
+
 pub(crate) mod processor;
 
 pub mod utils;
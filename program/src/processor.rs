@@ -6,20 +6,54 @@ use solana_program::{
 
 use crate::instruction::JabInstruction;
 
+pub mod accept_group_owner;
+pub mod accept_thread;
 pub mod add_admin_to_group;
+pub mod add_contact;
+pub mod ban_member;
+pub mod block_user;
+pub mod close_group_index;
+pub mod close_group_thread;
+pub mod close_profile;
+pub mod close_thread;
 pub mod create_group_index;
 pub mod create_group_thread;
+pub mod create_invite;
 pub mod create_profile;
 pub mod create_subscription;
 pub mod create_thread;
+pub mod decline_thread;
+pub mod delete_bucket_message;
 pub mod delete_group_message;
 pub mod delete_message;
+pub mod delete_subscription;
+pub mod edit_group_message;
 pub mod edit_group_thread;
+pub mod edit_message;
+pub mod grant_group_permissions;
+pub mod init_config;
+pub mod join_group;
+pub mod leave_group;
+pub mod migrate_account;
+pub mod mute_member;
+pub mod propose_group_owner;
+pub mod react_to_message;
+pub mod redeem_invite;
+pub mod refund_escrow;
+pub mod register_referrer;
 pub mod remove_admin_from_group;
+pub mod remove_contact;
+pub mod rename_group;
+pub mod revoke_group_permissions;
+pub mod revoke_invite;
 pub mod send_message;
 pub mod send_message_group;
 pub mod send_tip;
 pub mod set_user_profile;
+pub mod unban_member;
+pub mod unblock_user;
+pub mod update_config;
+pub mod withdraw_fees;
 
 pub struct Processor {}
 
@@ -120,6 +154,212 @@ impl Processor {
                     .map_err(|_| ProgramError::InvalidInstructionData)?;
                 create_subscription::process(program_id, accounts, params)?;
             }
+            JabInstruction::ReactToMessage => {
+                msg!("Instruction: React to message");
+                let params = react_to_message::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                react_to_message::process(program_id, accounts, params)?;
+            }
+            JabInstruction::EditMessage => {
+                msg!("Instruction: Edit message");
+                let params = edit_message::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                edit_message::process(program_id, accounts, params)?;
+            }
+            JabInstruction::EditGroupMessage => {
+                msg!("Instruction: Edit group message");
+                let params = edit_group_message::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                edit_group_message::process(program_id, accounts, params)?;
+            }
+            JabInstruction::CloseThread => {
+                msg!("Instruction: Close thread");
+                let params = close_thread::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                close_thread::process(program_id, accounts, params)?;
+            }
+            JabInstruction::CloseProfile => {
+                msg!("Instruction: Close profile");
+                let params = close_profile::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                close_profile::process(program_id, accounts, params)?;
+            }
+            JabInstruction::CloseGroupThread => {
+                msg!("Instruction: Close group thread");
+                let params = close_group_thread::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                close_group_thread::process(program_id, accounts, params)?;
+            }
+            JabInstruction::CloseGroupIndex => {
+                msg!("Instruction: Close group thread index");
+                let params = close_group_index::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                close_group_index::process(program_id, accounts, params)?;
+            }
+            JabInstruction::DeleteSubscription => {
+                msg!("Instruction: Delete subscription");
+                let params = delete_subscription::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                delete_subscription::process(program_id, accounts, params)?;
+            }
+            JabInstruction::JoinGroup => {
+                msg!("Instruction: Join group");
+                let params = join_group::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                join_group::process(program_id, accounts, params)?;
+            }
+            JabInstruction::LeaveGroup => {
+                msg!("Instruction: Leave group");
+                let params = leave_group::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                leave_group::process(program_id, accounts, params)?;
+            }
+            JabInstruction::CreateInvite => {
+                msg!("Instruction: Create invite");
+                let params = create_invite::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                create_invite::process(program_id, accounts, params)?;
+            }
+            JabInstruction::RedeemInvite => {
+                msg!("Instruction: Redeem invite");
+                let params = redeem_invite::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                redeem_invite::process(program_id, accounts, params)?;
+            }
+            JabInstruction::BanMember => {
+                msg!("Instruction: Ban member");
+                let params = ban_member::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                ban_member::process(program_id, accounts, params)?;
+            }
+            JabInstruction::UnbanMember => {
+                msg!("Instruction: Unban member");
+                let params = unban_member::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                unban_member::process(program_id, accounts, params)?;
+            }
+            JabInstruction::MuteMember => {
+                msg!("Instruction: Mute member");
+                let params = mute_member::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                mute_member::process(program_id, accounts, params)?;
+            }
+            JabInstruction::BlockUser => {
+                msg!("Instruction: Block user");
+                let params = block_user::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                block_user::process(program_id, accounts, params)?;
+            }
+            JabInstruction::UnblockUser => {
+                msg!("Instruction: Unblock user");
+                let params = unblock_user::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                unblock_user::process(program_id, accounts, params)?;
+            }
+            JabInstruction::AcceptThread => {
+                msg!("Instruction: Accept thread");
+                let params = accept_thread::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                accept_thread::process(program_id, accounts, params)?;
+            }
+            JabInstruction::DeclineThread => {
+                msg!("Instruction: Decline thread");
+                let params = decline_thread::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                decline_thread::process(program_id, accounts, params)?;
+            }
+            JabInstruction::AddContact => {
+                msg!("Instruction: Add contact");
+                let params = add_contact::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                add_contact::process(program_id, accounts, params)?;
+            }
+            JabInstruction::RemoveContact => {
+                msg!("Instruction: Remove contact");
+                let params = remove_contact::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                remove_contact::process(program_id, accounts, params)?;
+            }
+            JabInstruction::RefundEscrow => {
+                msg!("Instruction: Refund escrow");
+                let params = refund_escrow::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                refund_escrow::process(program_id, accounts, params)?;
+            }
+            JabInstruction::InitConfig => {
+                msg!("Instruction: Init config");
+                let params = init_config::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                init_config::process(program_id, accounts, params)?;
+            }
+            JabInstruction::UpdateConfig => {
+                msg!("Instruction: Update config");
+                let params = update_config::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                update_config::process(program_id, accounts, params)?;
+            }
+            JabInstruction::WithdrawFees => {
+                msg!("Instruction: Withdraw fees");
+                let params = withdraw_fees::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                withdraw_fees::process(program_id, accounts, params)?;
+            }
+            JabInstruction::RegisterReferrer => {
+                msg!("Instruction: Register referrer");
+                let params = register_referrer::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                register_referrer::process(program_id, accounts, params)?;
+            }
+            JabInstruction::ProposeGroupOwner => {
+                msg!("Instruction: Propose group owner");
+                let params = propose_group_owner::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                propose_group_owner::process(program_id, accounts, params)?;
+            }
+            JabInstruction::AcceptGroupOwner => {
+                msg!("Instruction: Accept group owner");
+                let params = accept_group_owner::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                accept_group_owner::process(program_id, accounts, params)?;
+            }
+            JabInstruction::GrantGroupPermissions => {
+                msg!("Instruction: Grant group permissions");
+                let params =
+                    grant_group_permissions::Params::try_from_slice(&instruction_data[1..])
+                        .map_err(|_| ProgramError::InvalidInstructionData)?;
+                grant_group_permissions::process(program_id, accounts, params)?;
+            }
+            JabInstruction::RevokeGroupPermissions => {
+                msg!("Instruction: Revoke group permissions");
+                let params =
+                    revoke_group_permissions::Params::try_from_slice(&instruction_data[1..])
+                        .map_err(|_| ProgramError::InvalidInstructionData)?;
+                revoke_group_permissions::process(program_id, accounts, params)?;
+            }
+            JabInstruction::RenameGroup => {
+                msg!("Instruction: Rename group");
+                let params = rename_group::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                rename_group::process(program_id, accounts, params)?;
+            }
+            JabInstruction::DeleteBucketMessage => {
+                msg!("Instruction: Delete bucket message");
+                let params = delete_bucket_message::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                delete_bucket_message::process(program_id, accounts, params)?;
+            }
+            JabInstruction::MigrateAccount => {
+                msg!("Instruction: Migrate account");
+                let params = migrate_account::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                migrate_account::process(program_id, accounts, params)?;
+            }
+            JabInstruction::RevokeInvite => {
+                msg!("Instruction: Revoke invite");
+                let params = revoke_invite::Params::try_from_slice(&instruction_data[1..])
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                revoke_invite::process(program_id, accounts, params)?;
+            }
         }
         Ok(())
     }
@@ -0,0 +1,78 @@
+//! Accept a pending thread
+use crate::error::JabError;
+use crate::state::{Thread, ThreadState};
+use crate::utils::{check_account_key, check_account_owner, check_signer};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The receiver account
+    #[cons(signer)]
+    pub receiver: &'a T,
+
+    /// The thread account
+    #[cons(writable)]
+    pub thread: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            receiver: next_account_info(accounts_iter)?,
+            thread: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(
+            accounts.thread,
+            program_id,
+            JabError::WrongThreadAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.receiver)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let mut thread = Thread::from_account_info(accounts.thread)?;
+    let thread_key = Thread::create_key(&thread.user_1, &thread.user_2, program_id, thread.bump);
+
+    check_account_key(
+        accounts.thread,
+        &thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    thread.check_can_respond(accounts.receiver.key)?;
+
+    thread.state = ThreadState::Accepted;
+    thread.save(&mut accounts.thread.data.borrow_mut());
+
+    Ok(())
+}
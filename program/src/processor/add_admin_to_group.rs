@@ -1,13 +1,18 @@
 //! Add an admin to the group
 use crate::error::JabError;
-use crate::state::GroupThread;
+use crate::state::{GroupAdmin, GroupThread};
 use crate::utils::{check_account_key, check_account_owner, check_signer};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    program::invoke_signed,
     program_error::ProgramError,
     pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::create_account,
+    system_program,
+    sysvar::Sysvar,
 };
 
 use bonfida_utils::{BorshSize, InstructionsAccount};
@@ -19,6 +24,9 @@ pub struct Params {
 
 #[derive(InstructionsAccount)]
 pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
     /// The group thread account
     #[cons(writable)]
     pub group_thread: &'a T,
@@ -26,6 +34,10 @@ pub struct Accounts<'a, T> {
     /// The group owner account
     #[cons(writable, signer)]
     pub group_owner: &'a T,
+
+    /// The group admin account
+    #[cons(writable)]
+    pub group_admin: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -36,11 +48,18 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts_iter = &mut accounts.iter();
 
         let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
             group_thread: next_account_info(accounts_iter)?,
             group_owner: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
         };
 
         // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
 
         // Check ownership
         check_account_owner(
@@ -48,6 +67,11 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             program_id,
             JabError::WrongGroupThreadOwner,
         )?;
+        check_account_owner(
+            accounts.group_admin,
+            &system_program::ID,
+            JabError::WrongOwner,
+        )?;
 
         // Check signer
         check_signer(accounts.group_owner)?;
@@ -66,11 +90,7 @@ pub(crate) fn process(
 
     let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
 
-    let (expected_group_thread_key, _) = GroupThread::find_key(
-        group_thread.group_name.clone(),
-        *accounts.group_owner.key,
-        program_id,
-    );
+    let expected_group_thread_key = group_thread.key(program_id);
     check_account_key(
         accounts.group_thread,
         &expected_group_thread_key,
@@ -83,7 +103,44 @@ pub(crate) fn process(
         JabError::WrongGroupOwner,
     )?;
 
-    group_thread.add_admin(admin_address)?;
+    let (group_admin_key, bump) =
+        GroupAdmin::find_key(&expected_group_thread_key, &admin_address, program_id);
+    check_account_key(
+        accounts.group_admin,
+        &group_admin_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let group_admin = GroupAdmin::new(expected_group_thread_key, admin_address);
+    let space = group_admin.borsh_len();
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    let allocate_account = create_account(
+        accounts.group_owner.key,
+        &group_admin_key,
+        lamports,
+        space as u64,
+        program_id,
+    );
+
+    invoke_signed(
+        &allocate_account,
+        &[
+            accounts.system_program.clone(),
+            accounts.group_owner.clone(),
+            accounts.group_admin.clone(),
+        ],
+        &[&[
+            GroupAdmin::SEED.as_bytes(),
+            &expected_group_thread_key.to_bytes(),
+            &admin_address.to_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    group_admin.save(&mut accounts.group_admin.data.borrow_mut());
+
+    group_thread.add_linked_account();
     group_thread.save(&mut accounts.group_thread.data.borrow_mut());
 
     Ok(())
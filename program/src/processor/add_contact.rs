@@ -0,0 +1,139 @@
+//! Add a user to the contact list
+use crate::error::JabError;
+use crate::state::Contact;
+use crate::utils::{check_account_key, check_account_owner, check_signer};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::create_account,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub contact: Pubkey,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The contact list owner account
+    #[cons(writable, signer)]
+    pub owner: &'a T,
+
+    /// The contact account
+    #[cons(writable)]
+    pub contact: &'a T,
+
+    /// The contact account of the owner in the contact list (may be empty)
+    #[cons(writable)]
+    pub reverse_contact: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            owner: next_account_info(accounts_iter)?,
+            contact: next_account_info(accounts_iter)?,
+            reverse_contact: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.contact, &system_program::ID, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params { contact } = params;
+
+    if accounts.owner.key == &contact {
+        msg!("Cannot add yourself as a contact");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (contact_key, bump) = Contact::find_key(accounts.owner.key, &contact, program_id);
+    check_account_key(
+        accounts.contact,
+        &contact_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let (reverse_contact_key, _) = Contact::find_key(&contact, accounts.owner.key, program_id);
+    check_account_key(
+        accounts.reverse_contact,
+        &reverse_contact_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let mutual = !accounts.reverse_contact.data_is_empty();
+    if mutual {
+        check_account_owner(accounts.reverse_contact, program_id, JabError::WrongOwner)?;
+        let mut reverse_contact = Contact::from_account_info(accounts.reverse_contact)?;
+        reverse_contact.mutual = true;
+        reverse_contact.save(&mut accounts.reverse_contact.data.borrow_mut());
+    }
+
+    let contact_account = Contact::new(*accounts.owner.key, contact, mutual);
+    let space = contact_account.borsh_len();
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    let allocate_account = create_account(
+        accounts.owner.key,
+        &contact_key,
+        lamports,
+        space as u64,
+        program_id,
+    );
+
+    invoke_signed(
+        &allocate_account,
+        &[
+            accounts.system_program.clone(),
+            accounts.owner.clone(),
+            accounts.contact.clone(),
+        ],
+        &[&[
+            Contact::SEED.as_bytes(),
+            &accounts.owner.key.to_bytes(),
+            &contact.to_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    contact_account.save(&mut accounts.contact.data.borrow_mut());
+
+    Ok(())
+}
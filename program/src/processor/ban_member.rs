@@ -0,0 +1,181 @@
+//! Ban a user from a group
+use crate::error::JabError;
+use crate::state::{GroupPermission, GroupThread};
+use crate::utils::{
+    check_account_key, check_account_owner, check_group_permission, check_outranks, check_signer,
+    close_account, load_group_admin, load_group_member, load_or_create_group_moderation,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub user: Pubkey,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The group owner or admin account
+    #[cons(writable, signer)]
+    pub moderator: &'a T,
+
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+
+    /// The group moderation account
+    #[cons(writable)]
+    pub group_moderation: &'a T,
+
+    /// The moderator group membership account (may be empty)
+    pub group_member: &'a T,
+
+    /// The moderator group admin account (may be empty)
+    pub group_admin: &'a T,
+
+    /// The banned user account
+    #[cons(writable)]
+    pub user: &'a T,
+
+    /// The banned user group membership account (may be empty)
+    #[cons(writable)]
+    pub user_group_member: &'a T,
+
+    /// The banned user group admin account (may be empty)
+    pub user_group_admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            moderator: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+            group_moderation: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
+            user: next_account_info(accounts_iter)?,
+            user_group_member: next_account_info(accounts_iter)?,
+            user_group_admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongGroupThreadOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.moderator)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params { user } = params;
+
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    check_account_key(accounts.user, &user, JabError::AccountNotDeterministic)?;
+
+    let group_member = load_group_member(
+        accounts.group_member,
+        &group_thread_key,
+        accounts.moderator.key,
+        program_id,
+    )?;
+    let group_admin = load_group_admin(
+        accounts.group_admin,
+        &group_thread_key,
+        accounts.moderator.key,
+        program_id,
+    )?;
+    check_group_permission(
+        &group_thread,
+        accounts.moderator.key,
+        group_admin.as_ref(),
+        group_member.as_ref(),
+        GroupPermission::BAN,
+    )?;
+
+    let user_group_member = load_group_member(
+        accounts.user_group_member,
+        &group_thread_key,
+        &user,
+        program_id,
+    )?;
+    let user_group_admin = load_group_admin(
+        accounts.user_group_admin,
+        &group_thread_key,
+        &user,
+        program_id,
+    )?;
+    check_outranks(
+        group_thread.permissions(
+            accounts.moderator.key,
+            group_admin.as_ref(),
+            group_member.as_ref(),
+        ),
+        group_thread.permissions(&user, user_group_admin.as_ref(), user_group_member.as_ref()),
+    )?;
+
+    if accounts.group_moderation.data_is_empty() {
+        group_thread.add_linked_account();
+    }
+    let mut group_moderation = load_or_create_group_moderation(
+        accounts.group_moderation,
+        accounts.moderator,
+        accounts.system_program,
+        &group_thread_key,
+        &user,
+        program_id,
+    )?;
+
+    group_moderation.banned = true;
+    group_moderation.save(&mut accounts.group_moderation.data.borrow_mut());
+
+    // The membership rent goes back to the banned user who paid for it
+    if user_group_member.is_some() {
+        close_account(accounts.user_group_member, accounts.user)?;
+        group_thread.remove_linked_account();
+    }
+
+    group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! Block a user from sending direct messages
+use crate::error::JabError;
+use crate::state::Block;
+use crate::utils::{check_account_key, check_account_owner, check_signer};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::create_account,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub blocked: Pubkey,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The blocker account
+    #[cons(writable, signer)]
+    pub blocker: &'a T,
+
+    /// The block account
+    #[cons(writable)]
+    pub block: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            blocker: next_account_info(accounts_iter)?,
+            block: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.block, &system_program::ID, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.blocker)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params { blocked } = params;
+
+    if accounts.blocker.key == &blocked {
+        msg!("Cannot block yourself");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (block_key, bump) = Block::find_key(accounts.blocker.key, &blocked, program_id);
+
+    check_account_key(
+        accounts.block,
+        &block_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let block = Block::new(*accounts.blocker.key, blocked);
+    let space = block.borsh_len();
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    let allocate_account = create_account(
+        accounts.blocker.key,
+        &block_key,
+        lamports,
+        space as u64,
+        program_id,
+    );
+
+    invoke_signed(
+        &allocate_account,
+        &[
+            accounts.system_program.clone(),
+            accounts.blocker.clone(),
+            accounts.block.clone(),
+        ],
+        &[&[
+            Block::SEED.as_bytes(),
+            &accounts.blocker.key.to_bytes(),
+            &blocked.to_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    block.save(&mut accounts.block.data.borrow_mut());
+
+    Ok(())
+}
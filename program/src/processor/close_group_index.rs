@@ -0,0 +1,100 @@
+//! Close a group index
+use crate::error::JabError;
+use crate::state::GroupThreadIndex;
+use crate::utils::{check_account_key, check_account_owner, check_signer, close_account};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The group thread index owner account
+    #[cons(writable, signer)]
+    pub owner: &'a T,
+
+    /// The group thread index account
+    #[cons(writable)]
+    pub group_thread_index: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            owner: next_account_info(accounts_iter)?,
+            group_thread_index: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread_index,
+            program_id,
+            JabError::WrongOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let group_thread_index = GroupThreadIndex::from_account_info(accounts.group_thread_index)?;
+
+    let (mut expected_group_thread_index_key, _) = GroupThreadIndex::find_key(
+        group_thread_index.group_name.clone(),
+        group_thread_index.group_thread_key,
+        group_thread_index.owner,
+        program_id,
+    );
+
+    // Indexes created before hashed seeds are derived from the raw group name
+    if accounts.group_thread_index.key != &expected_group_thread_index_key
+        && group_thread_index.group_name.len() <= MAX_SEED_LEN
+    {
+        expected_group_thread_index_key = GroupThreadIndex::find_key_v1(
+            group_thread_index.group_name,
+            group_thread_index.group_thread_key,
+            group_thread_index.owner,
+            program_id,
+        )
+        .0;
+    }
+
+    check_account_key(
+        accounts.group_thread_index,
+        &expected_group_thread_index_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    check_account_key(
+        accounts.owner,
+        &group_thread_index.owner,
+        JabError::AccountNotAuthorized,
+    )?;
+
+    close_account(accounts.group_thread_index, accounts.owner)?;
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//! Close a group thread
+use crate::error::JabError;
+use crate::state::GroupThread;
+use crate::utils::{check_account_key, check_account_owner, check_signer, close_account};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The group owner account
+    #[cons(writable, signer)]
+    pub group_owner: &'a T,
+
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            group_owner: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongGroupThreadOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.group_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+
+    let expected_group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &expected_group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    check_account_key(
+        accounts.group_owner,
+        &group_thread.owner,
+        JabError::WrongGroupOwner,
+    )?;
+
+    // Message buckets are derived from the bucket index,
+    // which restarts at 0 if the group is recreated.
+    // Messages stored in their own account are keyed by sender and nonce and are not counted
+    if group_thread.open_msg_count != 0 {
+        return Err(JabError::ThreadNotEmpty.into());
+    }
+
+    // Members, admins, moderations and invites are keyed by the group address and would apply to
+    // a group recreated at the same address
+    if group_thread.linked_account_count != 0 {
+        return Err(JabError::GroupNotEmpty.into());
+    }
+
+    close_account(accounts.group_thread, accounts.group_owner)?;
+
+    Ok(())
+}
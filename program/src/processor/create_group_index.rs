@@ -1,7 +1,7 @@
 //! Create a group index for a user
 use crate::error::JabError;
-use crate::state::GroupThreadIndex;
-use crate::utils::{check_account_key, check_account_owner};
+use crate::state::{group_name_seed, GroupThreadIndex};
+use crate::utils::{check_account_key, check_account_owner, check_signer};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -21,7 +21,6 @@ use bonfida_utils::{BorshSize, InstructionsAccount};
 pub struct Params {
     pub group_name: String,
     pub group_thread_key: Pubkey,
-    pub owner: Pubkey,
 }
 
 #[derive(InstructionsAccount)]
@@ -36,6 +35,10 @@ pub struct Accounts<'a, T> {
     /// The fee payer account
     #[cons(writable, signer)]
     pub fee_payer: &'a T,
+
+    /// The account of the indexed user
+    #[cons(signer)]
+    pub owner: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -49,6 +52,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             system_program: next_account_info(accounts_iter)?,
             group_thread_index: next_account_info(accounts_iter)?,
             fee_payer: next_account_info(accounts_iter)?,
+            owner: next_account_info(accounts_iter)?,
         };
 
         // Check keys
@@ -65,6 +69,10 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             JabError::WrongOwner,
         )?;
 
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.owner)?;
+
         Ok(accounts)
     }
 }
@@ -78,8 +86,8 @@ pub(crate) fn process(
     let Params {
         group_name,
         group_thread_key,
-        owner,
     } = params;
+    let owner = *accounts.owner.key;
 
     let (group_thread_index_key, bump) =
         GroupThreadIndex::find_key(group_name.to_string(), group_thread_key, owner, program_id);
@@ -111,7 +119,7 @@ pub(crate) fn process(
         ],
         &[&[
             GroupThreadIndex::SEED.as_bytes(),
-            group_name.as_bytes(),
+            &group_name_seed(&group_name),
             &owner.to_bytes(),
             &group_thread_key.to_bytes(),
             &[bump],
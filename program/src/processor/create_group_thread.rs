@@ -1,6 +1,6 @@
 //! Create a group thread
 use crate::error::JabError;
-use crate::state::{GroupThread, MAX_GROUP_THREAD_LEN};
+use crate::state::{GroupThread, StorageMode, MAX_GROUP_THREAD_LEN};
 use crate::utils::{check_account_key, check_account_owner, check_group_thread_params};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
@@ -21,13 +21,19 @@ use bonfida_utils::{BorshSize, InstructionsAccount};
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
     pub visible: bool,
+    // Stable identifier of the group, e.g. random bytes
+    pub group_id: [u8; 32],
     pub group_name: String,
     pub destination_wallet: Pubkey,
     pub lamports_per_message: u64,
-    pub admins: Vec<Pubkey>,
+    pub price_mint: Option<Pubkey>,
+    pub price_amount: u64,
     pub owner: Pubkey,
     pub media_enabled: bool,
     pub admin_only: bool,
+    pub members_only: bool,
+    pub private: bool,
+    pub storage_mode: StorageMode,
 }
 
 #[derive(InstructionsAccount)]
@@ -83,18 +89,23 @@ pub(crate) fn process(
     let accounts = Accounts::parse(program_id, accounts)?;
     let Params {
         visible,
+        group_id,
         group_name,
         destination_wallet,
         lamports_per_message,
-        admins,
+        price_mint,
+        price_amount,
         owner,
         media_enabled,
         admin_only,
+        members_only,
+        private,
+        storage_mode,
     } = params;
 
-    let (group_thread_key, bump) = GroupThread::find_key(group_name.to_string(), owner, program_id);
+    let (group_thread_key, bump) = GroupThread::find_key(group_id, owner, program_id);
 
-    check_group_thread_params(&group_name, &admins)?;
+    check_group_thread_params(&group_name)?;
 
     check_account_key(
         accounts.group_thread,
@@ -120,7 +131,7 @@ pub(crate) fn process(
         ],
         &[&[
             GroupThread::SEED.as_bytes(),
-            group_name.as_bytes(),
+            &group_id,
             &owner.to_bytes(),
             &[bump],
         ]],
@@ -130,14 +141,19 @@ pub(crate) fn process(
 
     let group_thread = GroupThread::new(
         visible,
+        group_id,
         group_name,
         destination_wallet,
         lamports_per_message,
+        price_mint,
+        price_amount,
         bump,
-        admins,
         owner,
         media_enabled,
         admin_only,
+        members_only,
+        private,
+        storage_mode,
         current_time,
     );
 
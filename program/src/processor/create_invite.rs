@@ -0,0 +1,188 @@
+//! Create an invite to a group
+use crate::error::JabError;
+use crate::state::{GroupPermission, GroupThread, Invite};
+use crate::utils::{
+    check_account_key, check_account_owner, check_group_permission, check_signer, load_group_admin,
+    load_group_member,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::create_account,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub code_key: Pubkey,
+    pub expires_at: i64,
+    pub max_uses: u32,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The group owner or admin account
+    #[cons(writable, signer)]
+    pub creator: &'a T,
+
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+
+    /// The invite account
+    #[cons(writable)]
+    pub invite: &'a T,
+
+    /// The creator group membership account (may be empty)
+    pub group_member: &'a T,
+
+    /// The creator group admin account (may be empty)
+    pub group_admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            creator: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+            invite: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongThreadAccountOwner,
+        )?;
+        check_account_owner(accounts.invite, &system_program::ID, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.creator)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params {
+        code_key,
+        expires_at,
+        max_uses,
+    } = params;
+
+    // Such invites could never be redeemed
+    if expires_at <= Clock::get()?.unix_timestamp {
+        return Err(JabError::InvalidInviteExpiry.into());
+    }
+    if max_uses == 0 {
+        return Err(JabError::InvalidInviteMaxUses.into());
+    }
+
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let group_member = load_group_member(
+        accounts.group_member,
+        &group_thread_key,
+        accounts.creator.key,
+        program_id,
+    )?;
+    let group_admin = load_group_admin(
+        accounts.group_admin,
+        &group_thread_key,
+        accounts.creator.key,
+        program_id,
+    )?;
+    check_group_permission(
+        &group_thread,
+        accounts.creator.key,
+        group_admin.as_ref(),
+        group_member.as_ref(),
+        GroupPermission::INVITE,
+    )?;
+
+    let (invite_key, bump) = Invite::find_key(&group_thread_key, &code_key, program_id);
+
+    check_account_key(
+        accounts.invite,
+        &invite_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let invite = Invite::new(
+        group_thread_key,
+        code_key,
+        *accounts.creator.key,
+        expires_at,
+        max_uses,
+    );
+    let space = invite.borsh_len();
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    let allocate_account = create_account(
+        accounts.creator.key,
+        &invite_key,
+        lamports,
+        space as u64,
+        program_id,
+    );
+
+    invoke_signed(
+        &allocate_account,
+        &[
+            accounts.system_program.clone(),
+            accounts.creator.clone(),
+            accounts.invite.clone(),
+        ],
+        &[&[
+            Invite::SEED.as_bytes(),
+            &group_thread_key.to_bytes(),
+            &code_key.to_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    invite.save(&mut accounts.invite.data.borrow_mut());
+
+    group_thread.add_linked_account();
+    group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+
+    Ok(())
+}
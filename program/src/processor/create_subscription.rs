@@ -12,7 +12,8 @@ use solana_program::{
     sysvar::Sysvar,
 };
 
-use crate::state::Subscription;
+use crate::state::{Profile, Subscription};
+use crate::utils::save_profile;
 
 use bonfida_utils::{
     checks::{check_account_key, check_account_owner, check_signer},
@@ -36,11 +37,15 @@ pub struct Accounts<'a, T> {
 
     /// The system program account
     pub system_program: &'a T,
+
+    /// The profile account of the user subscribed to
+    #[cons(writable)]
+    pub subscribed_to_profile: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     pub fn parse(
-        _program_id: &Pubkey,
+        program_id: &Pubkey,
         accounts: &'a [AccountInfo<'b>],
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
@@ -48,6 +53,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             subscription: next_account_info(accounts_iter)?,
             subscriber: next_account_info(accounts_iter)?,
             system_program: next_account_info(accounts_iter)?,
+            subscribed_to_profile: next_account_info(accounts_iter)?,
         };
 
         // Check keys
@@ -55,6 +61,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
 
         // Check ownership
         check_account_owner(accounts.subscription, &system_program::ID)?;
+        check_account_owner(accounts.subscribed_to_profile, program_id)?;
 
         // Check signer
         check_signer(accounts.subscriber)?;
@@ -76,7 +83,10 @@ pub(crate) fn process(
 
     check_account_key(accounts.subscription, &subscription_key)?;
 
-    let subscription = Subscription::new(*accounts.subscriber.key, *accounts.subscription.key);
+    let (subscribed_to_profile_key, _) = Profile::find_key(&subscribed_to, program_id);
+    check_account_key(accounts.subscribed_to_profile, &subscribed_to_profile_key)?;
+
+    let subscription = Subscription::new(*accounts.subscriber.key, subscribed_to);
 
     let space = subscription.borsh_len();
     let lamports = Rent::get()?.minimum_balance(space);
@@ -106,5 +116,14 @@ pub(crate) fn process(
 
     subscription.save(&mut accounts.subscription.try_borrow_mut_data()?);
 
+    let mut profile = Profile::from_account_info(accounts.subscribed_to_profile)?;
+    profile.subscriber_count += 1;
+    save_profile(
+        &profile,
+        accounts.subscribed_to_profile,
+        accounts.subscriber,
+        accounts.system_program,
+    )?;
+
     Ok(())
 }
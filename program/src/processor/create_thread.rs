@@ -87,7 +87,7 @@ pub(crate) fn process(
 
     let (key_1, key_2) = order_keys(&receiver_key, &sender_key);
     let current_time = Clock::get()?.unix_timestamp;
-    let thread = Thread::new(key_1, key_2, bump, current_time);
+    let thread = Thread::new(key_1, key_2, bump, current_time, *accounts.fee_payer.key);
 
     let lamports = Rent::get()?.minimum_balance(thread.borsh_len());
 
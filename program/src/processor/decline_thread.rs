@@ -0,0 +1,141 @@
+//! Decline a pending thread, closing it, its request message and the escrow of the request
+use crate::error::JabError;
+use crate::state::{Escrow, Message, Thread};
+use crate::utils::{check_account_key, check_account_owner, check_signer, close_account};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The receiver account
+    #[cons(signer)]
+    pub receiver: &'a T,
+
+    /// The thread account
+    #[cons(writable)]
+    pub thread: &'a T,
+
+    /// The account that paid for the thread
+    #[cons(writable)]
+    pub thread_payer: &'a T,
+
+    /// The request message account (may be empty)
+    #[cons(writable)]
+    pub message: &'a T,
+
+    /// The requester account
+    #[cons(writable)]
+    pub requester: &'a T,
+
+    /// The request message escrow account (may be empty)
+    #[cons(writable)]
+    pub escrow: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            receiver: next_account_info(accounts_iter)?,
+            thread: next_account_info(accounts_iter)?,
+            thread_payer: next_account_info(accounts_iter)?,
+            message: next_account_info(accounts_iter)?,
+            requester: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(
+            accounts.thread,
+            program_id,
+            JabError::WrongThreadAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.receiver)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let thread = Thread::from_account_info(accounts.thread)?;
+    let thread_key = Thread::create_key(&thread.user_1, &thread.user_2, program_id, thread.bump);
+
+    check_account_key(
+        accounts.thread,
+        &thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    thread.check_can_respond(accounts.receiver.key)?;
+
+    check_account_key(
+        accounts.thread_payer,
+        &thread.payer,
+        JabError::AccountNotAuthorized,
+    )?;
+
+    let (message_key, _) = Message::find_key(0, &thread.user_1, &thread.user_2, program_id);
+
+    check_account_key(
+        accounts.message,
+        &message_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    // The request message may already have been deleted by its sender
+    if !accounts.message.data_is_empty() {
+        check_account_owner(accounts.message, program_id, JabError::WrongMessageOwner)?;
+        let message = Message::from_account_info(accounts.message)?;
+        check_account_key(
+            accounts.requester,
+            &message.sender,
+            JabError::AccountNotAuthorized,
+        )?;
+        close_account(accounts.message, accounts.requester)?;
+    }
+
+    // The payment held for the request goes back to the requester
+    let (escrow_key, _) = Escrow::find_key(&message_key, program_id);
+    check_account_key(
+        accounts.escrow,
+        &escrow_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    if !accounts.escrow.data_is_empty() {
+        check_account_owner(accounts.escrow, program_id, JabError::WrongOwner)?;
+        let escrow = Escrow::from_account_info(accounts.escrow)?;
+        check_account_key(
+            accounts.requester,
+            &escrow.sender,
+            JabError::AccountNotAuthorized,
+        )?;
+        close_account(accounts.escrow, accounts.requester)?;
+    }
+
+    close_account(accounts.thread, accounts.thread_payer)?;
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+//! Delete a message stored in a message bucket
+use crate::{
+    state::{GroupPermission, GroupThread, MessageBucket},
+    utils::{
+        check_account_key, check_account_owner, check_group_permission, check_signer,
+        close_account, load_group_admin, load_group_member,
+    },
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::error::JabError;
+use crate::events::MessageDeleted;
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub bucket_index: u32,
+    pub slot: u16,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+
+    /// The message bucket account
+    #[cons(writable)]
+    pub message_bucket: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The account that paid for the bucket
+    #[cons(writable)]
+    pub bucket_payer: &'a T,
+
+    /// The fee payer group membership account (may be empty)
+    pub group_member: &'a T,
+
+    /// The fee payer group admin account (may be empty)
+    pub group_admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            group_thread: next_account_info(accounts_iter)?,
+            message_bucket: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            bucket_payer: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongGroupThreadOwner,
+        )?;
+        check_account_owner(accounts.message_bucket, program_id, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params { bucket_index, slot } = params;
+
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let (message_bucket_key, _) =
+        MessageBucket::find_key(&group_thread_key, bucket_index, program_id);
+    check_account_key(
+        accounts.message_bucket,
+        &message_bucket_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let mut message_bucket = MessageBucket::from_account_info(accounts.message_bucket)?;
+    check_account_key(
+        accounts.bucket_payer,
+        &message_bucket.payer,
+        JabError::WrongOwner,
+    )?;
+
+    let message = message_bucket.delete(slot)?;
+
+    // The message can be deleted by:
+    // - The original sender
+    // - Members allowed to delete messages (owner, admins and moderators by default)
+    if *accounts.fee_payer.key != message.sender {
+        let group_member = load_group_member(
+            accounts.group_member,
+            &group_thread_key,
+            accounts.fee_payer.key,
+            program_id,
+        )?;
+        let group_admin = load_group_admin(
+            accounts.group_admin,
+            &group_thread_key,
+            accounts.fee_payer.key,
+            program_id,
+        )?;
+        check_group_permission(
+            &group_thread,
+            accounts.fee_payer.key,
+            group_admin.as_ref(),
+            group_member.as_ref(),
+            GroupPermission::DELETE_MESSAGES,
+        )?;
+    }
+
+    // The rent goes back to the payer of the bucket once all its messages are deleted
+    if message_bucket.live_count == 0 {
+        close_account(accounts.message_bucket, accounts.bucket_payer)?;
+    } else {
+        message_bucket.save(&mut accounts.message_bucket.data.borrow_mut());
+    }
+
+    group_thread.decrement_open_msg_count();
+    group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+
+    MessageDeleted {
+        message: *accounts.message_bucket.key,
+        sender: message.sender,
+        deleted_by: *accounts.fee_payer.key,
+        timestamp: Clock::get()?.unix_timestamp,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -1,27 +1,31 @@
 //! Delete a message sent to a group
 use crate::{
-    state::GroupThread,
-    utils::{check_account_key, check_account_owner, check_keys, check_names, check_signer},
+    state::{GroupPermission, GroupThread},
+    utils::{
+        check_account_key, check_account_owner, check_group_message_key, check_group_permission,
+        check_signer, close_account, load_group_admin, load_group_member,
+    },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 
 use crate::error::JabError;
-use crate::state::{Message, MessageType};
+use crate::events::MessageDeleted;
+use crate::state::Message;
 
 use bonfida_utils::{BorshSize, InstructionsAccount};
 
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
-    pub message_index: u32,
-    pub owner: Pubkey,
-    pub admin_index: Option<u64>,
-    pub group_name: String,
+    // Sender nonce of the message, or its index for messages sent before nonces
+    pub nonce: u32,
 }
 
 #[derive(InstructionsAccount)]
@@ -36,6 +40,21 @@ pub struct Accounts<'a, T> {
     /// The fee payer account
     #[cons(writable, signer)]
     pub fee_payer: &'a T,
+
+    /// The message sender account
+    #[cons(writable)]
+    pub sender: &'a T,
+
+    /// The fee payer group membership account (may be empty)
+    pub group_member: &'a T,
+
+    /// The fee payer group admin account (may be empty)
+    pub group_admin: &'a T,
+
+    /// The group thread account again, writable, when the message is keyed by the legacy group
+    /// counter
+    #[cons(writable)]
+    pub legacy_group_thread: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -48,6 +67,10 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             group_thread: next_account_info(accounts_iter)?,
             message: next_account_info(accounts_iter)?,
             fee_payer: next_account_info(accounts_iter)?,
+            sender: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
+            legacy_group_thread: next_account_info(accounts_iter).ok(),
         };
 
         // Check keys
@@ -74,60 +97,81 @@ pub(crate) fn process(
 ) -> ProgramResult {
     let accounts = Accounts::parse(program_id, accounts)?;
 
-    let mut message = Message::from_account_info(accounts.message)?;
-    let group_thread = GroupThread::from_account_info(accounts.group_thread)?;
-
-    let (expected_message_key, _) = Message::find_key(
-        params.message_index,
-        accounts.group_thread.key,
-        accounts.group_thread.key,
-        program_id,
-    );
-
-    let (expected_group_key, _) =
-        GroupThread::find_key(params.group_name.to_string(), params.owner, program_id);
+    // Legacy messages can be deleted without being migrated first
+    let message = Message::from_account_info_read_only(accounts.message)?;
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
 
-    check_keys(&params.owner, &group_thread.owner)?;
-    check_names(&params.group_name, &group_thread.group_name)?;
+    let expected_group_key = group_thread.key(program_id);
 
     check_account_key(
         accounts.group_thread,
         &expected_group_key,
         JabError::AccountNotDeterministic,
     )?;
-    check_account_key(
+    let is_legacy = check_group_message_key(
         accounts.message,
-        &expected_message_key,
+        &expected_group_key,
+        &message.sender,
+        params.nonce,
+        program_id,
+    )?;
+    check_account_key(
+        accounts.sender,
+        &message.sender,
         JabError::AccountNotDeterministic,
     )?;
 
     // The message can be deleted by:
     // - The original sender
-    // - The owner of the group
-    // - An admin of the group
-
+    // - Members allowed to delete messages (owner, admins and moderators by default)
     let is_sender = *accounts.fee_payer.key == message.sender;
-    let is_owner = *accounts.fee_payer.key == group_thread.owner;
-
-    let mut is_admin = false;
-
-    if let Some(index) = params.admin_index {
-        is_admin = group_thread.admins.get(index as usize).unwrap() == accounts.fee_payer.key;
+    if !is_sender {
+        let group_member = load_group_member(
+            accounts.group_member,
+            &expected_group_key,
+            accounts.fee_payer.key,
+            program_id,
+        )?;
+        let group_admin = load_group_admin(
+            accounts.group_admin,
+            &expected_group_key,
+            accounts.fee_payer.key,
+            program_id,
+        )?;
+        check_group_permission(
+            &group_thread,
+            accounts.fee_payer.key,
+            group_admin.as_ref(),
+            group_member.as_ref(),
+            GroupPermission::DELETE_MESSAGES,
+        )?;
     }
 
-    if !(is_admin || is_sender || is_owner) {
-        return Err(JabError::AccountNotAuthorized.into());
+    // Rent always goes back to the sender, moderators only pay the transaction fee
+    close_account(accounts.message, accounts.sender)?;
+
+    // Only messages keyed by the legacy counter are counted, other deletions do not write lock the
+    // group thread
+    if is_legacy {
+        let legacy_group_thread = accounts
+            .legacy_group_thread
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        check_account_key(
+            legacy_group_thread,
+            &expected_group_key,
+            JabError::AccountNotDeterministic,
+        )?;
+        group_thread.decrement_open_msg_count();
+        group_thread.save(&mut legacy_group_thread.data.borrow_mut());
     }
 
-    message.kind = MessageType::Deleted;
-    message.save(&mut accounts.message.data.borrow_mut());
-
-    let mut message_lamports = accounts.message.lamports.borrow_mut();
-    let mut target_lamports = accounts.fee_payer.lamports.borrow_mut();
-
-    **target_lamports += **message_lamports;
-
-    **message_lamports = 0;
+    MessageDeleted {
+        message: *accounts.message.key,
+        sender: message.sender,
+        deleted_by: *accounts.fee_payer.key,
+        timestamp: Clock::get()?.unix_timestamp,
+    }
+    .emit();
 
     Ok(())
 }
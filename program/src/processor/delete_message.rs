@@ -1,15 +1,18 @@
 //! Delete a message (DM)
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{check_account_key, check_account_owner, check_signer, close_account};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     program_error::ProgramError,
     pubkey::Pubkey,
+    sysvar::Sysvar,
 };
 
 use crate::error::JabError;
-use crate::state::{Message, MessageType};
+use crate::events::MessageDeleted;
+use crate::state::{Message, Thread};
 
 use bonfida_utils::{BorshSize, InstructionsAccount};
 
@@ -29,6 +32,10 @@ pub struct Accounts<'a, T> {
     /// The message account
     #[cons(writable)]
     pub message: &'a T,
+
+    /// The thread account
+    #[cons(writable)]
+    pub thread: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -41,12 +48,18 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             sender: next_account_info(accounts_iter)?,
             receiver: next_account_info(accounts_iter)?,
             message: next_account_info(accounts_iter)?,
+            thread: next_account_info(accounts_iter)?,
         };
 
         // Check keys
 
         // Check ownership
         check_account_owner(accounts.message, program_id, JabError::WrongMessageOwner)?;
+        check_account_owner(
+            accounts.thread,
+            program_id,
+            JabError::WrongThreadAccountOwner,
+        )?;
 
         // Check signer
         check_signer(accounts.sender)?;
@@ -62,7 +75,21 @@ pub(crate) fn process(
 ) -> ProgramResult {
     let accounts = Accounts::parse(program_id, accounts)?;
 
-    let mut message = Message::from_account_info(accounts.message)?;
+    // Legacy messages can be deleted without being migrated first
+    let message = Message::from_account_info_read_only(accounts.message)?;
+    let mut thread = Thread::from_account_info(accounts.thread)?;
+
+    let thread_key = Thread::create_key(
+        accounts.sender.key,
+        accounts.receiver.key,
+        program_id,
+        thread.bump,
+    );
+    check_account_key(
+        accounts.thread,
+        &thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
 
     let (expected_message_key, _) = Message::find_key(
         params.message_index,
@@ -82,15 +109,18 @@ pub(crate) fn process(
         JabError::AccountNotAuthorized,
     )?;
 
-    message.kind = MessageType::Deleted;
-    message.save(&mut accounts.message.data.borrow_mut());
-
-    let mut message_lamports = accounts.message.lamports.borrow_mut();
-    let mut target_lamports = accounts.sender.lamports.borrow_mut();
+    close_account(accounts.message, accounts.sender)?;
 
-    **target_lamports += **message_lamports;
+    thread.decrement_open_msg_count();
+    thread.save(&mut accounts.thread.data.borrow_mut());
 
-    **message_lamports = 0;
+    MessageDeleted {
+        message: *accounts.message.key,
+        sender: message.sender,
+        deleted_by: *accounts.sender.key,
+        timestamp: Clock::get()?.unix_timestamp,
+    }
+    .emit();
 
     Ok(())
 }
@@ -0,0 +1,102 @@
+//! Delete a subscription
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::legacy::SubscriptionV0;
+use crate::state::{Profile, Subscription};
+use crate::utils::close_account;
+
+use bonfida_utils::{
+    checks::{check_account_key, check_account_owner, check_signer},
+    BorshSize, InstructionsAccount,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The subscription account
+    #[cons(writable)]
+    pub subscription: &'a T,
+
+    /// The subscriber account
+    #[cons(writable, signer)]
+    pub subscriber: &'a T,
+
+    /// The profile account of the user subscribed to
+    #[cons(writable)]
+    pub subscribed_to_profile: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            subscription: next_account_info(accounts_iter)?,
+            subscriber: next_account_info(accounts_iter)?,
+            subscribed_to_profile: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(accounts.subscription, program_id)?;
+
+        // Check signer
+        check_signer(accounts.subscriber)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    // Legacy subscriptions were not counted in `subscriber_count`
+    if SubscriptionV0::is_legacy(&accounts.subscription.data.borrow()) {
+        let subscription =
+            SubscriptionV0::deserialize(&mut &accounts.subscription.data.borrow()[..])?;
+        check_account_key(accounts.subscriber, &subscription.subscriber)?;
+        check_account_key(accounts.subscription, &subscription.subscribed_to)?;
+        close_account(accounts.subscription, accounts.subscriber)?;
+        return Ok(());
+    }
+
+    let subscription = Subscription::from_account_info(accounts.subscription)?;
+
+    let (subscription_key, _) = Subscription::find_key(
+        accounts.subscriber.key,
+        &subscription.subscribed_to,
+        program_id,
+    );
+    check_account_key(accounts.subscription, &subscription_key)?;
+
+    let (subscribed_to_profile_key, _) = Profile::find_key(&subscription.subscribed_to, program_id);
+    check_account_key(accounts.subscribed_to_profile, &subscribed_to_profile_key)?;
+
+    close_account(accounts.subscription, accounts.subscriber)?;
+
+    // The profile might have been closed since the subscription
+    if !accounts.subscribed_to_profile.data_is_empty() {
+        check_account_owner(accounts.subscribed_to_profile, program_id)?;
+        // Already grown to the current layout by `CreateSubscription`
+        let mut profile = Profile::from_account_info(accounts.subscribed_to_profile)?;
+        profile.subscriber_count = profile.subscriber_count.saturating_sub(1);
+        profile.save(&mut accounts.subscribed_to_profile.data.borrow_mut());
+    }
+
+    Ok(())
+}
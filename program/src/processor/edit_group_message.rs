@@ -0,0 +1,129 @@
+//! Edit a message sent to a group
+use crate::utils::{
+    check_account_key, check_account_owner, check_group_message_key, check_signer, resize_account,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::error::JabError;
+use crate::state::{GroupThread, Message, StorageMode};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    // Sender nonce of the message, or its index for messages sent before nonces
+    pub nonce: u32,
+    pub message: Vec<u8>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The message sender account
+    #[cons(writable, signer)]
+    pub sender: &'a T,
+
+    /// The group thread account
+    pub group_thread: &'a T,
+
+    /// The message account
+    #[cons(writable)]
+    pub message: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            sender: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+            message: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongGroupThreadOwner,
+        )?;
+        check_account_owner(accounts.message, program_id, JabError::WrongMessageOwner)?;
+
+        // Check signer
+        check_signer(accounts.sender)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    // Messages are packed in fixed size buckets and cannot grow
+    if group_thread.storage_mode == StorageMode::Buckets {
+        return Err(JabError::NotSupportedForBuckets.into());
+    }
+    let mut message = Message::from_account_info(accounts.message)?;
+
+    let expected_group_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &expected_group_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    check_group_message_key(
+        accounts.message,
+        &expected_group_key,
+        &message.sender,
+        params.nonce,
+        program_id,
+    )?;
+    check_account_key(
+        accounts.sender,
+        &message.sender,
+        JabError::AccountNotAuthorized,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    message.check_editable(group_thread.edit_window, now)?;
+
+    message.msg = params.message;
+    message.edited_at = now;
+
+    resize_account(
+        accounts.message,
+        accounts.sender,
+        accounts.system_program,
+        message.borsh_len(),
+    )?;
+    message.save(&mut accounts.message.data.borrow_mut());
+
+    Ok(())
+}
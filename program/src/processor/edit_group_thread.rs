@@ -1,5 +1,8 @@
 //! Edit a group thread information
-use crate::utils::{check_account_key, check_account_owner, check_hash_len, check_signer};
+use crate::utils::{
+    check_account_key, check_account_owner, check_edit_window, check_group_permission,
+    check_hash_len, check_signer, load_group_admin, load_group_member,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -9,30 +12,41 @@ use solana_program::{
 };
 
 use crate::error::JabError;
-use crate::state::GroupThread;
+use crate::state::{GroupPermission, GroupThread};
 
 use bonfida_utils::{BorshSize, InstructionsAccount};
 
+// Only the fields set to `Some` are edited
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
-    pub visible: bool,
-    pub destination_wallet: Pubkey,
-    pub lamports_per_message: u64,
-    pub owner: Pubkey,
-    pub media_enabled: bool,
-    pub admin_only: bool,
-    pub group_pic_hash: String,
+    pub visible: Option<bool>,
+    pub destination_wallet: Option<Pubkey>,
+    pub lamports_per_message: Option<u64>,
+    pub price_mint: Option<Option<Pubkey>>,
+    pub price_amount: Option<u64>,
+    pub media_enabled: Option<bool>,
+    pub admin_only: Option<bool>,
+    pub members_only: Option<bool>,
+    pub private: Option<bool>,
+    pub edit_window: Option<i64>,
+    pub group_pic_hash: Option<String>,
 }
 
 #[derive(InstructionsAccount)]
 pub struct Accounts<'a, T> {
-    /// The group owner account
+    /// The group owner or admin account
     #[cons(writable, signer)]
-    pub group_owner: &'a T,
+    pub editor: &'a T,
 
     /// The group thread account
     #[cons(writable)]
     pub group_thread: &'a T,
+
+    /// The editor group membership account (may be empty)
+    pub group_member: &'a T,
+
+    /// The editor group admin account (may be empty)
+    pub group_admin: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -42,8 +56,10 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Self {
-            group_owner: next_account_info(accounts_iter)?,
+            editor: next_account_info(accounts_iter)?,
             group_thread: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
         };
 
         // Check keys
@@ -56,7 +72,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         )?;
 
         // Check signer
-        check_signer(accounts.group_owner)?;
+        check_signer(accounts.editor)?;
 
         Ok(accounts)
     }
@@ -69,13 +85,9 @@ pub(crate) fn process(
 ) -> ProgramResult {
     let accounts = Accounts::parse(program_id, accounts)?;
 
-    let group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
 
-    let (expected_group_thread_key, _) = GroupThread::find_key(
-        group_thread.group_name,
-        *accounts.group_owner.key,
-        program_id,
-    );
+    let expected_group_thread_key = group_thread.key(program_id);
 
     check_account_key(
         accounts.group_thread,
@@ -83,33 +95,104 @@ pub(crate) fn process(
         JabError::AccountNotDeterministic,
     )?;
 
-    check_account_key(
-        accounts.group_owner,
-        &group_thread.owner,
-        JabError::WrongGroupOwner,
-    )?;
-
     let Params {
         visible,
         destination_wallet,
         lamports_per_message,
-        owner,
+        price_mint,
+        price_amount,
         media_enabled,
         group_pic_hash,
         admin_only,
+        members_only,
+        private,
+        edit_window,
     } = params;
 
-    check_hash_len(&group_pic_hash)?;
+    check_edit_window(edit_window)?;
+
+    // The payment settings can only be edited by the owner
+    let is_owner = accounts.editor.key == &group_thread.owner;
+    if !is_owner
+        && (destination_wallet.is_some()
+            || lamports_per_message.is_some()
+            || price_mint.is_some()
+            || price_amount.is_some())
+    {
+        return Err(JabError::WrongGroupOwner.into());
+    }
 
-    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_member = load_group_member(
+        accounts.group_member,
+        &expected_group_thread_key,
+        accounts.editor.key,
+        program_id,
+    )?;
+    let group_admin = load_group_admin(
+        accounts.group_admin,
+        &expected_group_thread_key,
+        accounts.editor.key,
+        program_id,
+    )?;
+    let edits_settings = visible.is_some()
+        || media_enabled.is_some()
+        || admin_only.is_some()
+        || members_only.is_some()
+        || private.is_some()
+        || edit_window.is_some();
+    if edits_settings {
+        check_group_permission(
+            &group_thread,
+            accounts.editor.key,
+            group_admin.as_ref(),
+            group_member.as_ref(),
+            GroupPermission::EDIT_SETTINGS,
+        )?;
+    }
+    if group_pic_hash.is_some() {
+        check_group_permission(
+            &group_thread,
+            accounts.editor.key,
+            group_admin.as_ref(),
+            group_member.as_ref(),
+            GroupPermission::EDIT_PICTURE,
+        )?;
+    }
 
-    group_thread.lamports_per_message = lamports_per_message;
-    group_thread.destination_wallet = destination_wallet;
-    group_thread.owner = owner;
-    group_thread.media_enabled = media_enabled;
-    group_thread.group_pic_hash = group_pic_hash;
-    group_thread.admin_only = admin_only;
-    group_thread.visible = visible;
+    if let Some(group_pic_hash) = group_pic_hash {
+        check_hash_len(&group_pic_hash)?;
+        group_thread.group_pic_hash = group_pic_hash;
+    }
+    if let Some(visible) = visible {
+        group_thread.visible = visible;
+    }
+    if let Some(destination_wallet) = destination_wallet {
+        group_thread.destination_wallet = destination_wallet;
+    }
+    if let Some(lamports_per_message) = lamports_per_message {
+        group_thread.lamports_per_message = lamports_per_message;
+    }
+    if let Some(price_mint) = price_mint {
+        group_thread.price_mint = price_mint;
+    }
+    if let Some(price_amount) = price_amount {
+        group_thread.price_amount = price_amount;
+    }
+    if let Some(media_enabled) = media_enabled {
+        group_thread.media_enabled = media_enabled;
+    }
+    if let Some(admin_only) = admin_only {
+        group_thread.admin_only = admin_only;
+    }
+    if let Some(members_only) = members_only {
+        group_thread.members_only = members_only;
+    }
+    if let Some(private) = private {
+        group_thread.private = private;
+    }
+    if let Some(edit_window) = edit_window {
+        group_thread.edit_window = edit_window;
+    }
 
     group_thread.save(&mut accounts.group_thread.data.borrow_mut());
 
@@ -0,0 +1,136 @@
+//! Edit a message (DM)
+use crate::utils::{check_account_key, check_account_owner, check_signer, resize_account};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::error::JabError;
+use crate::state::{Message, Profile, DEFAULT_EDIT_WINDOW};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub message_index: u32,
+    pub message: Vec<u8>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The message sender account
+    #[cons(writable, signer)]
+    pub sender: &'a T,
+
+    /// The message receiver account
+    pub receiver: &'a T,
+
+    /// The message account
+    #[cons(writable)]
+    pub message: &'a T,
+
+    /// The message receiver profile account (may be empty)
+    pub receiver_profile: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            sender: next_account_info(accounts_iter)?,
+            receiver: next_account_info(accounts_iter)?,
+            message: next_account_info(accounts_iter)?,
+            receiver_profile: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.message, program_id, JabError::WrongMessageOwner)?;
+
+        // Check signer
+        check_signer(accounts.sender)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let mut message = Message::from_account_info(accounts.message)?;
+
+    let (expected_message_key, _) = Message::find_key(
+        params.message_index,
+        accounts.sender.key,
+        accounts.receiver.key,
+        program_id,
+    );
+
+    check_account_key(
+        accounts.message,
+        &expected_message_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    check_account_key(
+        accounts.sender,
+        &message.sender,
+        JabError::AccountNotAuthorized,
+    )?;
+
+    // The receiver chooses how long messages sent to them can be edited
+    let (receiver_profile_key, _) = Profile::find_key(accounts.receiver.key, program_id);
+    check_account_key(
+        accounts.receiver_profile,
+        &receiver_profile_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    let edit_window = if accounts.receiver_profile.data_is_empty() {
+        DEFAULT_EDIT_WINDOW
+    } else {
+        check_account_owner(
+            accounts.receiver_profile,
+            program_id,
+            JabError::WrongProfileOwner,
+        )?;
+        Profile::from_account_info(accounts.receiver_profile)?.edit_window()
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    message.check_editable(edit_window, now)?;
+
+    message.msg = params.message;
+    message.edited_at = now;
+
+    resize_account(
+        accounts.message,
+        accounts.sender,
+        accounts.system_program,
+        message.borsh_len(),
+    )?;
+    message.save(&mut accounts.message.data.borrow_mut());
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! Grant a role and permissions to a group member
+use crate::error::JabError;
+use crate::state::{GroupMember, GroupRole, GroupThread};
+use crate::utils::{check_account_key, check_account_owner, check_signer, load_group_member};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::create_account,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub user: Pubkey,
+    pub role: GroupRole,
+    // Permissions granted on top of the role ones
+    pub permissions: u8,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The group owner account
+    #[cons(writable, signer)]
+    pub group_owner: &'a T,
+
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+
+    /// The user group membership account (may be empty)
+    #[cons(writable)]
+    pub group_member: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            group_owner: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongGroupThreadOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.group_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params {
+        user,
+        role,
+        permissions,
+    } = params;
+
+    // Ownership is transferred with `ProposeGroupOwner`
+    if role == GroupRole::Owner {
+        msg!("The owner role cannot be granted");
+        return Err(ProgramError::InvalidArgument);
+    }
+    // Admins are the users with a `GroupAdmin` account, added with `AddAdminToGroup`
+    if role == GroupRole::Admin {
+        msg!("The admin role cannot be granted");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    check_account_key(
+        accounts.group_owner,
+        &group_thread.owner,
+        JabError::WrongGroupOwner,
+    )?;
+
+    let mut group_member =
+        match load_group_member(accounts.group_member, &group_thread_key, &user, program_id)? {
+            Some(group_member) => group_member,
+            None => {
+                let (group_member_key, bump) =
+                    GroupMember::find_key(&group_thread_key, &user, program_id);
+                let group_member =
+                    GroupMember::new(group_thread_key, user, Clock::get()?.unix_timestamp);
+                let space = group_member.borsh_len();
+                let lamports = Rent::get()?.minimum_balance(space);
+
+                let allocate_account = create_account(
+                    accounts.group_owner.key,
+                    &group_member_key,
+                    lamports,
+                    space as u64,
+                    program_id,
+                );
+
+                invoke_signed(
+                    &allocate_account,
+                    &[
+                        accounts.system_program.clone(),
+                        accounts.group_owner.clone(),
+                        accounts.group_member.clone(),
+                    ],
+                    &[&[
+                        GroupMember::SEED.as_bytes(),
+                        &group_thread_key.to_bytes(),
+                        &user.to_bytes(),
+                        &[bump],
+                    ]],
+                )?;
+                group_thread.add_linked_account();
+                group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+                group_member
+            }
+        };
+
+    group_member.role = role;
+    group_member.permissions = role.permissions() | permissions;
+    group_member.save(&mut accounts.group_member.data.borrow_mut());
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+//! Initialize the program config, can only be called once by the program upgrade authority
+use crate::error::JabError;
+use crate::state::Config;
+use crate::utils::{check_account_key, check_account_owner, check_signer, check_upgrade_authority};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::{create_account, transfer},
+    system_program,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+    pub fee_authority: Pubkey,
+    pub referrer_share_bps: u16,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The program config account
+    #[cons(writable)]
+    pub config: &'a T,
+
+    /// The program upgrade authority account
+    #[cons(signer)]
+    pub upgrade_authority: &'a T,
+
+    /// The program data account of the program
+    pub program_data: &'a T,
+
+    /// The SOL vault account
+    #[cons(writable)]
+    pub vault: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            upgrade_authority: next_account_info(accounts_iter)?,
+            program_data: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.config, &system_program::ID, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+        check_signer(accounts.upgrade_authority)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params {
+        admin,
+        fee_bps,
+        fee_authority,
+        referrer_share_bps,
+    } = params;
+
+    if fee_bps > 10_000 || referrer_share_bps > 10_000 {
+        return Err(JabError::InvalidFee.into());
+    }
+
+    // Prevents anyone else from initializing the config first
+    check_upgrade_authority(
+        accounts.program_data,
+        accounts.upgrade_authority,
+        program_id,
+    )?;
+
+    let (config_key, bump) = Config::find_key(program_id);
+
+    check_account_key(
+        accounts.config,
+        &config_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let (vault, vault_bump) = Config::find_vault_key(program_id);
+    check_account_key(accounts.vault, &vault, JabError::AccountNotDeterministic)?;
+    let config = Config::new(
+        admin,
+        fee_bps,
+        vault,
+        vault_bump,
+        fee_authority,
+        referrer_share_bps,
+    );
+    let space = config.borsh_len();
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    let allocate_account = create_account(
+        accounts.fee_payer.key,
+        &config_key,
+        lamports,
+        space as u64,
+        program_id,
+    );
+
+    invoke_signed(
+        &allocate_account,
+        &[
+            accounts.system_program.clone(),
+            accounts.fee_payer.clone(),
+            accounts.config.clone(),
+        ],
+        &[&[Config::SEED.as_bytes(), &[bump]]],
+    )?;
+
+    config.save(&mut accounts.config.data.borrow_mut());
+
+    // Fees smaller than the rent exempt minimum could not be sent to an empty vault
+    let vault_rent = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(accounts.vault.lamports());
+    if vault_rent > 0 {
+        invoke(
+            &transfer(accounts.fee_payer.key, &vault, vault_rent),
+            &[
+                accounts.system_program.clone(),
+                accounts.fee_payer.clone(),
+                accounts.vault.clone(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
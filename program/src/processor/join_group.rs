@@ -0,0 +1,157 @@
+//! Join a group
+use crate::error::JabError;
+use crate::state::{GroupMember, GroupThread};
+use crate::utils::{check_account_key, check_account_owner, check_signer, load_group_moderation};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::create_account,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The member account
+    #[cons(writable, signer)]
+    pub member: &'a T,
+
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+
+    /// The group member account
+    #[cons(writable)]
+    pub group_member: &'a T,
+
+    /// The member group moderation account
+    pub group_moderation: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            member: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_moderation: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongThreadAccountOwner,
+        )?;
+        check_account_owner(
+            accounts.group_member,
+            &system_program::ID,
+            JabError::WrongOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.member)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    if group_thread.private {
+        return Err(JabError::PrivateGroup.into());
+    }
+
+    if let Some(moderation) = load_group_moderation(
+        accounts.group_moderation,
+        &group_thread_key,
+        accounts.member.key,
+        program_id,
+    )? {
+        moderation.check_can_join()?;
+    }
+
+    let (group_member_key, bump) =
+        GroupMember::find_key(&group_thread_key, accounts.member.key, program_id);
+
+    check_account_key(
+        accounts.group_member,
+        &group_member_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let group_member = GroupMember::new(group_thread_key, *accounts.member.key, now);
+    let space = group_member.borsh_len();
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    let allocate_account = create_account(
+        accounts.member.key,
+        &group_member_key,
+        lamports,
+        space as u64,
+        program_id,
+    );
+
+    invoke_signed(
+        &allocate_account,
+        &[
+            accounts.system_program.clone(),
+            accounts.member.clone(),
+            accounts.group_member.clone(),
+        ],
+        &[&[
+            GroupMember::SEED.as_bytes(),
+            &group_thread_key.to_bytes(),
+            &accounts.member.key.to_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    group_member.save(&mut accounts.group_member.data.borrow_mut());
+
+    group_thread.add_linked_account();
+    group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Leave a group
+use crate::error::JabError;
+use crate::state::{GroupMember, GroupThread};
+use crate::utils::{check_account_key, check_account_owner, check_signer, close_account};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The member account
+    #[cons(writable, signer)]
+    pub member: &'a T,
+
+    /// The group member account
+    #[cons(writable)]
+    pub group_member: &'a T,
+
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            member: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(accounts.group_member, program_id, JabError::WrongOwner)?;
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongGroupThreadOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.member)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let group_member = GroupMember::from_account_info(accounts.group_member)?;
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+
+    check_account_key(
+        accounts.group_thread,
+        &group_member.group_thread,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let (expected_group_member_key, _) =
+        GroupMember::find_key(&group_member.group_thread, &group_member.member, program_id);
+
+    check_account_key(
+        accounts.group_member,
+        &expected_group_member_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    check_account_key(
+        accounts.member,
+        &group_member.member,
+        JabError::AccountNotAuthorized,
+    )?;
+
+    close_account(accounts.group_member, accounts.member)?;
+
+    group_thread.remove_linked_account();
+    group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+//! Convert an account created by the initial program version to the current layout
+use crate::error::JabError;
+use crate::legacy::{GroupThreadV0, MessageV0, ThreadV0};
+use crate::state::{GroupAdmin, Tag, Thread, MAX_GROUP_THREAD_LEN};
+use crate::utils::{check_account_key, check_account_owner, check_signer, resize_account};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::create_account,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The fee payer account
+    #[cons(writable, signer)]
+    pub fee_payer: &'a T,
+
+    /// The account to migrate
+    #[cons(writable)]
+    pub account: &'a T,
+
+    /// The group owner account, when migrating a group
+    #[cons(writable)]
+    pub group_owner: Option<&'a T>,
+
+    /// The group admin accounts of the legacy admins in order, when migrating a group
+    #[cons(writable)]
+    pub group_admins: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            fee_payer: next_account_info(accounts_iter)?,
+            account: next_account_info(accounts_iter)?,
+            group_owner: next_account_info(accounts_iter).ok(),
+            group_admins: accounts_iter.as_slice(),
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.account, program_id, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.fee_payer)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let tag = accounts.account.data.borrow()[0];
+    if tag == Tag::Thread as u8 && ThreadV0::is_legacy(&accounts.account.data.borrow()) {
+        let thread = ThreadV0::deserialize(&mut &accounts.account.data.borrow()[..])?.migrate();
+        let thread_key =
+            Thread::create_key(&thread.user_1, &thread.user_2, program_id, thread.bump);
+        check_account_key(
+            accounts.account,
+            &thread_key,
+            JabError::AccountNotDeterministic,
+        )?;
+        resize_account(
+            accounts.account,
+            accounts.fee_payer,
+            accounts.system_program,
+            thread.borsh_len(),
+        )?;
+        thread.save(&mut accounts.account.data.borrow_mut());
+    } else if tag == Tag::Message as u8 && MessageV0::is_legacy(&accounts.account.data.borrow()) {
+        let message = MessageV0::deserialize(&mut &accounts.account.data.borrow()[..])?.migrate();
+        resize_account(
+            accounts.account,
+            accounts.fee_payer,
+            accounts.system_program,
+            message.borsh_len(),
+        )?;
+        message.save(&mut accounts.account.data.borrow_mut());
+    } else if tag == Tag::GroupThread as u8
+        && GroupThreadV0::is_legacy(&accounts.account.data.borrow())
+    {
+        let legacy = GroupThreadV0::deserialize(&mut &accounts.account.data.borrow()[..])?;
+        let admins = legacy.admins.clone();
+        let mut group_thread = legacy.migrate();
+        let group_thread_key = group_thread.key(program_id);
+        check_account_key(
+            accounts.account,
+            &group_thread_key,
+            JabError::AccountNotDeterministic,
+        )?;
+
+        let group_owner = accounts
+            .group_owner
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        check_account_key(group_owner, &group_thread.owner, JabError::WrongGroupOwner)?;
+        if accounts.group_admins.len() != admins.len() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        for (admin, group_admin_account) in admins.into_iter().zip(accounts.group_admins) {
+            let (group_admin_key, bump) =
+                GroupAdmin::find_key(&group_thread_key, &admin, program_id);
+            check_account_key(
+                group_admin_account,
+                &group_admin_key,
+                JabError::AccountNotDeterministic,
+            )?;
+            // The same admin may be listed twice
+            if !group_admin_account.data_is_empty() {
+                continue;
+            }
+
+            let group_admin = GroupAdmin::new(group_thread_key, admin);
+            let space = group_admin.borsh_len();
+            let lamports = Rent::get()?.minimum_balance(space);
+            invoke_signed(
+                &create_account(
+                    accounts.fee_payer.key,
+                    &group_admin_key,
+                    lamports,
+                    space as u64,
+                    program_id,
+                ),
+                &[
+                    accounts.system_program.clone(),
+                    accounts.fee_payer.clone(),
+                    group_admin_account.clone(),
+                ],
+                &[&[
+                    GroupAdmin::SEED.as_bytes(),
+                    &group_thread_key.to_bytes(),
+                    &admin.to_bytes(),
+                    &[bump],
+                ]],
+            )?;
+            group_admin.save(&mut group_admin_account.data.borrow_mut());
+            group_thread.add_linked_account();
+        }
+
+        // The current layout is smaller, the freed rent goes back to the group owner
+        resize_account(
+            accounts.account,
+            group_owner,
+            accounts.system_program,
+            MAX_GROUP_THREAD_LEN,
+        )?;
+        accounts.account.data.borrow_mut().fill(0);
+        group_thread.save(&mut accounts.account.data.borrow_mut());
+    } else {
+        msg!("Account does not use a legacy layout");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
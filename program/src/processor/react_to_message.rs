@@ -0,0 +1,279 @@
+//! React to a message (DM or group)
+use crate::utils::{
+    check_account_key, check_account_owner, check_group_message_key, check_signer, close_account,
+    load_group_admin, load_group_member, load_group_moderation,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::create_account,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use crate::error::JabError;
+use crate::state::{GroupThread, Message, Reaction, ReactionType, StorageMode, Tag, Thread};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    // Index of the message, or its sender nonce in groups
+    pub message_index: u32,
+    // `None` removes the existing reaction
+    pub reaction: Option<ReactionType>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The reactor account
+    #[cons(writable, signer)]
+    pub reactor: &'a T,
+
+    /// The thread or group thread account
+    pub thread: &'a T,
+
+    /// The message account
+    #[cons(writable)]
+    pub message: &'a T,
+
+    /// The reaction account
+    #[cons(writable)]
+    pub reaction: &'a T,
+
+    /// The reactor group membership account, for group messages
+    pub group_member: Option<&'a T>,
+
+    /// The reactor group moderation account, for group messages
+    pub group_moderation: Option<&'a T>,
+
+    /// The reactor group admin account (may be empty), for group messages
+    pub group_admin: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            reactor: next_account_info(accounts_iter)?,
+            thread: next_account_info(accounts_iter)?,
+            message: next_account_info(accounts_iter)?,
+            reaction: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter).ok(),
+            group_moderation: next_account_info(accounts_iter).ok(),
+            group_admin: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(
+            accounts.thread,
+            program_id,
+            JabError::WrongThreadAccountOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.reactor)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params {
+        message_index,
+        reaction,
+    } = params;
+
+    // DM reactions are restricted to the participants of the thread
+    let tag = accounts.thread.data.borrow()[0];
+    let expected_message_key = if tag == Tag::Thread as u8 {
+        let thread = Thread::from_account_info(accounts.thread)?;
+        let thread_key =
+            Thread::create_key(&thread.user_1, &thread.user_2, program_id, thread.bump);
+        check_account_key(
+            accounts.thread,
+            &thread_key,
+            JabError::AccountNotDeterministic,
+        )?;
+        if *accounts.reactor.key != thread.user_1 && *accounts.reactor.key != thread.user_2 {
+            return Err(JabError::AccountNotAuthorized.into());
+        }
+        Message::find_key(message_index, &thread.user_1, &thread.user_2, program_id).0
+    } else if tag == Tag::GroupThread as u8 {
+        let group_thread = GroupThread::from_account_info(accounts.thread)?;
+        let group_thread_key = group_thread.key(program_id);
+        check_account_key(
+            accounts.thread,
+            &group_thread_key,
+            JabError::AccountNotDeterministic,
+        )?;
+        // Bucket messages do not have their own key to bind reactions to
+        if group_thread.storage_mode == StorageMode::Buckets {
+            return Err(JabError::NotSupportedForBuckets.into());
+        }
+
+        // Reactors are held to the same membership and moderation rules as senders, removing a
+        // reaction is always allowed so that its rent can be reclaimed
+        let group_member = load_group_member(
+            accounts
+                .group_member
+                .ok_or(ProgramError::NotEnoughAccountKeys)?,
+            &group_thread_key,
+            accounts.reactor.key,
+            program_id,
+        )?;
+        let group_admin = load_group_admin(
+            accounts
+                .group_admin
+                .ok_or(ProgramError::NotEnoughAccountKeys)?,
+            &group_thread_key,
+            accounts.reactor.key,
+            program_id,
+        )?;
+        if reaction.is_some()
+            && group_thread.requires_membership()
+            && group_member.is_none()
+            && group_admin.is_none()
+            && group_thread.owner != *accounts.reactor.key
+        {
+            return Err(JabError::NotGroupMember.into());
+        }
+        if let Some(moderation) = load_group_moderation(
+            accounts
+                .group_moderation
+                .ok_or(ProgramError::NotEnoughAccountKeys)?,
+            &group_thread_key,
+            accounts.reactor.key,
+            program_id,
+        )? {
+            if reaction.is_some() {
+                moderation.check_can_post(Clock::get()?.unix_timestamp)?;
+            }
+        }
+
+        // Reactions to deleted messages can only be removed and are bound to the message key
+        if !accounts.message.data_is_empty() {
+            check_account_owner(accounts.message, program_id, JabError::WrongMessageOwner)?;
+            let message = Message::from_account_info(accounts.message)?;
+            check_group_message_key(
+                accounts.message,
+                &group_thread_key,
+                &message.sender,
+                message_index,
+                program_id,
+            )?;
+        }
+        *accounts.message.key
+    } else {
+        return Err(JabError::DataTypeMismatch.into());
+    };
+
+    check_account_key(
+        accounts.message,
+        &expected_message_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let (reaction_key, bump) =
+        Reaction::find_key(accounts.message.key, accounts.reactor.key, program_id);
+    check_account_key(
+        accounts.reaction,
+        &reaction_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    // Reactions to a deleted message can only be removed
+    if accounts.message.data_is_empty() {
+        if reaction.is_some() || accounts.reaction.data_is_empty() {
+            return Err(JabError::MessageDeleted.into());
+        }
+        check_account_owner(accounts.reaction, program_id, JabError::WrongOwner)?;
+        return close_account(accounts.reaction, accounts.reactor);
+    }
+
+    check_account_owner(accounts.message, program_id, JabError::WrongMessageOwner)?;
+    let mut message = Message::from_account_info(accounts.message)?;
+
+    if accounts.reaction.data_is_empty() {
+        let kind = match reaction {
+            Some(kind) => kind,
+            None => {
+                msg!("No reaction to remove");
+                return Err(ProgramError::InvalidArgument);
+            }
+        };
+
+        let reaction = Reaction::new(kind, *accounts.message.key, *accounts.reactor.key);
+        let space = reaction.borsh_len();
+        let lamports = Rent::get()?.minimum_balance(space);
+
+        let allocate_account = create_account(
+            accounts.reactor.key,
+            &reaction_key,
+            lamports,
+            space as u64,
+            program_id,
+        );
+
+        invoke_signed(
+            &allocate_account,
+            &[
+                accounts.system_program.clone(),
+                accounts.reactor.clone(),
+                accounts.reaction.clone(),
+            ],
+            &[&[
+                Reaction::SEED.as_bytes(),
+                &accounts.message.key.to_bytes(),
+                &accounts.reactor.key.to_bytes(),
+                &[bump],
+            ]],
+        )?;
+
+        reaction.save(&mut accounts.reaction.data.borrow_mut());
+        message.add_reaction(kind);
+    } else {
+        check_account_owner(accounts.reaction, program_id, JabError::WrongOwner)?;
+        let mut previous = Reaction::from_account_info(accounts.reaction)?;
+        message.remove_reaction(previous.kind);
+
+        match reaction {
+            Some(kind) => {
+                message.add_reaction(kind);
+                previous.kind = kind;
+                previous.save(&mut accounts.reaction.data.borrow_mut());
+            }
+            None => close_account(accounts.reaction, accounts.reactor)?,
+        }
+    }
+
+    message.save(&mut accounts.message.data.borrow_mut());
+
+    Ok(())
+}
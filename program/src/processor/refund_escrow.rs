@@ -0,0 +1,84 @@
+//! Refund the payment of an unanswered message after the escrow deadline
+use crate::error::JabError;
+use crate::state::Escrow;
+use crate::utils::{check_account_key, check_account_owner, check_signer, close_account};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The message sender account
+    #[cons(writable, signer)]
+    pub sender: &'a T,
+
+    /// The escrow account
+    #[cons(writable)]
+    pub escrow: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            sender: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(accounts.escrow, program_id, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.sender)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let escrow = Escrow::from_account_info(accounts.escrow)?;
+    let (escrow_key, _) = Escrow::find_key(&escrow.message, program_id);
+
+    check_account_key(
+        accounts.escrow,
+        &escrow_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    check_account_key(
+        accounts.sender,
+        &escrow.sender,
+        JabError::AccountNotAuthorized,
+    )?;
+
+    if Clock::get()?.unix_timestamp < escrow.deadline {
+        return Err(JabError::EscrowNotExpired.into());
+    }
+
+    // Only the escrowed payment is returned, the protocol fee was kept when the message was sent
+    close_account(accounts.escrow, accounts.sender)?;
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Register a wallet as a referrer
+use crate::error::JabError;
+use crate::state::Referrer;
+use crate::utils::{check_account_key, check_account_owner, check_signer};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::create_account,
+    system_program,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The referrer wallet account
+    #[cons(writable, signer)]
+    pub wallet: &'a T,
+
+    /// The referrer account
+    #[cons(writable)]
+    pub referrer: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        _program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            wallet: next_account_info(accounts_iter)?,
+            referrer: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.referrer, &system_program::ID, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.wallet)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let (referrer_key, bump) = Referrer::find_key(accounts.wallet.key, program_id);
+    check_account_key(
+        accounts.referrer,
+        &referrer_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let referrer = Referrer::new(*accounts.wallet.key);
+    let space = referrer.borsh_len();
+    let lamports = Rent::get()?.minimum_balance(space);
+
+    let allocate_account = create_account(
+        accounts.wallet.key,
+        &referrer_key,
+        lamports,
+        space as u64,
+        program_id,
+    );
+
+    invoke_signed(
+        &allocate_account,
+        &[
+            accounts.system_program.clone(),
+            accounts.wallet.clone(),
+            accounts.referrer.clone(),
+        ],
+        &[&[
+            Referrer::SEED.as_bytes(),
+            &accounts.wallet.key.to_bytes(),
+            &[bump],
+        ]],
+    )?;
+
+    referrer.save(&mut accounts.referrer.data.borrow_mut());
+
+    Ok(())
+}
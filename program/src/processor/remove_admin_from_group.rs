@@ -1,7 +1,7 @@
 //! Remove an admin from the group
 use crate::error::JabError;
-use crate::state::GroupThread;
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::state::{GroupAdmin, GroupThread};
+use crate::utils::{check_account_key, check_account_owner, check_signer, close_account};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
@@ -15,7 +15,6 @@ use bonfida_utils::{BorshSize, InstructionsAccount};
 #[derive(BorshDeserialize, BorshSerialize, BorshSize)]
 pub struct Params {
     pub admin_address: Pubkey,
-    pub admin_index: u64,
 }
 
 #[derive(InstructionsAccount)]
@@ -27,6 +26,10 @@ pub struct Accounts<'a, T> {
     /// The group owner account
     #[cons(writable, signer)]
     pub group_owner: &'a T,
+
+    /// The group admin account
+    #[cons(writable)]
+    pub group_admin: &'a T,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -39,6 +42,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts = Self {
             group_thread: next_account_info(accounts_iter)?,
             group_owner: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
         };
 
         // Check keys
@@ -49,6 +53,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             program_id,
             JabError::WrongGroupThreadOwner,
         )?;
+        check_account_owner(accounts.group_admin, program_id, JabError::WrongOwner)?;
 
         // Check signer
         check_signer(accounts.group_owner)?;
@@ -63,18 +68,11 @@ pub(crate) fn process(
     params: Params,
 ) -> ProgramResult {
     let accounts = Accounts::parse(program_id, accounts)?;
-    let Params {
-        admin_address,
-        admin_index,
-    } = params;
+    let Params { admin_address } = params;
 
     let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
 
-    let (expected_group_thread_key, _) = GroupThread::find_key(
-        group_thread.group_name.clone(),
-        *accounts.group_owner.key,
-        program_id,
-    );
+    let expected_group_thread_key = group_thread.key(program_id);
 
     check_account_key(
         accounts.group_thread,
@@ -88,7 +86,17 @@ pub(crate) fn process(
         JabError::WrongGroupOwner,
     )?;
 
-    group_thread.remove_admin(admin_address, admin_index as usize)?;
+    let (group_admin_key, _) =
+        GroupAdmin::find_key(&expected_group_thread_key, &admin_address, program_id);
+    check_account_key(
+        accounts.group_admin,
+        &group_admin_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    close_account(accounts.group_admin, accounts.group_owner)?;
+
+    group_thread.remove_linked_account();
     group_thread.save(&mut accounts.group_thread.data.borrow_mut());
 
     Ok(())
@@ -0,0 +1,96 @@
+//! Remove a user from the contact list
+use crate::error::JabError;
+use crate::state::Contact;
+use crate::utils::{check_account_key, check_account_owner, check_signer, close_account};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The contact list owner account
+    #[cons(writable, signer)]
+    pub owner: &'a T,
+
+    /// The contact account
+    #[cons(writable)]
+    pub contact: &'a T,
+
+    /// The contact account of the owner in the contact list (may be empty)
+    #[cons(writable)]
+    pub reverse_contact: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            owner: next_account_info(accounts_iter)?,
+            contact: next_account_info(accounts_iter)?,
+            reverse_contact: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(accounts.contact, program_id, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let contact = Contact::from_account_info(accounts.contact)?;
+    let (contact_key, _) = Contact::find_key(&contact.owner, &contact.contact, program_id);
+
+    check_account_key(
+        accounts.contact,
+        &contact_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    check_account_key(
+        accounts.owner,
+        &contact.owner,
+        JabError::AccountNotAuthorized,
+    )?;
+
+    let (reverse_contact_key, _) = Contact::find_key(&contact.contact, &contact.owner, program_id);
+    check_account_key(
+        accounts.reverse_contact,
+        &reverse_contact_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    if !accounts.reverse_contact.data_is_empty() {
+        check_account_owner(accounts.reverse_contact, program_id, JabError::WrongOwner)?;
+        let mut reverse_contact = Contact::from_account_info(accounts.reverse_contact)?;
+        reverse_contact.mutual = false;
+        reverse_contact.save(&mut accounts.reverse_contact.data.borrow_mut());
+    }
+
+    close_account(accounts.contact, accounts.owner)?;
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Rename a group
+use crate::error::JabError;
+use crate::state::{GroupPermission, GroupThread};
+use crate::utils::{
+    check_account_key, check_account_owner, check_group_permission, check_group_thread_params,
+    check_signer, load_group_admin, load_group_member,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub group_name: String,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The group owner or admin account
+    #[cons(signer)]
+    pub editor: &'a T,
+
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+
+    /// The editor group membership account (may be empty)
+    pub group_member: &'a T,
+
+    /// The editor group admin account (may be empty)
+    pub group_admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            editor: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongGroupThreadOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.editor)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params { group_name } = params;
+
+    check_group_thread_params(&group_name)?;
+
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let group_member = load_group_member(
+        accounts.group_member,
+        &group_thread_key,
+        accounts.editor.key,
+        program_id,
+    )?;
+    let group_admin = load_group_admin(
+        accounts.group_admin,
+        &group_thread_key,
+        accounts.editor.key,
+        program_id,
+    )?;
+    check_group_permission(
+        &group_thread,
+        accounts.editor.key,
+        group_admin.as_ref(),
+        group_member.as_ref(),
+        GroupPermission::EDIT_SETTINGS,
+    )?;
+
+    group_thread.rename(group_name)?;
+    group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+
+    Ok(())
+}
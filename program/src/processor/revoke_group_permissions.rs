@@ -0,0 +1,93 @@
+//! Revoke permissions from a group member
+use crate::error::JabError;
+use crate::state::GroupThread;
+use crate::utils::{check_account_key, check_account_owner, check_signer, load_group_member};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub user: Pubkey,
+    pub permissions: u8,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The group owner account
+    #[cons(signer)]
+    pub group_owner: &'a T,
+
+    /// The group thread account
+    pub group_thread: &'a T,
+
+    /// The user group membership account
+    #[cons(writable)]
+    pub group_member: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            group_owner: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongGroupThreadOwner,
+        )?;
+
+        // Check signer
+        check_signer(accounts.group_owner)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params { user, permissions } = params;
+
+    let group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    check_account_key(
+        accounts.group_owner,
+        &group_thread.owner,
+        JabError::WrongGroupOwner,
+    )?;
+
+    let mut group_member =
+        load_group_member(accounts.group_member, &group_thread_key, &user, program_id)?
+            .ok_or(JabError::NotGroupMember)?;
+
+    group_member.permissions &= !permissions;
+    group_member.save(&mut accounts.group_member.data.borrow_mut());
+
+    Ok(())
+}
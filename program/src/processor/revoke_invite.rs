@@ -0,0 +1,138 @@
+//! Revoke an invite to a group
+use crate::error::JabError;
+use crate::state::{GroupPermission, GroupThread, Invite};
+use crate::utils::{
+    check_account_key, check_account_owner, check_group_permission, check_signer, close_account,
+    load_group_admin, load_group_member,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The invite creator or a member allowed to invite
+    #[cons(signer)]
+    pub revoker: &'a T,
+
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+
+    /// The invite account
+    #[cons(writable)]
+    pub invite: &'a T,
+
+    /// The invite creator account
+    #[cons(writable)]
+    pub creator: &'a T,
+
+    /// The revoker group membership account (may be empty)
+    pub group_member: &'a T,
+
+    /// The revoker group admin account (may be empty)
+    pub group_admin: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            revoker: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+            invite: next_account_info(accounts_iter)?,
+            creator: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongThreadAccountOwner,
+        )?;
+        check_account_owner(accounts.invite, program_id, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.revoker)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let invite = Invite::from_account_info(accounts.invite)?;
+    let (invite_key, _) = Invite::find_key(&group_thread_key, &invite.code_key, program_id);
+
+    check_account_key(
+        accounts.invite,
+        &invite_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    check_account_key(
+        accounts.creator,
+        &invite.creator,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    // The invite can be revoked by its creator or by members allowed to invite
+    if *accounts.revoker.key != invite.creator {
+        let group_member = load_group_member(
+            accounts.group_member,
+            &group_thread_key,
+            accounts.revoker.key,
+            program_id,
+        )?;
+        let group_admin = load_group_admin(
+            accounts.group_admin,
+            &group_thread_key,
+            accounts.revoker.key,
+            program_id,
+        )?;
+        check_group_permission(
+            &group_thread,
+            accounts.revoker.key,
+            group_admin.as_ref(),
+            group_member.as_ref(),
+            GroupPermission::INVITE,
+        )?;
+    }
+
+    // Rent goes back to the creator who paid for the invite
+    close_account(accounts.invite, accounts.creator)?;
+
+    group_thread.remove_linked_account();
+    group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 //! Send a message (DM)
 use crate::{
     state::MessageType,
-    utils::{check_account_key, check_account_owner, check_signer, order_keys, FEE, SOL_VAULT},
+    utils::{
+        check_account_key, check_account_owner, check_signer, close_account, load_config,
+        order_keys, transfer_fee, transfer_token_payment,
+    },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
@@ -18,7 +21,7 @@ use solana_program::{
 };
 
 use crate::error::JabError;
-use crate::state::{Message, Profile, Thread};
+use crate::state::{Block, Contact, Escrow, Message, Profile, Thread, ThreadState};
 
 use bonfida_utils::{BorshSize, InstructionsAccount};
 
@@ -56,6 +59,46 @@ pub struct Accounts<'a, T> {
     /// The SOL vault account
     #[cons(writable)]
     pub sol_vault: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+
+    /// The block account of the sender by the receiver
+    pub block: &'a T,
+
+    /// The contact account of the sender in the receiver list
+    pub contact: &'a T,
+
+    /// The escrow account of the message
+    #[cons(writable)]
+    pub escrow: &'a T,
+
+    /// The escrow account of the message replied to
+    #[cons(writable)]
+    pub reply_escrow: &'a T,
+
+    /// The SPL token program account, when paying in tokens
+    pub spl_token_program: Option<&'a T>,
+
+    /// The sender token account, when paying in tokens
+    #[cons(writable)]
+    pub token_source: Option<&'a T>,
+
+    /// The receiver token account, when paying in tokens
+    #[cons(writable)]
+    pub token_destination: Option<&'a T>,
+
+    /// The vault token account, when paying in tokens
+    #[cons(writable)]
+    pub vault_token_account: Option<&'a T>,
+
+    /// The referrer wallet, or token account when paying in tokens
+    #[cons(writable)]
+    pub referrer: Option<&'a T>,
+
+    /// The referrer account
+    #[cons(writable)]
+    pub referrer_stats: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -64,7 +107,7 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         accounts: &'a [AccountInfo<'b>],
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
-        let accounts = Self {
+        let mut accounts = Self {
             system_program: next_account_info(accounts_iter)?,
             sender: next_account_info(accounts_iter)?,
             receiver: next_account_info(accounts_iter)?,
@@ -72,19 +115,37 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             receiver_profile: next_account_info(accounts_iter)?,
             message: next_account_info(accounts_iter)?,
             sol_vault: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            block: next_account_info(accounts_iter)?,
+            contact: next_account_info(accounts_iter)?,
+            escrow: next_account_info(accounts_iter)?,
+            reply_escrow: next_account_info(accounts_iter)?,
+            spl_token_program: None,
+            token_source: None,
+            token_destination: None,
+            vault_token_account: None,
+            referrer: None,
+            referrer_stats: None,
         };
 
+        // The token accounts are only passed when paying in tokens
+        if let Some(spl_token_program) = accounts_iter.as_slice().first() {
+            if spl_token_program.key == &spl_token::ID {
+                accounts.spl_token_program = next_account_info(accounts_iter).ok();
+                accounts.token_source = next_account_info(accounts_iter).ok();
+                accounts.token_destination = next_account_info(accounts_iter).ok();
+                accounts.vault_token_account = next_account_info(accounts_iter).ok();
+            }
+        }
+        accounts.referrer = next_account_info(accounts_iter).ok();
+        accounts.referrer_stats = next_account_info(accounts_iter).ok();
+
         // Check keys
         check_account_key(
             accounts.system_program,
             &system_program::ID,
             JabError::WrongSystemProgramAccount,
         )?;
-        check_account_key(
-            accounts.sol_vault,
-            &SOL_VAULT,
-            JabError::WrongSolVaultAccount,
-        )?;
 
         // Check ownership
         check_account_owner(
@@ -114,6 +175,17 @@ pub(crate) fn process(
         replies_to,
     } = params;
 
+    if kind == MessageType::Deleted {
+        return Err(JabError::NonSupportedMessageType.into());
+    }
+
+    let config = load_config(accounts.config, program_id)?;
+    check_account_key(
+        accounts.sol_vault,
+        &config.vault,
+        JabError::WrongSolVaultAccount,
+    )?;
+
     let mut thread = Thread::from_account_info(accounts.thread)?;
     let thread_key = Thread::create_key(
         accounts.sender.key,
@@ -127,6 +199,32 @@ pub(crate) fn process(
         JabError::AccountNotDeterministic,
     )?;
 
+    let (block_key, _) = Block::find_key(accounts.receiver.key, accounts.sender.key, program_id);
+    check_account_key(
+        accounts.block,
+        &block_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    if !accounts.block.data_is_empty() && accounts.block.owner == program_id {
+        return Err(JabError::SenderBlocked.into());
+    }
+
+    let (contact_key, _) =
+        Contact::find_key(accounts.receiver.key, accounts.sender.key, program_id);
+    check_account_key(
+        accounts.contact,
+        &contact_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    let is_contact = !accounts.contact.data_is_empty() && accounts.contact.owner == program_id;
+
+    // Contacts do not go through message requests
+    if is_contact {
+        thread.state = ThreadState::Accepted;
+    }
+    thread.check_can_send(accounts.sender.key)?;
+
     let (message_key, bump) = Message::find_key(
         thread.msg_count,
         accounts.sender.key,
@@ -176,6 +274,37 @@ pub(crate) fn process(
     thread.increment_msg_count(now);
     thread.save(&mut accounts.thread.data.borrow_mut());
 
+    let (reply_escrow_key, _) = Escrow::find_key(&replies_to, program_id);
+    check_account_key(
+        accounts.reply_escrow,
+        &reply_escrow_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    // Replying releases the payment held for the message replied to
+    if !accounts.reply_escrow.data_is_empty() && accounts.reply_escrow.owner == program_id {
+        let reply_escrow = Escrow::from_account_info(accounts.reply_escrow)?;
+        if reply_escrow.receiver == *accounts.sender.key
+            && reply_escrow.sender == *accounts.receiver.key
+        {
+            {
+                let mut escrow_lamports = accounts.reply_escrow.lamports.borrow_mut();
+                let mut sender_lamports = accounts.sender.lamports.borrow_mut();
+                **escrow_lamports = escrow_lamports.checked_sub(reply_escrow.amount).unwrap();
+                **sender_lamports = sender_lamports.checked_add(reply_escrow.amount).unwrap();
+            }
+            // The rent goes back to the sender of the message replied to
+            close_account(accounts.reply_escrow, accounts.receiver)?;
+        }
+    }
+
+    // Checked before its emptiness so that payments cannot be skipped with another account
+    check_account_key(
+        accounts.receiver_profile,
+        &Profile::find_key(accounts.receiver.key, program_id).0,
+        JabError::AccountNotDeterministic,
+    )?;
+
     // Transfer lamports if receiver profile exists
     if !accounts.receiver_profile.data_is_empty() {
         check_account_owner(
@@ -189,38 +318,105 @@ pub(crate) fn process(
             return Err(JabError::DmClosed.into());
         }
 
-        let transfer_fee = profile
-            .lamports_per_message
-            .checked_mul(FEE)
-            .unwrap()
-            .checked_div(100)
-            .unwrap();
-        let transfer_amount = profile
-            .lamports_per_message
-            .checked_sub(transfer_fee)
-            .unwrap();
-
-        let transfer_amount_instruction =
-            transfer(accounts.sender.key, accounts.receiver.key, transfer_amount);
-        let transfer_fee_instruction =
-            transfer(accounts.sender.key, accounts.sol_vault.key, transfer_fee);
-
-        invoke(
-            &transfer_amount_instruction,
-            &[
-                accounts.system_program.clone(),
-                accounts.sender.clone(),
-                accounts.receiver.clone(),
-            ],
-        )?;
+        // Contacts of the receiver message for free
+        if is_contact {
+            return Ok(());
+        }
 
-        invoke(
-            &transfer_fee_instruction,
-            &[
-                accounts.system_program.clone(),
-                accounts.sender.clone(),
-                accounts.sol_vault.clone(),
-            ],
+        if let Some(price_mint) = profile.price_mint {
+            let (spl_token_program, token_source, token_destination, vault_token_account) = match (
+                accounts.spl_token_program,
+                accounts.token_source,
+                accounts.token_destination,
+                accounts.vault_token_account,
+            ) {
+                (Some(a), Some(b), Some(c), Some(d)) => (a, b, c, d),
+                _ => return Err(JabError::MissingTokenAccounts.into()),
+            };
+            return transfer_token_payment(
+                program_id,
+                &config,
+                spl_token_program,
+                accounts.sender,
+                token_source,
+                token_destination,
+                vault_token_account,
+                accounts.referrer.zip(accounts.referrer_stats),
+                &price_mint,
+                accounts.receiver.key,
+                profile.price_amount,
+            );
+        }
+
+        let fee = config.fee(profile.lamports_per_message);
+        let transfer_amount = profile.lamports_per_message.checked_sub(fee).unwrap();
+
+        // The fee is charged up front and kept even if the escrowed payment is refunded
+        if profile.escrow_enabled {
+            let (escrow_key, escrow_bump) = Escrow::find_key(&message_key, program_id);
+            check_account_key(
+                accounts.escrow,
+                &escrow_key,
+                JabError::AccountNotDeterministic,
+            )?;
+
+            let escrow = Escrow::new(
+                message_key,
+                *accounts.sender.key,
+                *accounts.receiver.key,
+                transfer_amount,
+                now.checked_add(profile.escrow_period)
+                    .ok_or(JabError::InvalidEscrowPeriod)?,
+            );
+            let escrow_len = escrow.borsh_len();
+            let escrow_lamports = Rent::get()?
+                .minimum_balance(escrow_len)
+                .checked_add(transfer_amount)
+                .unwrap();
+
+            invoke_signed(
+                &create_account(
+                    accounts.sender.key,
+                    &escrow_key,
+                    escrow_lamports,
+                    escrow_len as u64,
+                    program_id,
+                ),
+                &[
+                    accounts.system_program.clone(),
+                    accounts.sender.clone(),
+                    accounts.escrow.clone(),
+                ],
+                &[&[
+                    Escrow::SEED.as_bytes(),
+                    &message_key.to_bytes(),
+                    &[escrow_bump],
+                ]],
+            )?;
+
+            escrow.save(&mut accounts.escrow.data.borrow_mut());
+        } else {
+            let transfer_amount_instruction =
+                transfer(accounts.sender.key, accounts.receiver.key, transfer_amount);
+
+            invoke(
+                &transfer_amount_instruction,
+                &[
+                    accounts.system_program.clone(),
+                    accounts.sender.clone(),
+                    accounts.receiver.clone(),
+                ],
+            )?;
+        }
+
+        transfer_fee(
+            program_id,
+            &config,
+            accounts.system_program,
+            accounts.sender,
+            accounts.sol_vault,
+            accounts.referrer.zip(accounts.referrer_stats),
+            fee,
         )?;
     }
 
@@ -2,8 +2,9 @@
 use crate::{
     state::MessageType,
     utils::{
-        check_account_key, check_account_owner, check_admin_only, check_group_message_type,
-        check_signer, FEE, SOL_VAULT,
+        append_bucket_message, check_account_key, check_account_owner, check_group_message_type,
+        check_group_permission, check_signer, load_config, load_group_admin, load_group_member,
+        load_group_moderation, transfer_fee, transfer_token_payment,
     },
 };
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -21,7 +22,8 @@ use solana_program::{
 };
 
 use crate::error::JabError;
-use crate::state::{GroupThread, Message};
+use crate::events::GroupMessageSent;
+use crate::state::{BucketMessage, GroupPermission, GroupThread, Message, StorageMode};
 
 use bonfida_utils::{BorshSize, InstructionsAccount};
 
@@ -29,9 +31,9 @@ use bonfida_utils::{BorshSize, InstructionsAccount};
 pub struct Params {
     pub kind: MessageType,
     pub replies_to: Pubkey,
-    pub admin_index: Option<u64>,
-    pub group_name: String,
     pub message: Vec<u8>,
+    // Chosen by the sender, e.g. a per sender sequence number (unused in bucketed groups)
+    pub nonce: u32,
 }
 
 #[derive(InstructionsAccount)]
@@ -43,7 +45,7 @@ pub struct Accounts<'a, T> {
     #[cons(writable, signer)]
     pub sender: &'a T,
 
-    /// The group thread account
+    /// The group thread account, only written to in bucketed groups
     #[cons(writable)]
     pub group_thread: &'a T,
 
@@ -51,13 +53,48 @@ pub struct Accounts<'a, T> {
     #[cons(writable)]
     pub destination_wallet: &'a T,
 
-    /// The message account
+    /// The message account, or the current message bucket in bucketed groups
     #[cons(writable)]
     pub message: &'a T,
 
     /// The SOL vault account
     #[cons(writable)]
     pub sol_vault: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+
+    /// The sender group membership account
+    pub group_member: &'a T,
+
+    /// The sender group moderation account
+    pub group_moderation: &'a T,
+
+    /// The sender group admin account (may be empty)
+    pub group_admin: &'a T,
+
+    /// The SPL token program account, when paying in tokens
+    pub spl_token_program: Option<&'a T>,
+
+    /// The sender token account, when paying in tokens
+    #[cons(writable)]
+    pub token_source: Option<&'a T>,
+
+    /// The destination wallet token account, when paying in tokens
+    #[cons(writable)]
+    pub token_destination: Option<&'a T>,
+
+    /// The vault token account, when paying in tokens
+    #[cons(writable)]
+    pub vault_token_account: Option<&'a T>,
+
+    /// The referrer wallet, or token account when paying in tokens
+    #[cons(writable)]
+    pub referrer: Option<&'a T>,
+
+    /// The referrer account
+    #[cons(writable)]
+    pub referrer_stats: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -66,26 +103,43 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         accounts: &'a [AccountInfo<'b>],
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
-        let accounts = Self {
+        let mut accounts = Self {
             system_program: next_account_info(accounts_iter)?,
             sender: next_account_info(accounts_iter)?,
             group_thread: next_account_info(accounts_iter)?,
             destination_wallet: next_account_info(accounts_iter)?,
             message: next_account_info(accounts_iter)?,
             sol_vault: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_moderation: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
+            spl_token_program: None,
+            token_source: None,
+            token_destination: None,
+            vault_token_account: None,
+            referrer: None,
+            referrer_stats: None,
         };
 
+        // The token accounts are only passed when paying in tokens
+        if let Some(spl_token_program) = accounts_iter.as_slice().first() {
+            if spl_token_program.key == &spl_token::ID {
+                accounts.spl_token_program = next_account_info(accounts_iter).ok();
+                accounts.token_source = next_account_info(accounts_iter).ok();
+                accounts.token_destination = next_account_info(accounts_iter).ok();
+                accounts.vault_token_account = next_account_info(accounts_iter).ok();
+            }
+        }
+        accounts.referrer = next_account_info(accounts_iter).ok();
+        accounts.referrer_stats = next_account_info(accounts_iter).ok();
+
         // Check keys
         check_account_key(
             accounts.system_program,
             &system_program::ID,
             JabError::WrongSystemProgramAccount,
         )?;
-        check_account_key(
-            accounts.sol_vault,
-            &SOL_VAULT,
-            JabError::WrongSolVaultAccount,
-        )?;
 
         // Check ownership
         check_account_owner(
@@ -93,7 +147,6 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             program_id,
             JabError::WrongThreadAccountOwner,
         )?;
-        check_account_owner(accounts.message, &system_program::ID, JabError::WrongOwner)?;
 
         // Check signer
         check_signer(accounts.sender)?;
@@ -112,15 +165,19 @@ pub(crate) fn process(
     let Params {
         kind,
         message,
-        group_name,
         replies_to,
-        admin_index,
+        nonce,
     } = params;
 
-    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
-    let (group_thread_key, _) = GroupThread::find_key(group_name, group_thread.owner, program_id);
+    let config = load_config(accounts.config, program_id)?;
+    check_account_key(
+        accounts.sol_vault,
+        &config.vault,
+        JabError::WrongSolVaultAccount,
+    )?;
 
-    check_admin_only(&group_thread, accounts.sender.key, admin_index)?;
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
 
     check_account_key(
         accounts.group_thread,
@@ -136,67 +193,163 @@ pub(crate) fn process(
 
     check_group_message_type(&group_thread, &kind)?;
 
-    let (message_key, bump) = Message::find_key(
-        group_thread.msg_count,
+    let group_member = load_group_member(
+        accounts.group_member,
         &group_thread_key,
+        accounts.sender.key,
+        program_id,
+    )?;
+    let group_admin = load_group_admin(
+        accounts.group_admin,
         &group_thread_key,
+        accounts.sender.key,
         program_id,
-    );
-
-    check_account_key(
-        accounts.message,
-        &message_key,
-        JabError::AccountNotDeterministic,
     )?;
+    let has_permission = |permission: u8| {
+        check_group_permission(
+            &group_thread,
+            accounts.sender.key,
+            group_admin.as_ref(),
+            group_member.as_ref(),
+            permission,
+        )
+        .is_ok()
+    };
+
+    if group_thread.admin_only && !has_permission(GroupPermission::POST_ADMIN_ONLY) {
+        return Err(JabError::ChatMuted.into());
+    }
 
-    let now = Clock::get()?.unix_timestamp;
-    let message = Message::new(kind, now, message, *accounts.sender.key, replies_to);
-    let message_len = message.borsh_len();
-    let lamports = Rent::get()?.minimum_balance(message_len);
+    // The owner and admins do not need a membership account
+    if group_thread.requires_membership()
+        && group_member.is_none()
+        && group_admin.is_none()
+        && group_thread.owner != *accounts.sender.key
+    {
+        return Err(JabError::NotGroupMember.into());
+    }
 
-    let allocate_account = create_account(
+    let is_fee_exempt = group_thread.destination_wallet == *accounts.sender.key
+        || has_permission(GroupPermission::FEE_EXEMPT);
+
+    let now = Clock::get()?.unix_timestamp;
+    if let Some(moderation) = load_group_moderation(
+        accounts.group_moderation,
+        &group_thread_key,
         accounts.sender.key,
-        &message_key,
-        lamports,
-        message_len as u64,
         program_id,
-    );
-
-    invoke_signed(
-        &allocate_account,
-        &[
-            accounts.system_program.clone(),
-            accounts.sender.clone(),
-            accounts.message.clone(),
-        ],
-        &[&[
-            Message::SEED.as_bytes(),
-            group_thread.msg_count.to_string().as_bytes(),
-            &accounts.group_thread.key.to_bytes(),
-            &accounts.group_thread.key.to_bytes(),
-            &[bump],
-        ]],
-    )?;
+    )? {
+        moderation.check_can_post(now)?;
+    }
 
-    message.save(&mut accounts.message.data.borrow_mut());
+    let bucket_slot = match group_thread.storage_mode {
+        StorageMode::Accounts => {
+            let (message_key, bump) =
+                Message::find_group_key(&group_thread_key, accounts.sender.key, nonce, program_id);
+
+            check_account_key(
+                accounts.message,
+                &message_key,
+                JabError::AccountNotDeterministic,
+            )?;
+            check_account_owner(accounts.message, &system_program::ID, JabError::WrongOwner)?;
+
+            let message = Message::new(kind, now, message, *accounts.sender.key, replies_to);
+            let message_len = message.borsh_len();
+            let lamports = Rent::get()?.minimum_balance(message_len);
+
+            let allocate_account = create_account(
+                accounts.sender.key,
+                &message_key,
+                lamports,
+                message_len as u64,
+                program_id,
+            );
+
+            invoke_signed(
+                &allocate_account,
+                &[
+                    accounts.system_program.clone(),
+                    accounts.sender.clone(),
+                    accounts.message.clone(),
+                ],
+                &[&[
+                    Message::GROUP_SEED.as_bytes(),
+                    &group_thread_key.to_bytes(),
+                    &accounts.sender.key.to_bytes(),
+                    &nonce.to_le_bytes(),
+                    &[bump],
+                ]],
+            )?;
+
+            message.save(&mut accounts.message.data.borrow_mut());
+            None
+        }
+        StorageMode::Buckets => {
+            let message = BucketMessage::new(kind, now, message, *accounts.sender.key, replies_to);
+            let slot = append_bucket_message(
+                program_id,
+                &mut group_thread,
+                &group_thread_key,
+                accounts.system_program,
+                accounts.sender,
+                accounts.message,
+                message,
+            )?;
+            Some((group_thread.current_bucket, slot))
+        }
+    };
+
+    GroupMessageSent {
+        message: *accounts.message.key,
+        group_thread: group_thread_key,
+        sender: *accounts.sender.key,
+        nonce,
+        timestamp: now,
+        bucket_slot,
+    }
+    .emit();
 
-    group_thread.increment_msg_count(now);
-    group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+    // Messages stored in their own account are keyed by sender nonce and are not counted
+    if group_thread.storage_mode == StorageMode::Buckets {
+        group_thread.increment_msg_count(now);
+        group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+    }
 
-    let is_fee_exempt =
-        GroupThread::is_fee_exempt(&group_thread, *accounts.sender.key, admin_index);
+    if let (false, Some(price_mint)) = (is_fee_exempt, group_thread.price_mint) {
+        let (spl_token_program, token_source, token_destination, vault_token_account) = match (
+            accounts.spl_token_program,
+            accounts.token_source,
+            accounts.token_destination,
+            accounts.vault_token_account,
+        ) {
+            (Some(a), Some(b), Some(c), Some(d)) => (a, b, c, d),
+            _ => return Err(JabError::MissingTokenAccounts.into()),
+        };
+        return transfer_token_payment(
+            program_id,
+            &config,
+            spl_token_program,
+            accounts.sender,
+            token_source,
+            token_destination,
+            vault_token_account,
+            accounts.referrer.zip(accounts.referrer_stats),
+            &price_mint,
+            &group_thread.destination_wallet,
+            group_thread.price_amount,
+        );
+    }
 
     if !is_fee_exempt && group_thread.lamports_per_message > 0 {
-        let transfer_fee = (group_thread.lamports_per_message * FEE) / 100;
-        let transfer_amount = group_thread.lamports_per_message - transfer_fee;
+        let fee = config.fee(group_thread.lamports_per_message);
+        let transfer_amount = group_thread.lamports_per_message - fee;
 
         let transfer_amount_instruction = transfer(
             accounts.sender.key,
             accounts.destination_wallet.key,
             transfer_amount,
         );
-        let transfer_fee_instruction =
-            transfer(accounts.sender.key, accounts.sol_vault.key, transfer_fee);
 
         invoke(
             &transfer_amount_instruction,
@@ -207,13 +360,14 @@ pub(crate) fn process(
             ],
         )?;
 
-        invoke(
-            &transfer_fee_instruction,
-            &[
-                accounts.system_program.clone(),
-                accounts.sender.clone(),
-                accounts.sol_vault.clone(),
-            ],
+        transfer_fee(
+            program_id,
+            &config,
+            accounts.system_program,
+            accounts.sender,
+            accounts.sol_vault,
+            accounts.referrer.zip(accounts.referrer_stats),
+            fee,
         )?;
     }
 
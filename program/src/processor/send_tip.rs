@@ -1,18 +1,21 @@
 //! Send a tip
-use crate::utils::{check_account_key, check_account_owner, check_signer};
+use crate::utils::{
+    check_account_key, check_account_owner, check_signer, load_config, save_profile,
+    transfer_token_payment,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
-    program::invoke,
     program_error::ProgramError,
     program_pack::Pack,
     pubkey::Pubkey,
+    system_program,
 };
 
 use crate::error::JabError;
 use crate::state::Profile;
-use spl_token::{instruction::transfer, state::Account};
+use spl_token::state::Account;
 
 use bonfida_utils::{BorshSize, InstructionsAccount};
 
@@ -26,6 +29,9 @@ pub struct Accounts<'a, T> {
     /// The SPL token program ID
     pub spl_token_program: &'a T,
 
+    /// The system program account
+    pub system_program: &'a T,
+
     /// The tip sender profile account
     #[cons(writable)]
     pub sender_profile: &'a T,
@@ -48,6 +54,21 @@ pub struct Accounts<'a, T> {
     /// The token destination account
     #[cons(writable)]
     pub token_destination: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+
+    /// The vault token account
+    #[cons(writable)]
+    pub vault_token_account: &'a T,
+
+    /// The referrer token account
+    #[cons(writable)]
+    pub referrer: Option<&'a T>,
+
+    /// The referrer account
+    #[cons(writable)]
+    pub referrer_stats: Option<&'a T>,
 }
 
 impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
@@ -58,12 +79,17 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Self {
             spl_token_program: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
             sender_profile: next_account_info(accounts_iter)?,
             sender: next_account_info(accounts_iter)?,
             receiver_profile: next_account_info(accounts_iter)?,
             receiver: next_account_info(accounts_iter)?,
             token_source: next_account_info(accounts_iter)?,
             token_destination: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            vault_token_account: next_account_info(accounts_iter)?,
+            referrer: next_account_info(accounts_iter).ok(),
+            referrer_stats: next_account_info(accounts_iter).ok(),
         };
 
         // Check keys
@@ -72,6 +98,11 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
             &spl_token::ID,
             JabError::WrongSplId,
         )?;
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
 
         // Check ownership
         check_account_owner(
@@ -105,6 +136,8 @@ pub(crate) fn process(
     let accounts = Accounts::parse(program_id, accounts)?;
     let Params { amount } = params;
 
+    let config = load_config(accounts.config, program_id)?;
+
     let (sender_profile_key, _) = Profile::find_key(accounts.sender.key, program_id);
     let (receiver_profile_key, _) = Profile::find_key(accounts.receiver.key, program_id);
 
@@ -133,27 +166,31 @@ pub(crate) fn process(
     sender_profile.tips_sent += 1;
     receiver_profile.tips_received += 1;
 
-    sender_profile.save(&mut accounts.sender_profile.data.borrow_mut());
-    receiver_profile.save(&mut accounts.receiver_profile.data.borrow_mut());
-
-    // Transfer tokens
-    let transfer_ix = transfer(
-        &spl_token::ID,
-        accounts.token_source.key,
-        accounts.token_destination.key,
-        accounts.sender.key,
-        &[],
-        amount,
+    save_profile(
+        &sender_profile,
+        accounts.sender_profile,
+        accounts.sender,
+        accounts.system_program,
     )?;
-    invoke(
-        &transfer_ix,
-        &[
-            accounts.spl_token_program.clone(),
-            accounts.token_source.clone(),
-            accounts.token_destination.clone(),
-            accounts.sender.clone(),
-        ],
+    save_profile(
+        &receiver_profile,
+        accounts.receiver_profile,
+        accounts.sender,
+        accounts.system_program,
     )?;
 
-    Ok(())
+    // Transfer tokens
+    transfer_token_payment(
+        program_id,
+        &config,
+        accounts.spl_token_program,
+        accounts.sender,
+        accounts.token_source,
+        accounts.token_destination,
+        accounts.vault_token_account,
+        accounts.referrer.zip(accounts.referrer_stats),
+        &destination_token_account.mint,
+        accounts.receiver.key,
+        amount,
+    )
 }
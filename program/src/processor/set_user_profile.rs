@@ -1,13 +1,17 @@
 //! Edit a Jab profile information
 use crate::error::JabError;
 use crate::state::Profile;
-use crate::utils::{check_account_key, check_account_owner, check_profile_params, check_signer};
+use crate::utils::{
+    check_account_key, check_account_owner, check_edit_window, check_escrow_settings,
+    check_profile_params, check_signer, save_profile,
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
     program_error::ProgramError,
     pubkey::Pubkey,
+    system_program,
 };
 
 use bonfida_utils::{BorshSize, InstructionsAccount};
@@ -18,11 +22,19 @@ pub struct Params {
     pub display_domain_name: String,
     pub bio: String,
     pub lamports_per_message: u64,
+    pub price_mint: Option<Pubkey>,
+    pub price_amount: u64,
     pub allow_dm: bool,
+    pub escrow_enabled: bool,
+    pub escrow_period: i64,
+    pub edit_window: Option<i64>,
 }
 
 #[derive(InstructionsAccount)]
 pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
     /// The profile owner account
     #[cons(writable, signer)]
     pub profile_owner: &'a T,
@@ -39,11 +51,17 @@ impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
     ) -> Result<Self, ProgramError> {
         let accounts_iter = &mut accounts.iter();
         let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
             profile_owner: next_account_info(accounts_iter)?,
             profile: next_account_info(accounts_iter)?,
         };
 
         // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
 
         // Check ownership
         check_account_owner(accounts.profile, program_id, JabError::WrongProfileOwner)?;
@@ -67,7 +85,12 @@ pub(crate) fn process(
         display_domain_name,
         bio,
         lamports_per_message,
+        price_mint,
+        price_amount,
         allow_dm,
+        escrow_enabled,
+        escrow_period,
+        edit_window,
     } = params;
 
     let (expected_user_profile_key, _) = Profile::find_key(accounts.profile_owner.key, program_id);
@@ -79,15 +102,27 @@ pub(crate) fn process(
     )?;
 
     check_profile_params(&picture_hash, &display_domain_name, &bio)?;
+    check_edit_window(edit_window)?;
+    check_escrow_settings(escrow_enabled, escrow_period, price_mint)?;
 
     let mut profile = Profile::from_account_info(accounts.profile)?;
 
     profile.lamports_per_message = lamports_per_message;
+    profile.price_mint = price_mint;
+    profile.price_amount = price_amount;
     profile.bio = bio;
     profile.picture_hash = picture_hash;
     profile.allow_dm = allow_dm;
+    profile.escrow_enabled = escrow_enabled;
+    profile.escrow_period = escrow_period;
+    profile.edit_window = edit_window;
 
-    profile.save(&mut accounts.profile.data.borrow_mut());
+    save_profile(
+        &profile,
+        accounts.profile,
+        accounts.profile_owner,
+        accounts.system_program,
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,142 @@
+//! Unban a user from a group
+use crate::error::JabError;
+use crate::state::{GroupModeration, GroupPermission, GroupThread};
+use crate::utils::{
+    check_account_key, check_account_owner, check_group_permission, check_signer, close_account,
+    load_group_admin, load_group_member,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The group owner or admin account
+    #[cons(writable, signer)]
+    pub moderator: &'a T,
+
+    /// The group thread account
+    #[cons(writable)]
+    pub group_thread: &'a T,
+
+    /// The group moderation account
+    #[cons(writable)]
+    pub group_moderation: &'a T,
+
+    /// The moderator group membership account (may be empty)
+    pub group_member: &'a T,
+
+    /// The moderator group admin account (may be empty)
+    pub group_admin: &'a T,
+
+    /// The account that paid for the group moderation account
+    #[cons(writable)]
+    pub moderation_payer: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            moderator: next_account_info(accounts_iter)?,
+            group_thread: next_account_info(accounts_iter)?,
+            group_moderation: next_account_info(accounts_iter)?,
+            group_member: next_account_info(accounts_iter)?,
+            group_admin: next_account_info(accounts_iter)?,
+            moderation_payer: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(
+            accounts.group_thread,
+            program_id,
+            JabError::WrongGroupThreadOwner,
+        )?;
+        check_account_owner(accounts.group_moderation, program_id, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.moderator)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let mut group_thread = GroupThread::from_account_info(accounts.group_thread)?;
+    let group_thread_key = group_thread.key(program_id);
+
+    check_account_key(
+        accounts.group_thread,
+        &group_thread_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    let group_member = load_group_member(
+        accounts.group_member,
+        &group_thread_key,
+        accounts.moderator.key,
+        program_id,
+    )?;
+    let group_admin = load_group_admin(
+        accounts.group_admin,
+        &group_thread_key,
+        accounts.moderator.key,
+        program_id,
+    )?;
+    check_group_permission(
+        &group_thread,
+        accounts.moderator.key,
+        group_admin.as_ref(),
+        group_member.as_ref(),
+        GroupPermission::BAN,
+    )?;
+
+    let mut group_moderation = GroupModeration::from_account_info(accounts.group_moderation)?;
+    let (group_moderation_key, _) =
+        GroupModeration::find_key(&group_thread_key, &group_moderation.user, program_id);
+
+    check_account_key(
+        accounts.group_moderation,
+        &group_moderation_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    check_account_key(
+        accounts.moderation_payer,
+        &group_moderation.payer,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    // The account is no longer needed once the user is neither banned nor muted
+    if group_moderation.muted_until <= Clock::get()?.unix_timestamp {
+        group_thread.remove_linked_account();
+        group_thread.save(&mut accounts.group_thread.data.borrow_mut());
+        return close_account(accounts.group_moderation, accounts.moderation_payer);
+    }
+
+    group_moderation.banned = false;
+    group_moderation.save(&mut accounts.group_moderation.data.borrow_mut());
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! Unblock a user and close the block account
+use crate::error::JabError;
+use crate::state::Block;
+use crate::utils::{check_account_key, check_account_owner, check_signer, close_account};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The blocker account
+    #[cons(writable, signer)]
+    pub blocker: &'a T,
+
+    /// The block account
+    #[cons(writable)]
+    pub block: &'a T,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            blocker: next_account_info(accounts_iter)?,
+            block: next_account_info(accounts_iter)?,
+        };
+
+        // Check keys
+
+        // Check ownership
+        check_account_owner(accounts.block, program_id, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.blocker)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+
+    let block = Block::from_account_info(accounts.block)?;
+    let (block_key, _) = Block::find_key(&block.blocker, &block.blocked, program_id);
+
+    check_account_key(
+        accounts.block,
+        &block_key,
+        JabError::AccountNotDeterministic,
+    )?;
+
+    check_account_key(
+        accounts.blocker,
+        &block.blocker,
+        JabError::AccountNotAuthorized,
+    )?;
+
+    close_account(accounts.block, accounts.blocker)?;
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! Update the program config, optionally creating the vault token account of a mint
+use crate::error::JabError;
+use crate::state::Config;
+use crate::utils::{
+    check_account_key, check_account_owner, check_signer, create_vault_token_account,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    pub admin: Pubkey,
+    pub fee_bps: u16,
+    pub fee_authority: Pubkey,
+    pub referrer_share_bps: u16,
+    pub paused: bool,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The config admin account
+    #[cons(writable, signer)]
+    pub admin: &'a T,
+
+    /// The program config account
+    #[cons(writable)]
+    pub config: &'a T,
+
+    /// The system program account, when creating a vault token account
+    pub system_program: Option<&'a T>,
+
+    /// The SPL token program account, when creating a vault token account
+    pub spl_token_program: Option<&'a T>,
+
+    /// The associated token program account, when creating a vault token account
+    pub associated_token_program: Option<&'a T>,
+
+    /// The rent sysvar account, when creating a vault token account
+    pub rent_sysvar: Option<&'a T>,
+
+    /// The vault account, when creating a vault token account
+    pub vault: Option<&'a T>,
+
+    /// The mint of the vault token account to create
+    pub mint: Option<&'a T>,
+
+    /// The vault token account to create, skipped if it already exists
+    #[cons(writable)]
+    pub vault_token_account: Option<&'a T>,
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            admin: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter).ok(),
+            spl_token_program: next_account_info(accounts_iter).ok(),
+            associated_token_program: next_account_info(accounts_iter).ok(),
+            rent_sysvar: next_account_info(accounts_iter).ok(),
+            vault: next_account_info(accounts_iter).ok(),
+            mint: next_account_info(accounts_iter).ok(),
+            vault_token_account: next_account_info(accounts_iter).ok(),
+        };
+
+        // Check keys
+        let (config_key, _) = Config::find_key(program_id);
+        check_account_key(
+            accounts.config,
+            &config_key,
+            JabError::AccountNotDeterministic,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.config, program_id, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.admin)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params {
+        admin,
+        fee_bps,
+        fee_authority,
+        referrer_share_bps,
+        paused,
+    } = params;
+
+    let mut config = Config::from_account_info(accounts.config)?;
+
+    check_account_key(
+        accounts.admin,
+        &config.admin,
+        JabError::AccountNotAuthorized,
+    )?;
+
+    if fee_bps > 10_000 || referrer_share_bps > 10_000 {
+        return Err(JabError::InvalidFee.into());
+    }
+
+    config.admin = admin;
+    config.fee_bps = fee_bps;
+    config.fee_authority = fee_authority;
+    config.referrer_share_bps = referrer_share_bps;
+    config.paused = paused;
+
+    config.save(&mut accounts.config.data.borrow_mut());
+
+    // The vault token account of a mint has to exist before it can be used for payments
+    if let Some(system_program) = accounts.system_program {
+        let (
+            spl_token_program,
+            associated_token_program,
+            rent_sysvar,
+            vault,
+            mint,
+            vault_token_account,
+        ) = match (
+            accounts.spl_token_program,
+            accounts.associated_token_program,
+            accounts.rent_sysvar,
+            accounts.vault,
+            accounts.mint,
+            accounts.vault_token_account,
+        ) {
+            (Some(a), Some(b), Some(c), Some(d), Some(e), Some(f)) => (a, b, c, d, e, f),
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+        create_vault_token_account(
+            &config,
+            accounts.admin,
+            vault,
+            vault_token_account,
+            mint,
+            system_program,
+            spl_token_program,
+            associated_token_program,
+            rent_sysvar,
+        )?;
+    }
+
+    Ok(())
+}
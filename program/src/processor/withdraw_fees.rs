@@ -0,0 +1,198 @@
+//! Withdraw protocol fees from the vault, split between recipients
+use crate::error::JabError;
+use crate::state::Config;
+use crate::utils::{check_account_key, check_account_owner, check_signer};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction, system_program,
+    sysvar::Sysvar,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+use bonfida_utils::{BorshSize, InstructionsAccount};
+
+#[derive(BorshDeserialize, BorshSerialize, BorshSize)]
+pub struct Params {
+    // `None` to withdraw SOL
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    // Share of each recipient (basis points), in the order of the recipient accounts
+    pub shares_bps: Vec<u16>,
+}
+
+#[derive(InstructionsAccount)]
+pub struct Accounts<'a, T> {
+    /// The system program account
+    pub system_program: &'a T,
+
+    /// The SPL token program account
+    pub spl_token_program: &'a T,
+
+    /// The fee authority account
+    #[cons(signer)]
+    pub fee_authority: &'a T,
+
+    /// The program config account
+    pub config: &'a T,
+
+    /// The vault account
+    #[cons(writable)]
+    pub vault: &'a T,
+
+    /// The vault token account, or the vault account when withdrawing SOL
+    #[cons(writable)]
+    pub source: &'a T,
+
+    /// The recipient wallets, or token accounts when withdrawing tokens
+    #[cons(writable)]
+    pub recipients: &'a [T],
+}
+
+impl<'a, 'b: 'a> Accounts<'a, AccountInfo<'b>> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+        let accounts = Self {
+            system_program: next_account_info(accounts_iter)?,
+            spl_token_program: next_account_info(accounts_iter)?,
+            fee_authority: next_account_info(accounts_iter)?,
+            config: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            source: next_account_info(accounts_iter)?,
+            recipients: accounts_iter.as_slice(),
+        };
+
+        // Check keys
+        check_account_key(
+            accounts.system_program,
+            &system_program::ID,
+            JabError::WrongSystemProgramAccount,
+        )?;
+        check_account_key(
+            accounts.spl_token_program,
+            &spl_token::ID,
+            JabError::WrongSplId,
+        )?;
+        let (config_key, _) = Config::find_key(program_id);
+        check_account_key(
+            accounts.config,
+            &config_key,
+            JabError::AccountNotDeterministic,
+        )?;
+
+        // Check ownership
+        check_account_owner(accounts.config, program_id, JabError::WrongOwner)?;
+
+        // Check signer
+        check_signer(accounts.fee_authority)?;
+
+        Ok(accounts)
+    }
+}
+
+pub(crate) fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    params: Params,
+) -> ProgramResult {
+    let accounts = Accounts::parse(program_id, accounts)?;
+    let Params {
+        mint,
+        amount,
+        shares_bps,
+    } = params;
+
+    let config = Config::from_account_info(accounts.config)?;
+
+    check_account_key(
+        accounts.fee_authority,
+        &config.fee_authority,
+        JabError::AccountNotAuthorized,
+    )?;
+    check_account_key(
+        accounts.vault,
+        &config.vault,
+        JabError::WrongSolVaultAccount,
+    )?;
+
+    if shares_bps.len() != accounts.recipients.len()
+        || shares_bps.iter().map(|s| *s as u64).sum::<u64>() != 10_000
+    {
+        return Err(JabError::InvalidFeeSplit.into());
+    }
+
+    let expected_source = match mint {
+        Some(mint) => get_associated_token_address(&config.vault, &mint),
+        None => config.vault,
+    };
+    check_account_key(
+        accounts.source,
+        &expected_source,
+        JabError::WrongTokenAccount,
+    )?;
+
+    // The vault stays rent exempt when withdrawing SOL
+    if mint.is_none() {
+        let available = accounts
+            .vault
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        if amount > available {
+            return Err(ProgramError::InsufficientFunds);
+        }
+    }
+
+    let vault_seeds: &[&[u8]] = &[Config::VAULT_SEED.as_bytes(), &[config.vault_bump]];
+    let mut remaining = amount;
+
+    for (i, (recipient, share_bps)) in accounts.recipients.iter().zip(shares_bps).enumerate() {
+        // The last recipient gets the rounding remainder
+        let share = if i == accounts.recipients.len() - 1 {
+            remaining
+        } else {
+            ((amount as u128 * share_bps as u128) / 10_000) as u64
+        };
+        remaining -= share;
+
+        if mint.is_some() {
+            let transfer_ix = spl_token::instruction::transfer(
+                &spl_token::ID,
+                accounts.source.key,
+                recipient.key,
+                accounts.vault.key,
+                &[],
+                share,
+            )?;
+            invoke_signed(
+                &transfer_ix,
+                &[
+                    accounts.spl_token_program.clone(),
+                    accounts.source.clone(),
+                    recipient.clone(),
+                    accounts.vault.clone(),
+                ],
+                &[vault_seeds],
+            )?;
+        } else {
+            invoke_signed(
+                &system_instruction::transfer(accounts.vault.key, recipient.key, share),
+                &[
+                    accounts.system_program.clone(),
+                    accounts.vault.clone(),
+                    recipient.clone(),
+                ],
+                &[vault_seeds],
+            )?;
+        }
+    }
+
+    Ok(())
+}
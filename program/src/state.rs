@@ -1,33 +1,68 @@
-use crate::{error::JabError, utils::order_keys};
+use crate::{
+    error::JabError,
+    legacy::{GroupThreadV0, MessageV0, ProfileV0, ThreadV0},
+    utils::order_keys,
+};
 use bonfida_utils::BorshSize;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
-    account_info::AccountInfo, clock::UnixTimestamp, entrypoint::ProgramResult,
+    account_info::AccountInfo, clock::UnixTimestamp, entrypoint::ProgramResult, hash::hashv,
     program_error::ProgramError, pubkey::Pubkey,
 };
 
 pub const MAX_NAME_LENGTH: usize = 100;
 pub const MAX_BIO_LENGTH: usize = 100;
 pub const MAX_GROUP_NAME_LEN: usize = 100;
-pub const MAX_ADMIN_LEN: usize = 10;
 pub const MAX_HASH_LEN: usize = 64;
+// Default time during which a message can be edited (seconds)
+pub const DEFAULT_EDIT_WINDOW: i64 = 15 * 60;
+// Longest time a message payment can be held in escrow (seconds)
+pub const MAX_ESCROW_PERIOD: i64 = 30 * 24 * 60 * 60;
 
-pub const MAX_PROFILE_LEN: usize =
-    1 + 1 + MAX_HASH_LEN + MAX_NAME_LENGTH + MAX_BIO_LENGTH + 8 + 1 + 4 + 4;
+pub const MAX_PROFILE_LEN: usize = 1 // tag
+    + 1 // bump
+    + MAX_HASH_LEN // picture_hash
+    + MAX_NAME_LENGTH // display_domain_name
+    + MAX_BIO_LENGTH // bio
+    + 8 // lamports_per_message
+    + 1 // allow_dm
+    + 4 // tips_sent
+    + 4 // tips_received
+    + 4 // subscriber_count
+    + 1 // escrow_enabled
+    + 8 // escrow_period
+    + 33 // price_mint
+    + 8 // price_amount
+    + 9; // edit_window
 
 pub const MAX_GROUP_THREAD_LEN: usize = 1 // tag
     + 1 // bump
     + 1 // visible
     + 32 // owner
+    + 32 // creator
+    + 33 // pending_owner
     + 8 // last message time
     + 32 // destination_wallet
     + 4 // msg_count
+    + 4 // open_msg_count
     + 8 // lamports_per_message
+    + 33 // price_mint
+    + 8 // price_amount
     + 1 // media_enabled
     + 1 // admin_only
+    + 1 // members_only
+    + 1 // private
+    + 8 // edit_window
     + (4 + MAX_HASH_LEN) // group_pic_hash
     + (4 + MAX_GROUP_NAME_LEN) // group_name
-    + (4 + MAX_ADMIN_LEN * 32); // admins
+    + 1 // seed_version
+    + 32 // group_id
+    + 1 // storage_mode
+    + 4 // current_bucket
+    + 4; // linked_account_count
+
+// Size of the accounts storing the messages of bucketed groups
+pub const MESSAGE_BUCKET_LEN: usize = 4_096;
 
 pub const MAX_GROUP_THREAD_INDEX: usize = 1 + 4 + MAX_GROUP_NAME_LEN + 32 + 32;
 
@@ -41,6 +76,17 @@ pub enum Tag {
     GroupThread,
     GroupThreadIndex,
     Subscription,
+    Reaction,
+    GroupMember,
+    Invite,
+    GroupModeration,
+    Block,
+    Contact,
+    Escrow,
+    Config,
+    Referrer,
+    GroupAdmin,
+    MessageBucket,
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
@@ -54,6 +100,17 @@ pub struct Profile {
     pub allow_dm: bool,
     pub tips_sent: u32,
     pub tips_received: u32,
+    pub subscriber_count: u32,
+    // Whether message payments are held in escrow until the receiver replies
+    pub escrow_enabled: bool,
+    // Time after which an unanswered message payment can be refunded (seconds)
+    pub escrow_period: i64,
+    // Mint of the token messages are priced in, lamports are used when `None`
+    pub price_mint: Option<Pubkey>,
+    // Price per message in `price_mint` tokens
+    pub price_amount: u64,
+    // Time during which messages sent to the user can be edited, `DEFAULT_EDIT_WINDOW` when `None`
+    pub edit_window: Option<i64>,
 }
 
 impl Profile {
@@ -88,10 +145,20 @@ impl Profile {
             bump,
             tips_sent: 0,
             tips_received: 0,
+            subscriber_count: 0,
             allow_dm: true,
+            escrow_enabled: false,
+            escrow_period: 0,
+            price_mint: None,
+            price_amount: 0,
+            edit_window: None,
         }
     }
 
+    pub fn edit_window(&self) -> i64 {
+        self.edit_window.unwrap_or(DEFAULT_EDIT_WINDOW)
+    }
+
     pub fn save(&self, mut dst: &mut [u8]) {
         self.serialize(&mut dst).unwrap()
     }
@@ -101,11 +168,21 @@ impl Profile {
         if data[0] != Tag::Profile as u8 && data[0] != Tag::Uninitialized as u8 {
             return Err(JabError::DataTypeMismatch.into());
         }
+        if ProfileV0::is_legacy(data) {
+            return Ok(ProfileV0::deserialize(&mut data)?.migrate());
+        }
         let result = Profile::deserialize(&mut data)?;
         Ok(result)
     }
 }
 
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Clone, Copy)]
+pub enum ThreadState {
+    // Only the first message (the request) can be sent
+    Pending,
+    Accepted,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, BorshSize)]
 pub struct Thread {
     pub tag: Tag,
@@ -114,19 +191,36 @@ pub struct Thread {
     pub user_2: Pubkey,
     pub last_message_time: UnixTimestamp,
     pub bump: u8,
+    // Fields added after the initial layout, see `legacy::ThreadV0`
+    pub open_msg_count: u32,
+    pub state: ThreadState,
+    // Sender of the first message, `Pubkey::default()` until then
+    pub requester: Pubkey,
+    // Account that paid the rent of the thread
+    pub payer: Pubkey,
 }
 
 impl Thread {
     pub const SEED: &'static str = "thread";
 
-    pub fn new(user_1: Pubkey, user_2: Pubkey, bump: u8, last_message_time: UnixTimestamp) -> Self {
+    pub fn new(
+        user_1: Pubkey,
+        user_2: Pubkey,
+        bump: u8,
+        last_message_time: UnixTimestamp,
+        payer: Pubkey,
+    ) -> Self {
         Self {
             tag: Tag::Thread,
             msg_count: 0,
+            open_msg_count: 0,
             user_1,
             user_2,
             bump,
             last_message_time,
+            state: ThreadState::Pending,
+            requester: Pubkey::default(),
+            payer,
         }
     }
 
@@ -163,6 +257,9 @@ impl Thread {
         if data[0] != Tag::Thread as u8 && data[0] != Tag::Uninitialized as u8 {
             return Err(JabError::DataTypeMismatch.into());
         }
+        if ThreadV0::is_legacy(data) {
+            return Err(JabError::AccountNeedsMigration.into());
+        }
         let result = Thread::deserialize(&mut data)?;
         Ok(result)
     }
@@ -170,6 +267,42 @@ impl Thread {
     pub fn increment_msg_count(&mut self, current_time: i64) {
         self.last_message_time = current_time;
         self.msg_count += 1;
+        self.open_msg_count += 1;
+    }
+
+    pub fn decrement_open_msg_count(&mut self) {
+        self.open_msg_count = self.open_msg_count.saturating_sub(1);
+    }
+
+    pub fn is_participant(&self, address: &Pubkey) -> bool {
+        &self.user_1 == address || &self.user_2 == address
+    }
+
+    // Records the sender of the request or rejects messages while the thread is pending
+    pub fn check_can_send(&mut self, sender: &Pubkey) -> ProgramResult {
+        if self.state == ThreadState::Accepted {
+            return Ok(());
+        }
+        if self.msg_count != 0 {
+            return Err(JabError::ThreadPending.into());
+        }
+        self.requester = *sender;
+        Ok(())
+    }
+
+    // Checks that `address` is the recipient of a pending request
+    pub fn check_can_respond(&self, address: &Pubkey) -> ProgramResult {
+        if self.state != ThreadState::Pending {
+            return Err(JabError::ThreadNotPending.into());
+        }
+        // The requester is only known once the request message is sent
+        if self.msg_count == 0 || self.requester == Pubkey::default() {
+            return Err(JabError::AccountNotAuthorized.into());
+        }
+        if !self.is_participant(address) || &self.requester == address {
+            return Err(JabError::AccountNotAuthorized.into());
+        }
+        Ok(())
     }
 }
 
@@ -179,6 +312,7 @@ pub enum MessageType {
     UnencryptedText,
     EncryptedMedia,
     UnencryptedMedia,
+    // Tombstone of a message deleted from a bucket, deleted message accounts are closed instead
     Deleted,
 }
 
@@ -199,10 +333,13 @@ pub struct Message {
     pub dislikes_count: u16,
     // Message sent
     pub msg: Vec<u8>,
+    // Time of the last edit (0 if never edited), after `msg` to extend `legacy::MessageV0`
+    pub edited_at: UnixTimestamp,
 }
 
 impl Message {
     pub const SEED: &'static str = "message";
+    pub const GROUP_SEED: &'static str = "group_message";
 
     pub fn new(
         kind: MessageType,
@@ -220,6 +357,7 @@ impl Message {
             replies_to,
             likes_count: 0,
             dislikes_count: 0,
+            edited_at: 0,
         }
     }
 
@@ -262,6 +400,24 @@ impl Message {
         Pubkey::create_program_address(seeds, program_id).unwrap()
     }
 
+    // Group messages are keyed by sender so that concurrent posters do not race on a counter
+    pub fn find_group_key(
+        group_thread_key: &Pubkey,
+        sender: &Pubkey,
+        nonce: u32,
+        program_id: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                Message::GROUP_SEED.as_bytes(),
+                &group_thread_key.to_bytes(),
+                &sender.to_bytes(),
+                &nonce.to_le_bytes(),
+            ],
+            program_id,
+        )
+    }
+
     pub fn save(&self, mut dst: &mut [u8]) {
         self.serialize(&mut dst).unwrap()
     }
@@ -271,9 +427,46 @@ impl Message {
         if data[0] != Tag::Message as u8 && data[0] != Tag::Uninitialized as u8 {
             return Err(JabError::DataTypeMismatch.into());
         }
+        if MessageV0::is_legacy(data) {
+            return Err(JabError::AccountNeedsMigration.into());
+        }
         let result = Message::deserialize(&mut data)?;
         Ok(result)
     }
+
+    // Legacy messages are only migrated in memory, for instructions that never write them back
+    pub fn from_account_info_read_only(a: &AccountInfo) -> Result<Message, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::Message as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        if MessageV0::is_legacy(data) {
+            return Ok(MessageV0::deserialize(&mut data)?.migrate());
+        }
+        let result = Message::deserialize(&mut data)?;
+        Ok(result)
+    }
+
+    pub fn check_editable(&self, edit_window: i64, current_time: i64) -> ProgramResult {
+        if current_time > self.timestamp.saturating_add(edit_window) {
+            return Err(JabError::EditWindowExpired.into());
+        }
+        Ok(())
+    }
+
+    pub fn add_reaction(&mut self, kind: ReactionType) {
+        match kind {
+            ReactionType::Like => self.likes_count = self.likes_count.saturating_add(1),
+            ReactionType::Dislike => self.dislikes_count = self.dislikes_count.saturating_add(1),
+        }
+    }
+
+    pub fn remove_reaction(&mut self, kind: ReactionType) {
+        match kind {
+            ReactionType::Like => self.likes_count = self.likes_count.saturating_sub(1),
+            ReactionType::Dislike => self.dislikes_count = self.dislikes_count.saturating_sub(1),
+        }
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
@@ -285,24 +478,74 @@ pub struct GroupThread {
     pub visible: bool,
     // Owner of the group (fee exempt)
     pub owner: Pubkey,
-    // Time at which the message was sent
+    // Creator of the group, used for PDA derivation
+    pub creator: Pubkey,
+    // Owner proposed by the current owner, until accepted
+    pub pending_owner: Option<Pubkey>,
+    // Time at which the last message was sent, only updated in bucketed groups
     pub last_message_time: UnixTimestamp,
     // Destination of the fees
     pub destination_wallet: Pubkey,
-    // Message counter for PDA derivation
+    // Number of messages sent, only updated in bucketed groups (and by legacy messages which
+    // used it for PDA derivation)
     pub msg_count: u32,
+    // Number of messages that have not been deleted, messages stored in their own account are
+    // only counted for legacy groups
+    pub open_msg_count: u32,
     // Fee per message
     pub lamports_per_message: u64,
+    // Mint of the token messages are priced in, lamports are used when `None`
+    pub price_mint: Option<Pubkey>,
+    // Price per message in `price_mint` tokens
+    pub price_amount: u64,
     // Whether users can post media (images, videos and audios)
     pub media_enabled: bool,
     // Whether admins only can post messages
     pub admin_only: bool,
+    // Whether a membership account is required to post messages
+    pub members_only: bool,
+    // Whether the group can only be joined with an invite
+    pub private: bool,
+    // Time during which messages can be edited (seconds)
+    pub edit_window: i64,
     // IPFS hash of the group
     pub group_pic_hash: String,
     // Human readable group name
     pub group_name: String,
-    // Admins of the group (fee exempt)
-    pub admins: Vec<Pubkey>,
+    // Seeds of the group address, `V1` is only set by `MigrateAccount` on legacy groups
+    pub seed_version: SeedVersion,
+    // Stable identifier of the group, seeds the address of `V3` groups
+    pub group_id: [u8; 32],
+    // How messages are stored, chosen at creation
+    pub storage_mode: StorageMode,
+    // Index of the bucket new messages are appended to
+    pub current_bucket: u32,
+    // Number of `GroupMember`, `GroupAdmin`, `GroupModeration` and `Invite` accounts of the group,
+    // they are keyed by the group address and must be closed before the group
+    pub linked_account_count: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Debug, Clone, Copy)]
+pub enum StorageMode {
+    // Each message is its own account
+    Accounts,
+    // Messages are appended to shared `MessageBucket` accounts
+    Buckets,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Debug, Clone, Copy)]
+pub enum SeedVersion {
+    // Seeded with the raw group name, which must fit in 32 bytes
+    V1,
+    // Never assigned, keeps the discriminant of `V3` stable
+    Reserved,
+    // Seeded with a stable identifier chosen at creation, so that the group can be renamed
+    V3,
+}
+
+// Seed of a group name, names differing only by case or surrounding spaces share it
+pub fn group_name_seed(group_name: &str) -> [u8; 32] {
+    hashv(&[group_name.trim().to_lowercase().as_bytes()]).to_bytes()
 }
 
 impl GroupThread {
@@ -311,14 +554,19 @@ impl GroupThread {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         visible: bool,
+        group_id: [u8; 32],
         group_name: String,
         destination_wallet: Pubkey,
         lamports_per_message: u64,
+        price_mint: Option<Pubkey>,
+        price_amount: u64,
         bump: u8,
-        admins: Vec<Pubkey>,
         owner: Pubkey,
         media_enabled: bool,
         admin_only: bool,
+        members_only: bool,
+        private: bool,
+        storage_mode: StorageMode,
         current_time: i64,
     ) -> Self {
         Self {
@@ -326,36 +574,81 @@ impl GroupThread {
             visible,
             group_name,
             msg_count: 0,
+            open_msg_count: 0,
             destination_wallet,
             lamports_per_message,
+            price_mint,
+            price_amount,
             bump,
-            admins,
             owner,
+            creator: owner,
+            pending_owner: None,
             media_enabled,
             group_pic_hash: "".to_string(),
             admin_only,
+            members_only,
+            private,
+            edit_window: DEFAULT_EDIT_WINDOW,
             last_message_time: current_time,
+            seed_version: SeedVersion::V3,
+            group_id,
+            storage_mode,
+            current_bucket: 0,
+            linked_account_count: 0,
         }
     }
 
-    pub fn create_key(group_name: String, owner: Pubkey, program_id: &Pubkey, bump: u8) -> Pubkey {
+    pub fn create_key(group_id: [u8; 32], owner: Pubkey, program_id: &Pubkey, bump: u8) -> Pubkey {
         let seeds = &[
             GroupThread::SEED.as_bytes(),
-            group_name.as_bytes(),
+            &group_id,
             &owner.to_bytes(),
             &[bump],
         ];
         Pubkey::create_program_address(seeds, program_id).unwrap()
     }
 
-    pub fn find_key(group_name: String, owner: Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    pub fn find_key(group_id: [u8; 32], owner: Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[GroupThread::SEED.as_bytes(), &group_id, &owner.to_bytes()];
+        let (ama_thread_key, bump) = Pubkey::find_program_address(seeds, program_id);
+        (ama_thread_key, bump)
+    }
+
+    // `None` when the name does not fit in a seed, such a name never addressed a group
+    pub fn find_key_v1(
+        group_name: String,
+        owner: Pubkey,
+        program_id: &Pubkey,
+    ) -> Option<(Pubkey, u8)> {
         let seeds = &[
             GroupThread::SEED.as_bytes(),
             group_name.as_bytes(),
             &owner.to_bytes(),
         ];
-        let (ama_thread_key, bump) = Pubkey::find_program_address(seeds, program_id);
-        (ama_thread_key, bump)
+        Pubkey::try_find_program_address(seeds, program_id)
+    }
+
+    // Address of the group, following the seeds it was created with
+    pub fn key(&self, program_id: &Pubkey) -> Pubkey {
+        match self.seed_version {
+            SeedVersion::V1 => {
+                GroupThread::find_key_v1(self.group_name.clone(), self.creator, program_id)
+                    .map_or_else(Pubkey::default, |(key, _)| key)
+            }
+            SeedVersion::Reserved => Pubkey::default(),
+            SeedVersion::V3 => GroupThread::find_key(self.group_id, self.creator, program_id).0,
+        }
+    }
+
+    pub fn rename(&mut self, group_name: String) -> ProgramResult {
+        match self.seed_version {
+            SeedVersion::V1 | SeedVersion::Reserved => {
+                return Err(JabError::GroupNotRenamable.into())
+            }
+            SeedVersion::V3 => {}
+        }
+        self.group_name = group_name;
+        Ok(())
     }
 
     pub fn save(&self, mut dst: &mut [u8]) {
@@ -365,6 +658,19 @@ impl GroupThread {
     pub fn increment_msg_count(&mut self, current_time: i64) {
         self.last_message_time = current_time;
         self.msg_count += 1;
+        self.open_msg_count += 1;
+    }
+
+    pub fn decrement_open_msg_count(&mut self) {
+        self.open_msg_count = self.open_msg_count.saturating_sub(1);
+    }
+
+    pub fn add_linked_account(&mut self) {
+        self.linked_account_count += 1;
+    }
+
+    pub fn remove_linked_account(&mut self) {
+        self.linked_account_count = self.linked_account_count.saturating_sub(1);
     }
 
     pub fn from_account_info(a: &AccountInfo) -> Result<GroupThread, ProgramError> {
@@ -372,34 +678,32 @@ impl GroupThread {
         if data[0] != Tag::GroupThread as u8 && data[0] != Tag::Uninitialized as u8 {
             return Err(JabError::DataTypeMismatch.into());
         }
+        if GroupThreadV0::is_legacy(data) {
+            return Err(JabError::AccountNeedsMigration.into());
+        }
         let result = GroupThread::deserialize(&mut data)?;
         Ok(result)
     }
 
-    pub fn is_fee_exempt(&self, sender: Pubkey, admin_index: Option<u64>) -> bool {
-        if self.destination_wallet == sender {
-            return true;
-        }
-        if let Some(idx) = admin_index {
-            return self.admins[idx as usize] == sender;
-        }
-        false
+    pub fn requires_membership(&self) -> bool {
+        self.members_only || self.private
     }
 
-    pub fn add_admin(&mut self, admin_address: Pubkey) -> ProgramResult {
-        if self.admins.len() > MAX_ADMIN_LEN {
-            return Err(JabError::MaxAdminsReached.into());
+    // Permissions of `address` given its membership in the group
+    pub fn permissions(
+        &self,
+        address: &Pubkey,
+        admin: Option<&GroupAdmin>,
+        member: Option<&GroupMember>,
+    ) -> u8 {
+        if &self.owner == address {
+            return GroupRole::Owner.permissions();
         }
-        self.admins.push(admin_address);
-        Ok(())
-    }
-
-    pub fn remove_admin(&mut self, admin_address: Pubkey, admin_index: usize) -> ProgramResult {
-        let deleted = self.admins.remove(admin_index);
-        if admin_address != deleted {
-            return Err(JabError::InvalidAdminIndex.into());
+        let mut permissions = member.map(|m| m.permissions).unwrap_or(0);
+        if admin.is_some() {
+            permissions |= GroupRole::Admin.permissions();
         }
-        Ok(())
+        permissions
     }
 }
 
@@ -436,7 +740,7 @@ impl GroupThreadIndex {
     ) -> Pubkey {
         let seeds = &[
             GroupThreadIndex::SEED.as_bytes(),
-            group_name.as_bytes(),
+            &group_name_seed(&group_name),
             &owner.to_bytes(),
             &group_thread_key.to_bytes(),
             &[bump],
@@ -452,7 +756,7 @@ impl GroupThreadIndex {
     ) -> (Pubkey, u8) {
         let seeds = &[
             GroupThreadIndex::SEED.as_bytes(),
-            group_name.as_bytes(),
+            &group_name_seed(&group_name),
             &owner.to_bytes(),
             &group_thread_key.to_bytes(),
         ];
@@ -460,6 +764,21 @@ impl GroupThreadIndex {
         (ama_thread_key, bump)
     }
 
+    pub fn find_key_v1(
+        group_name: String,
+        group_thread_key: Pubkey,
+        owner: Pubkey,
+        program_id: &Pubkey,
+    ) -> (Pubkey, u8) {
+        let seeds = &[
+            GroupThreadIndex::SEED.as_bytes(),
+            group_name.as_bytes(),
+            &owner.to_bytes(),
+            &group_thread_key.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
     pub fn save(&self, mut dst: &mut [u8]) {
         self.serialize(&mut dst).unwrap()
     }
@@ -476,6 +795,9 @@ impl GroupThreadIndex {
 
 #[derive(BorshSerialize, BorshDeserialize, BorshSize)]
 pub struct Subscription {
+    pub tag: Tag,
+    // Version of the account layout
+    pub version: u8,
     // Pubkey of the subscriber
     pub subscriber: Pubkey,
     // Pubkey of the person the subscriber subscribed to
@@ -484,9 +806,12 @@ pub struct Subscription {
 
 impl Subscription {
     pub const SEED: &'static str = "subscription";
+    pub const VERSION: u8 = 1;
 
     pub fn new(subscriber: Pubkey, subscribed_to: Pubkey) -> Self {
         Self {
+            tag: Tag::Subscription,
+            version: Subscription::VERSION,
             subscriber,
             subscribed_to,
         }
@@ -533,3 +858,759 @@ impl Subscription {
         Ok(result)
     }
 }
+
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Debug, Clone, Copy, BorshSize)]
+pub enum ReactionType {
+    Like,
+    Dislike,
+}
+
+// One reaction per (message, reactor)
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct Reaction {
+    pub tag: Tag,
+    // Reaction type
+    pub kind: ReactionType,
+    // Message reacted to
+    pub message: Pubkey,
+    // Author of the reaction
+    pub reactor: Pubkey,
+}
+
+impl Reaction {
+    pub const SEED: &'static str = "reaction";
+
+    pub fn new(kind: ReactionType, message: Pubkey, reactor: Pubkey) -> Self {
+        Self {
+            tag: Tag::Reaction,
+            kind,
+            message,
+            reactor,
+        }
+    }
+
+    pub fn find_key(message: &Pubkey, reactor: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[
+            Reaction::SEED.as_bytes(),
+            &message.to_bytes(),
+            &reactor.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<Reaction, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::Reaction as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = Reaction::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Admin of a group
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct GroupAdmin {
+    pub tag: Tag,
+    // Group thread of the admin
+    pub group_thread: Pubkey,
+    // Admin of the group
+    pub admin: Pubkey,
+}
+
+impl GroupAdmin {
+    pub const SEED: &'static str = "group_admin";
+
+    pub fn new(group_thread: Pubkey, admin: Pubkey) -> Self {
+        Self {
+            tag: Tag::GroupAdmin,
+            group_thread,
+            admin,
+        }
+    }
+
+    pub fn find_key(group_thread: &Pubkey, admin: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[
+            GroupAdmin::SEED.as_bytes(),
+            &group_thread.to_bytes(),
+            &admin.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<GroupAdmin, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::GroupAdmin as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = GroupAdmin::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Permissions of group members (bit flags)
+pub struct GroupPermission;
+
+impl GroupPermission {
+    pub const DELETE_MESSAGES: u8 = 1;
+    pub const BAN: u8 = 1 << 1;
+    pub const EDIT_SETTINGS: u8 = 1 << 2;
+    pub const EDIT_PICTURE: u8 = 1 << 3;
+    pub const POST_ADMIN_ONLY: u8 = 1 << 4;
+    pub const FEE_EXEMPT: u8 = 1 << 5;
+    pub const INVITE: u8 = 1 << 6;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, BorshSize, PartialEq, Debug, Clone, Copy)]
+pub enum GroupRole {
+    Member,
+    Poster,
+    Moderator,
+    // Permissions of users with a `GroupAdmin` account, never stored in `GroupMember`
+    Admin,
+    Owner,
+}
+
+impl GroupRole {
+    // Default permissions of the role
+    pub fn permissions(&self) -> u8 {
+        match self {
+            GroupRole::Member => 0,
+            GroupRole::Poster => GroupPermission::POST_ADMIN_ONLY,
+            GroupRole::Moderator => {
+                GroupPermission::DELETE_MESSAGES
+                    | GroupPermission::BAN
+                    | GroupPermission::POST_ADMIN_ONLY
+                    | GroupPermission::INVITE
+            }
+            GroupRole::Admin => {
+                GroupPermission::DELETE_MESSAGES
+                    | GroupPermission::BAN
+                    | GroupPermission::EDIT_PICTURE
+                    | GroupPermission::POST_ADMIN_ONLY
+                    | GroupPermission::FEE_EXEMPT
+                    | GroupPermission::INVITE
+            }
+            GroupRole::Owner => u8::MAX,
+        }
+    }
+}
+
+// Membership of a user in a group
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct GroupMember {
+    pub tag: Tag,
+    // Group thread of the membership
+    pub group_thread: Pubkey,
+    // Member of the group
+    pub member: Pubkey,
+    // Time at which the member joined
+    pub joined_at: UnixTimestamp,
+    // Role of the member
+    pub role: GroupRole,
+    // Permissions granted to the member (`GroupPermission` flags)
+    pub permissions: u8,
+}
+
+impl GroupMember {
+    pub const SEED: &'static str = "group_member";
+
+    pub fn new(group_thread: Pubkey, member: Pubkey, joined_at: UnixTimestamp) -> Self {
+        Self {
+            tag: Tag::GroupMember,
+            group_thread,
+            member,
+            joined_at,
+            role: GroupRole::Member,
+            permissions: 0,
+        }
+    }
+
+    pub fn find_key(group_thread: &Pubkey, member: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[
+            GroupMember::SEED.as_bytes(),
+            &group_thread.to_bytes(),
+            &member.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<GroupMember, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::GroupMember as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = GroupMember::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Invite to join a group
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct Invite {
+    pub tag: Tag,
+    // Group thread of the invite
+    pub group_thread: Pubkey,
+    // Public key of the invite code, the code is the keypair shared with invitees
+    pub code_key: Pubkey,
+    // Account that created the invite
+    pub creator: Pubkey,
+    // Time after which the invite can no longer be redeemed
+    pub expires_at: UnixTimestamp,
+    // Maximum number of times the invite can be redeemed
+    pub max_uses: u32,
+    // Number of times the invite has been redeemed
+    pub uses: u32,
+}
+
+impl Invite {
+    pub const SEED: &'static str = "invite";
+
+    pub fn new(
+        group_thread: Pubkey,
+        code_key: Pubkey,
+        creator: Pubkey,
+        expires_at: UnixTimestamp,
+        max_uses: u32,
+    ) -> Self {
+        Self {
+            tag: Tag::Invite,
+            group_thread,
+            code_key,
+            creator,
+            expires_at,
+            max_uses,
+            uses: 0,
+        }
+    }
+
+    pub fn find_key(group_thread: &Pubkey, code_key: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[
+            Invite::SEED.as_bytes(),
+            &group_thread.to_bytes(),
+            &code_key.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn redeem(&mut self, now: UnixTimestamp) -> ProgramResult {
+        if now > self.expires_at {
+            return Err(JabError::InviteExpired.into());
+        }
+        if self.uses >= self.max_uses {
+            return Err(JabError::InviteExhausted.into());
+        }
+        self.uses += 1;
+        Ok(())
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<Invite, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::Invite as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = Invite::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Moderation status of a user in a group
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct GroupModeration {
+    pub tag: Tag,
+    // Group thread of the moderation
+    pub group_thread: Pubkey,
+    // Moderated user
+    pub user: Pubkey,
+    // Whether the user is banned from the group
+    pub banned: bool,
+    // Time until which the user cannot post messages
+    pub muted_until: UnixTimestamp,
+    // Account that paid for the moderation account, refunded when it is closed
+    pub payer: Pubkey,
+}
+
+impl GroupModeration {
+    pub const SEED: &'static str = "group_moderation";
+
+    pub fn new(group_thread: Pubkey, user: Pubkey, payer: Pubkey) -> Self {
+        Self {
+            tag: Tag::GroupModeration,
+            group_thread,
+            user,
+            banned: false,
+            muted_until: 0,
+            payer,
+        }
+    }
+
+    pub fn find_key(group_thread: &Pubkey, user: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[
+            GroupModeration::SEED.as_bytes(),
+            &group_thread.to_bytes(),
+            &user.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn check_can_join(&self) -> ProgramResult {
+        if self.banned {
+            return Err(JabError::MemberBanned.into());
+        }
+        Ok(())
+    }
+
+    pub fn check_can_post(&self, now: UnixTimestamp) -> ProgramResult {
+        self.check_can_join()?;
+        if now < self.muted_until {
+            return Err(JabError::MemberMuted.into());
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<GroupModeration, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::GroupModeration as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = GroupModeration::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Block of a user by another user for direct messages
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct Block {
+    pub tag: Tag,
+    // User who blocked
+    pub blocker: Pubkey,
+    // User who is blocked
+    pub blocked: Pubkey,
+}
+
+impl Block {
+    pub const SEED: &'static str = "block";
+
+    pub fn new(blocker: Pubkey, blocked: Pubkey) -> Self {
+        Self {
+            tag: Tag::Block,
+            blocker,
+            blocked,
+        }
+    }
+
+    pub fn find_key(blocker: &Pubkey, blocked: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[
+            Block::SEED.as_bytes(),
+            &blocker.to_bytes(),
+            &blocked.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<Block, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::Block as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = Block::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Contact of a user, messages from contacts are free
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct Contact {
+    pub tag: Tag,
+    // Owner of the contact list
+    pub owner: Pubkey,
+    // Contact of the owner
+    pub contact: Pubkey,
+    // Whether the contact also has the owner as a contact
+    pub mutual: bool,
+}
+
+impl Contact {
+    pub const SEED: &'static str = "contact";
+
+    pub fn new(owner: Pubkey, contact: Pubkey, mutual: bool) -> Self {
+        Self {
+            tag: Tag::Contact,
+            owner,
+            contact,
+            mutual,
+        }
+    }
+
+    pub fn find_key(owner: &Pubkey, contact: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[
+            Contact::SEED.as_bytes(),
+            &owner.to_bytes(),
+            &contact.to_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<Contact, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::Contact as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = Contact::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Payment of a message held until the receiver replies
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct Escrow {
+    pub tag: Tag,
+    // Message paid for
+    pub message: Pubkey,
+    // Sender of the message, refunded after the deadline
+    pub sender: Pubkey,
+    // Receiver of the message, paid when replying
+    pub receiver: Pubkey,
+    // Lamports held on top of the rent of the account, the protocol fee is not included
+    // as it is charged when the message is sent and not refunded
+    pub amount: u64,
+    // Time after which the sender can be refunded
+    pub deadline: UnixTimestamp,
+}
+
+impl Escrow {
+    pub const SEED: &'static str = "escrow";
+
+    pub fn new(
+        message: Pubkey,
+        sender: Pubkey,
+        receiver: Pubkey,
+        amount: u64,
+        deadline: UnixTimestamp,
+    ) -> Self {
+        Self {
+            tag: Tag::Escrow,
+            message,
+            sender,
+            receiver,
+            amount,
+            deadline,
+        }
+    }
+
+    pub fn find_key(message: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[Escrow::SEED.as_bytes(), &message.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<Escrow, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::Escrow as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = Escrow::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Program wide settings
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct Config {
+    pub tag: Tag,
+    // Authority allowed to update the config
+    pub admin: Pubkey,
+    // Protocol fee taken on message payments and tips (basis points)
+    pub fee_bps: u16,
+    // Program derived account receiving the protocol fees, see `Config::find_vault_key`
+    pub vault: Pubkey,
+    pub vault_bump: u8,
+    // Authority allowed to withdraw the protocol fees
+    pub fee_authority: Pubkey,
+    // Share of the protocol fee paid to referrers (basis points)
+    pub referrer_share_bps: u16,
+    // Whether messages and tips are disabled
+    pub paused: bool,
+}
+
+impl Config {
+    pub const SEED: &'static str = "config";
+    pub const VAULT_SEED: &'static str = "vault";
+
+    pub fn new(
+        admin: Pubkey,
+        fee_bps: u16,
+        vault: Pubkey,
+        vault_bump: u8,
+        fee_authority: Pubkey,
+        referrer_share_bps: u16,
+    ) -> Self {
+        Self {
+            tag: Tag::Config,
+            admin,
+            fee_bps,
+            vault,
+            vault_bump,
+            fee_authority,
+            referrer_share_bps,
+            paused: false,
+        }
+    }
+
+    pub fn find_key(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Config::SEED.as_bytes()], program_id)
+    }
+
+    // The vault is a system account holding SOL fees, and the owner of the token fee accounts.
+    // Its associated token account of a mint is created with `UpdateConfig` before the mint
+    // can be used for payments
+    pub fn find_vault_key(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[Config::VAULT_SEED.as_bytes()], program_id)
+    }
+
+    pub fn fee(&self, amount: u64) -> u64 {
+        ((amount as u128 * self.fee_bps as u128) / 10_000) as u64
+    }
+
+    pub fn referrer_share(&self, fee: u64) -> u64 {
+        ((fee as u128 * self.referrer_share_bps as u128) / 10_000) as u64
+    }
+
+    pub fn check_not_paused(&self) -> ProgramResult {
+        if self.paused {
+            return Err(JabError::ProgramPaused.into());
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<Config, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::Config as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = Config::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Referral statistics of an integrator
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct Referrer {
+    pub tag: Tag,
+    // Wallet receiving the referral fees
+    pub wallet: Pubkey,
+    // Number of payments referred
+    pub referral_count: u64,
+    // Lamports earned, token payments are only counted in `referral_count` and logged with
+    // the `ReferralPaid` event
+    pub lamports_earned: u64,
+}
+
+impl Referrer {
+    pub const SEED: &'static str = "referrer";
+
+    pub fn new(wallet: Pubkey) -> Self {
+        Self {
+            tag: Tag::Referrer,
+            wallet,
+            referral_count: 0,
+            lamports_earned: 0,
+        }
+    }
+
+    pub fn find_key(wallet: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[Referrer::SEED.as_bytes(), &wallet.to_bytes()];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    pub fn save(&self, mut dst: &mut [u8]) {
+        self.serialize(&mut dst).unwrap()
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<Referrer, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::Referrer as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = Referrer::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+// Message stored in a `MessageBucket`
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct BucketMessage {
+    // Message type, `Deleted` for tombstones
+    pub kind: MessageType,
+    // Time at which the message was sent
+    pub timestamp: UnixTimestamp,
+    // Sender of the message
+    pub sender: Pubkey,
+    // If the message is a response to another message
+    pub replies_to: Pubkey,
+    // Message sent, emptied on deletion
+    pub msg: Vec<u8>,
+}
+
+impl BucketMessage {
+    pub fn new(
+        kind: MessageType,
+        timestamp: UnixTimestamp,
+        msg: Vec<u8>,
+        sender: Pubkey,
+        replies_to: Pubkey,
+    ) -> Self {
+        Self {
+            kind,
+            timestamp,
+            sender,
+            replies_to,
+            msg,
+        }
+    }
+}
+
+// Fixed size account holding several messages of a group
+#[derive(BorshSerialize, BorshDeserialize, BorshSize)]
+pub struct MessageBucket {
+    pub tag: Tag,
+    // Group thread the messages were sent to
+    pub group_thread: Pubkey,
+    // Account that paid the rent of the bucket, refunded on closure
+    pub payer: Pubkey,
+    // Number of messages that have not been deleted
+    pub live_count: u16,
+    // Messages in sending order, deleted ones are kept so that slots do not shift
+    pub messages: Vec<BucketMessage>,
+}
+
+impl MessageBucket {
+    pub const SEED: &'static str = "message_bucket";
+
+    pub fn new(group_thread: Pubkey, payer: Pubkey) -> Self {
+        Self {
+            tag: Tag::MessageBucket,
+            group_thread,
+            payer,
+            live_count: 0,
+            messages: vec![],
+        }
+    }
+
+    pub fn find_key(group_thread: &Pubkey, index: u32, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[
+            MessageBucket::SEED.as_bytes(),
+            &group_thread.to_bytes(),
+            &index.to_le_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, program_id)
+    }
+
+    // Returns the slot of the message, or `None` when it does not fit in the bucket
+    pub fn push(&mut self, message: BucketMessage) -> Option<u16> {
+        if self.borsh_len() + message.borsh_len() > MESSAGE_BUCKET_LEN {
+            return None;
+        }
+        self.messages.push(message);
+        self.live_count += 1;
+        Some((self.messages.len() - 1) as u16)
+    }
+
+    // Turns the message into a tombstone so that later slots do not shift. Slots are never
+    // reused, but the freed content bytes are available to `push` while the bucket is current
+    pub fn delete(&mut self, slot: u16) -> Result<BucketMessage, ProgramError> {
+        let message = self
+            .messages
+            .get_mut(slot as usize)
+            .ok_or(ProgramError::InvalidArgument)?;
+        if message.kind == MessageType::Deleted {
+            return Err(JabError::MessageDeleted.into());
+        }
+        let deleted = BucketMessage::new(
+            MessageType::Deleted,
+            message.timestamp,
+            vec![],
+            message.sender,
+            message.replies_to,
+        );
+        self.live_count -= 1;
+        Ok(std::mem::replace(message, deleted))
+    }
+
+    // The bucket shrinks when messages are deleted, the bytes past it are zeroed so that deleted
+    // contents do not remain in the account
+    pub fn save(&self, dst: &mut [u8]) {
+        let len = self.borsh_len();
+        self.serialize(&mut &mut dst[..]).unwrap();
+        dst[len..].fill(0);
+    }
+
+    pub fn from_account_info(a: &AccountInfo) -> Result<MessageBucket, ProgramError> {
+        let mut data = &a.data.borrow() as &[u8];
+        if data[0] != Tag::MessageBucket as u8 && data[0] != Tag::Uninitialized as u8 {
+            return Err(JabError::DataTypeMismatch.into());
+        }
+        let result = MessageBucket::deserialize(&mut data)?;
+        Ok(result)
+    }
+}
+
+#[test]
+fn test_message_bucket_save() {
+    let mut message_bucket = MessageBucket::new(Pubkey::default(), Pubkey::default());
+    let message = BucketMessage::new(
+        MessageType::UnencryptedText,
+        0,
+        vec![0xab; 32],
+        Pubkey::default(),
+        Pubkey::default(),
+    );
+    assert_eq!(message_bucket.push(message), Some(0));
+
+    let mut data = vec![0; MESSAGE_BUCKET_LEN];
+    message_bucket.save(&mut data);
+    assert!(data.contains(&0xab));
+
+    message_bucket.delete(0).unwrap();
+    message_bucket.save(&mut data);
+    assert!(!data.contains(&0xab));
+
+    let saved = MessageBucket::deserialize(&mut &data[..]).unwrap();
+    assert_eq!(saved.live_count, 0);
+    assert!(saved.messages[0].kind == MessageType::Deleted);
+}
@@ -1,17 +1,29 @@
+use bonfida_utils::BorshSize;
+use borsh::BorshDeserialize;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey,
+    account_info::AccountInfo,
+    bpf_loader_upgradeable,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
     pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::{create_account, transfer},
+    system_program,
+    sysvar::Sysvar,
 };
+use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
 
 use crate::error::JabError;
+use crate::events::ReferralPaid;
 use crate::state::{
-    GroupThread, MessageType, MAX_ADMIN_LEN, MAX_BIO_LENGTH, MAX_GROUP_NAME_LEN, MAX_HASH_LEN,
-    MAX_NAME_LENGTH,
+    BucketMessage, Config, GroupAdmin, GroupMember, GroupModeration, GroupThread, Message,
+    MessageBucket, MessageType, Profile, Referrer, MAX_BIO_LENGTH, MAX_ESCROW_PERIOD,
+    MAX_GROUP_NAME_LEN, MAX_HASH_LEN, MAX_NAME_LENGTH, MAX_PROFILE_LEN, MESSAGE_BUCKET_LEN,
 };
-use std::cmp::Ordering::Less;
-
-pub const SOL_VAULT: Pubkey = pubkey!("GcWEQ9K78FV7LEHteFVciYApERk5YvQuFDQPk1yYJVXi");
-pub const FEE: u64 = 1;
+use std::cmp::Ordering::{Equal, Greater, Less};
 
 // Safety verification functions
 pub fn check_account_key(
@@ -71,15 +83,11 @@ pub fn order_keys(key_1: &Pubkey, key_2: &Pubkey) -> (Pubkey, Pubkey) {
     (*key_2, *key_1)
 }
 
-pub fn check_group_thread_params(group_name: &str, admins: &[Pubkey]) -> ProgramResult {
+pub fn check_group_thread_params(group_name: &str) -> ProgramResult {
     if group_name.len() > MAX_GROUP_NAME_LEN {
         msg!("Group name is too long - max is {}", MAX_GROUP_NAME_LEN);
         return Err(ProgramError::InvalidArgument);
     }
-    if admins.len() > MAX_ADMIN_LEN {
-        msg!("Too many admins - max is {}", MAX_ADMIN_LEN);
-        return Err(ProgramError::InvalidArgument);
-    }
 
     Ok(())
 }
@@ -91,26 +99,463 @@ pub fn check_group_message_type(
     match (group_thread.media_enabled, message_type) {
         (false, MessageType::EncryptedMedia) => Err(JabError::NonSupportedMessageType.into()),
         (false, MessageType::UnencryptedMedia) => Err(JabError::NonSupportedMessageType.into()),
+        (_, MessageType::Deleted) => Err(JabError::NonSupportedMessageType.into()),
         _ => Ok(()),
     }
 }
 
-pub fn check_admin_only(
+// Returns `None` when the user is not a member of the group
+pub fn load_group_member(
+    account: &AccountInfo,
+    group_thread: &Pubkey,
+    user: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<GroupMember>, ProgramError> {
+    let (key, _) = GroupMember::find_key(group_thread, user, program_id);
+    check_account_key(account, &key, JabError::AccountNotDeterministic)?;
+    if account.data_is_empty() {
+        return Ok(None);
+    }
+    check_account_owner(account, program_id, JabError::WrongOwner)?;
+    Ok(Some(GroupMember::from_account_info(account)?))
+}
+
+// Returns `None` when the user is not an admin of the group
+pub fn load_group_admin(
+    account: &AccountInfo,
+    group_thread: &Pubkey,
+    user: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<GroupAdmin>, ProgramError> {
+    let (key, _) = GroupAdmin::find_key(group_thread, user, program_id);
+    check_account_key(account, &key, JabError::AccountNotDeterministic)?;
+    if account.data_is_empty() {
+        return Ok(None);
+    }
+    check_account_owner(account, program_id, JabError::WrongOwner)?;
+    Ok(Some(GroupAdmin::from_account_info(account)?))
+}
+
+pub fn check_group_permission(
     group_thread: &GroupThread,
     address: &Pubkey,
-    admin_index: Option<u64>,
+    admin: Option<&GroupAdmin>,
+    member: Option<&GroupMember>,
+    permission: u8,
+) -> ProgramResult {
+    if group_thread.permissions(address, admin, member) & permission != permission {
+        return Err(JabError::AccountNotAuthorized.into());
+    }
+    Ok(())
+}
+
+// Moderators can only act on users whose permissions are a strict subset of theirs
+pub fn check_outranks(moderator_permissions: u8, user_permissions: u8) -> ProgramResult {
+    if user_permissions & !moderator_permissions != 0 || user_permissions == moderator_permissions {
+        return Err(JabError::AccountNotAuthorized.into());
+    }
+    Ok(())
+}
+
+// Messages sent before sender nonces are keyed by the group message counter, returns whether the
+// message uses that legacy key
+pub fn check_group_message_key(
+    account: &AccountInfo,
+    group_thread: &Pubkey,
+    sender: &Pubkey,
+    nonce: u32,
+    program_id: &Pubkey,
+) -> Result<bool, ProgramError> {
+    let (key, _) = Message::find_group_key(group_thread, sender, nonce, program_id);
+    if *account.key == key {
+        return Ok(false);
+    }
+    let (legacy_key, _) = Message::find_key(nonce, group_thread, group_thread, program_id);
+    check_account_key(account, &legacy_key, JabError::AccountNotDeterministic)?;
+    Ok(true)
+}
+
+// Returns `None` when the user has never been moderated in the group
+pub fn load_group_moderation(
+    account: &AccountInfo,
+    group_thread: &Pubkey,
+    user: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Option<GroupModeration>, ProgramError> {
+    let (key, _) = GroupModeration::find_key(group_thread, user, program_id);
+    check_account_key(account, &key, JabError::AccountNotDeterministic)?;
+    if account.data_is_empty() {
+        return Ok(None);
+    }
+    check_account_owner(account, program_id, JabError::WrongOwner)?;
+    Ok(Some(GroupModeration::from_account_info(account)?))
+}
+
+// Creates the moderation account of `user` when it is empty, `payer` pays for it
+pub fn load_or_create_group_moderation<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    group_thread: &Pubkey,
+    user: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<GroupModeration, ProgramError> {
+    let (key, bump) = GroupModeration::find_key(group_thread, user, program_id);
+    check_account_key(account, &key, JabError::AccountNotDeterministic)?;
+    if !account.data_is_empty() {
+        check_account_owner(account, program_id, JabError::WrongOwner)?;
+        return GroupModeration::from_account_info(account);
+    }
+
+    let group_moderation = GroupModeration::new(*group_thread, *user, *payer.key);
+    let space = group_moderation.borsh_len();
+    let lamports = Rent::get()?.minimum_balance(space);
+    invoke_signed(
+        &create_account(payer.key, &key, lamports, space as u64, program_id),
+        &[system_program.clone(), payer.clone(), account.clone()],
+        &[&[
+            GroupModeration::SEED.as_bytes(),
+            &group_thread.to_bytes(),
+            &user.to_bytes(),
+            &[bump],
+        ]],
+    )?;
+    Ok(group_moderation)
+}
+
+// Load the referral statistics of a referrer
+pub fn load_referrer(account: &AccountInfo, program_id: &Pubkey) -> Result<Referrer, ProgramError> {
+    check_account_owner(account, program_id, JabError::WrongOwner)?;
+    let referrer = Referrer::from_account_info(account)?;
+    let (referrer_key, _) = Referrer::find_key(&referrer.wallet, program_id);
+    check_account_key(account, &referrer_key, JabError::AccountNotDeterministic)?;
+    Ok(referrer)
+}
+
+// Pay the protocol fee in lamports, the referrer gets its share when given as
+// (wallet, referral statistics) accounts
+pub fn transfer_fee<'a>(
+    program_id: &Pubkey,
+    config: &Config,
+    system_program: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    sol_vault: &AccountInfo<'a>,
+    referrer: Option<(&AccountInfo<'a>, &AccountInfo<'a>)>,
+    fee: u64,
+) -> ProgramResult {
+    let mut vault_fee = fee;
+
+    if let Some((referrer_wallet, referrer_account)) = referrer {
+        let mut referrer = load_referrer(referrer_account, program_id)?;
+        check_account_key(referrer_wallet, &referrer.wallet, JabError::WrongReferrer)?;
+        // Payers cannot refer themselves to get a discount
+        if referrer.wallet == *payer.key {
+            return Err(JabError::WrongReferrer.into());
+        }
+
+        let share = config.referrer_share(fee);
+        vault_fee -= share;
+        invoke(
+            &transfer(payer.key, referrer_wallet.key, share),
+            &[
+                system_program.clone(),
+                payer.clone(),
+                referrer_wallet.clone(),
+            ],
+        )?;
+
+        referrer.referral_count += 1;
+        referrer.lamports_earned += share;
+        referrer.save(&mut referrer_account.data.borrow_mut());
+
+        ReferralPaid {
+            referrer: referrer.wallet,
+            payer: *payer.key,
+            mint: None,
+            amount: share,
+        }
+        .emit();
+    }
+
+    invoke(
+        &transfer(payer.key, sol_vault.key, vault_fee),
+        &[system_program.clone(), payer.clone(), sol_vault.clone()],
+    )
+}
+
+// Append a message to the current bucket of the group, passing the next bucket
+// starts it. Returns the slot of the message in the bucket.
+pub fn append_bucket_message<'a>(
+    program_id: &Pubkey,
+    group_thread: &mut GroupThread,
+    group_thread_key: &Pubkey,
+    system_program: &AccountInfo<'a>,
+    sender: &AccountInfo<'a>,
+    bucket_account: &AccountInfo<'a>,
+    message: BucketMessage,
+) -> Result<u16, ProgramError> {
+    let mut index = group_thread.current_bucket;
+    let mut bucket_key = MessageBucket::find_key(group_thread_key, index, program_id);
+    if *bucket_account.key != bucket_key.0 {
+        index += 1;
+        bucket_key = MessageBucket::find_key(group_thread_key, index, program_id);
+        check_account_key(
+            bucket_account,
+            &bucket_key.0,
+            JabError::AccountNotDeterministic,
+        )?;
+    }
+    let (bucket_key, bump) = bucket_key;
+
+    // Buckets are closed once all their messages are deleted, the current one may be recreated
+    let mut bucket = if bucket_account.data_is_empty() {
+        let lamports = Rent::get()?.minimum_balance(MESSAGE_BUCKET_LEN);
+        invoke_signed(
+            &create_account(
+                sender.key,
+                &bucket_key,
+                lamports,
+                MESSAGE_BUCKET_LEN as u64,
+                program_id,
+            ),
+            &[
+                system_program.clone(),
+                sender.clone(),
+                bucket_account.clone(),
+            ],
+            &[&[
+                MessageBucket::SEED.as_bytes(),
+                &group_thread_key.to_bytes(),
+                &index.to_le_bytes(),
+                &[bump],
+            ]],
+        )?;
+        MessageBucket::new(*group_thread_key, *sender.key)
+    } else {
+        check_account_owner(bucket_account, program_id, JabError::WrongOwner)?;
+        MessageBucket::from_account_info(bucket_account)?
+    };
+
+    let slot = bucket.push(message).ok_or(JabError::BucketFull)?;
+    bucket.save(&mut bucket_account.data.borrow_mut());
+    group_thread.current_bucket = index;
+
+    Ok(slot)
+}
+
+fn transfer_tokens<'a>(
+    spl_token_program: &AccountInfo<'a>,
+    owner: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::ID,
+        source.key,
+        destination.key,
+        owner.key,
+        &[],
+        amount,
+    )?;
+    invoke(
+        &transfer_ix,
+        &[
+            spl_token_program.clone(),
+            source.clone(),
+            destination.clone(),
+            owner.clone(),
+        ],
+    )
+}
+
+// Pay `amount` tokens of `mint` to `destination_owner`, the protocol fee goes to the vault
+// associated token account, minus the share of the referrer when given as
+// (token account, referral statistics) accounts. The vault token account is created by the
+// admin with `UpdateConfig`, payments in `mint` are rejected until it exists
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_token_payment<'a>(
+    program_id: &Pubkey,
+    config: &Config,
+    spl_token_program: &AccountInfo<'a>,
+    owner: &AccountInfo<'a>,
+    token_source: &AccountInfo<'a>,
+    token_destination: &AccountInfo<'a>,
+    vault_token_account: &AccountInfo<'a>,
+    referrer: Option<(&AccountInfo<'a>, &AccountInfo<'a>)>,
+    mint: &Pubkey,
+    destination_owner: &Pubkey,
+    amount: u64,
 ) -> ProgramResult {
-    if !group_thread.admin_only || &group_thread.owner == address {
+    check_account_key(spl_token_program, &spl_token::ID, JabError::WrongSplId)?;
+    check_account_key(
+        vault_token_account,
+        &get_associated_token_address(&config.vault, mint),
+        JabError::WrongTokenAccount,
+    )?;
+    check_account_owner(
+        vault_token_account,
+        &spl_token::ID,
+        JabError::MissingVaultTokenAccount,
+    )?;
+    check_account_owner(token_destination, &spl_token::ID, JabError::WrongOwner)?;
+
+    let destination = spl_token::state::Account::unpack(&token_destination.data.borrow())?;
+    if &destination.mint != mint || &destination.owner != destination_owner {
+        return Err(JabError::WrongTokenAccount.into());
+    }
+
+    let fee = config.fee(amount);
+    let mut vault_fee = fee;
+
+    if let Some((referrer_token_account, referrer_account)) = referrer {
+        let mut referrer = load_referrer(referrer_account, program_id)?;
+        if referrer.wallet == *owner.key {
+            return Err(JabError::WrongReferrer.into());
+        }
+        check_account_owner(referrer_token_account, &spl_token::ID, JabError::WrongOwner)?;
+        let referrer_destination =
+            spl_token::state::Account::unpack(&referrer_token_account.data.borrow())?;
+        if &referrer_destination.mint != mint || referrer_destination.owner != referrer.wallet {
+            return Err(JabError::WrongReferrer.into());
+        }
+
+        let share = config.referrer_share(fee);
+        vault_fee -= share;
+        transfer_tokens(
+            spl_token_program,
+            owner,
+            token_source,
+            referrer_token_account,
+            share,
+        )?;
+
+        referrer.referral_count += 1;
+        referrer.save(&mut referrer_account.data.borrow_mut());
+
+        // Token amounts of different mints cannot be added up in the referrer account
+        ReferralPaid {
+            referrer: referrer.wallet,
+            payer: *owner.key,
+            mint: Some(*mint),
+            amount: share,
+        }
+        .emit();
+    }
+
+    transfer_tokens(
+        spl_token_program,
+        owner,
+        token_source,
+        token_destination,
+        amount - fee,
+    )?;
+    transfer_tokens(
+        spl_token_program,
+        owner,
+        token_source,
+        vault_token_account,
+        vault_fee,
+    )
+}
+
+// Create the vault associated token account of `mint`, does nothing if it already exists
+#[allow(clippy::too_many_arguments)]
+pub fn create_vault_token_account<'a>(
+    config: &Config,
+    payer: &AccountInfo<'a>,
+    vault: &AccountInfo<'a>,
+    vault_token_account: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    spl_token_program: &AccountInfo<'a>,
+    associated_token_program: &AccountInfo<'a>,
+    rent_sysvar: &AccountInfo<'a>,
+) -> ProgramResult {
+    check_account_key(vault, &config.vault, JabError::AccountNotDeterministic)?;
+    check_account_key(
+        vault_token_account,
+        &get_associated_token_address(&config.vault, mint.key),
+        JabError::WrongTokenAccount,
+    )?;
+    check_account_key(
+        system_program,
+        &system_program::ID,
+        JabError::WrongSystemProgramAccount,
+    )?;
+    check_account_key(spl_token_program, &spl_token::ID, JabError::WrongSplId)?;
+    check_account_key(
+        associated_token_program,
+        &spl_associated_token_account::ID,
+        JabError::WrongSplId,
+    )?;
+
+    if !vault_token_account.data_is_empty() {
         return Ok(());
     }
-    let admin_index = admin_index.unwrap() as usize;
-    let is_admin = group_thread.admins.get(admin_index).unwrap() == address;
-    if !is_admin {
-        return Err(JabError::ChatMuted.into());
+
+    invoke(
+        &create_associated_token_account(payer.key, &config.vault, mint.key),
+        &[
+            associated_token_program.clone(),
+            payer.clone(),
+            vault_token_account.clone(),
+            vault.clone(),
+            mint.clone(),
+            system_program.clone(),
+            spl_token_program.clone(),
+            rent_sysvar.clone(),
+        ],
+    )
+}
+
+// Header of the upgradeable loader `ProgramData` account, its bincode encoding has the same
+// layout as borsh
+#[derive(BorshDeserialize)]
+struct ProgramDataHeader {
+    variant: u32,
+    _slot: u64,
+    upgrade_authority: Option<Pubkey>,
+}
+
+const PROGRAM_DATA_VARIANT: u32 = 3;
+
+// Check that `authority` is the upgrade authority of the program
+pub fn check_upgrade_authority(
+    program_data: &AccountInfo,
+    authority: &AccountInfo,
+    program_id: &Pubkey,
+) -> ProgramResult {
+    let (program_data_key, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID);
+    check_account_key(
+        program_data,
+        &program_data_key,
+        JabError::AccountNotDeterministic,
+    )?;
+    check_account_owner(
+        program_data,
+        &bpf_loader_upgradeable::ID,
+        JabError::WrongOwner,
+    )?;
+
+    let header = ProgramDataHeader::deserialize(&mut &program_data.data.borrow()[..])?;
+    if header.variant != PROGRAM_DATA_VARIANT || header.upgrade_authority != Some(*authority.key) {
+        return Err(JabError::AccountNotAuthorized.into());
     }
     Ok(())
 }
 
+// Load the program config, rejecting instructions while the program is paused
+pub fn load_config(account: &AccountInfo, program_id: &Pubkey) -> Result<Config, ProgramError> {
+    let (config_key, _) = Config::find_key(program_id);
+    check_account_key(account, &config_key, JabError::AccountNotDeterministic)?;
+    check_account_owner(account, program_id, JabError::WrongOwner)?;
+    let config = Config::from_account_info(account)?;
+    config.check_not_paused()?;
+    Ok(config)
+}
+
 pub fn check_hash_len(hash: &str) -> ProgramResult {
     if hash.len() > MAX_HASH_LEN {
         return Err(JabError::InvalidHashLength.into());
@@ -126,6 +571,89 @@ pub fn check_keys(key_1: &Pubkey, key_2: &Pubkey) -> ProgramResult {
     Ok(())
 }
 
+// Wipe the data of a program owned account and hand it back to the system program
+pub fn close_account(account: &AccountInfo, target: &AccountInfo) -> ProgramResult {
+    account.data.borrow_mut().fill(0);
+    account.realloc(0, false)?;
+    account.assign(&system_program::ID);
+
+    let mut account_lamports = account.lamports.borrow_mut();
+    let mut target_lamports = target.lamports.borrow_mut();
+
+    **target_lamports += **account_lamports;
+    **account_lamports = 0;
+
+    Ok(())
+}
+
+// Resize a program owned account, the payer tops up or gets refunded the rent difference
+pub fn resize_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    new_len: usize,
+) -> ProgramResult {
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let current_lamports = account.lamports();
+
+    match required_lamports.cmp(&current_lamports) {
+        Greater => {
+            invoke(
+                &transfer(payer.key, account.key, required_lamports - current_lamports),
+                &[system_program.clone(), payer.clone(), account.clone()],
+            )?;
+        }
+        Less => {
+            let mut account_lamports = account.lamports.borrow_mut();
+            let mut payer_lamports = payer.lamports.borrow_mut();
+            **payer_lamports += current_lamports - required_lamports;
+            **account_lamports = required_lamports;
+        }
+        Equal => {}
+    }
+
+    account.realloc(new_len, false)
+}
+
+// Profiles created with the initial layout are grown to `MAX_PROFILE_LEN` when written
+pub fn save_profile<'a>(
+    profile: &Profile,
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    if account.data_len() < MAX_PROFILE_LEN {
+        resize_account(account, payer, system_program, MAX_PROFILE_LEN)?;
+    }
+    profile.save(&mut account.data.borrow_mut());
+    Ok(())
+}
+
+pub fn check_edit_window(edit_window: Option<i64>) -> Result<(), JabError> {
+    if edit_window.unwrap_or_default() < 0 {
+        return Err(JabError::InvalidEditWindow);
+    }
+    Ok(())
+}
+
+// Token payments are transferred directly, only lamport payments can be held in escrow
+pub fn check_escrow_settings(
+    escrow_enabled: bool,
+    escrow_period: i64,
+    price_mint: Option<Pubkey>,
+) -> Result<(), JabError> {
+    if !escrow_enabled {
+        return Ok(());
+    }
+    if price_mint.is_some() {
+        return Err(JabError::EscrowNotSupported);
+    }
+    if escrow_period <= 0 || escrow_period > MAX_ESCROW_PERIOD {
+        return Err(JabError::InvalidEscrowPeriod);
+    }
+    Ok(())
+}
+
 pub fn check_names(name_1: &str, name_2: &str) -> ProgramResult {
     if name_1 != name_2 {
         msg!("+ names are not the same");
@@ -1,14 +1,32 @@
+use borsh::{BorshDeserialize, BorshSerialize};
 use jab::entrypoint::process_instruction;
 use jab::instruction::{
-    add_admin_to_group, create_group_index, create_group_thread, create_profile,
-    create_subscription, create_thread, delete_group_message, delete_message, edit_group_thread,
-    remove_admin_from_group, send_message, send_message_group, send_tip, set_user_profile,
+    accept_group_owner, accept_thread, add_admin_to_group, add_contact, ban_member, block_user,
+    close_group_index, close_group_thread, close_profile, close_thread, create_group_index,
+    create_group_thread, create_invite, create_profile, create_subscription, create_thread,
+    delete_bucket_message, delete_group_message, delete_message, delete_subscription,
+    edit_group_message, edit_group_thread, edit_message, grant_group_permissions, init_config,
+    join_group, leave_group, migrate_account, mute_member, propose_group_owner, react_to_message,
+    redeem_invite, refund_escrow, register_referrer, remove_admin_from_group, remove_contact,
+    rename_group, revoke_group_permissions, revoke_invite, send_message, send_message_group,
+    send_tip, set_user_profile, unban_member, unblock_user, update_config, withdraw_fees,
+};
+use jab::legacy::{
+    GroupThreadV0, MessageV0, ProfileV0, SubscriptionV0, ThreadV0, GROUP_THREAD_V0_LEN,
+    PROFILE_V0_LEN,
+};
+use jab::state::{
+    Block, Config, Contact, Escrow, GroupAdmin, GroupMember, GroupModeration, GroupPermission,
+    GroupRole, GroupThread, GroupThreadIndex, Invite, MessageBucket, MessageType, Reaction,
+    ReactionType, Referrer, SeedVersion, StorageMode, Subscription, Tag,
+};
+use jab::state::{Message, Profile, Thread, ThreadState, MAX_ESCROW_PERIOD, MAX_PROFILE_LEN};
+use solana_program::{
+    bpf_loader_upgradeable, clock::Clock, instruction::AccountMeta, pubkey::Pubkey, rent::Rent,
+    system_instruction, system_program,
 };
-use jab::state::{GroupThread, GroupThreadIndex, MessageType, Subscription};
-use jab::state::{Message, Profile, Thread};
-use jab::utils::SOL_VAULT;
-use solana_program::{pubkey::Pubkey, rent::Rent, system_instruction, system_program};
 use solana_program_test::{processor, ProgramTest};
+use solana_sdk::account::Account;
 use solana_sdk::signature::Keypair;
 use solana_sdk::signature::Signer;
 use spl_associated_token_account::{create_associated_token_account, get_associated_token_address};
@@ -23,11 +41,300 @@ async fn test_jab() {
     // Create program and test environment
     let jab_program_id = Pubkey::new_unique();
 
-    let program_test = ProgramTest::new("jab", jab_program_id, processor!(process_instruction));
+    let mut program_test = ProgramTest::new("jab", jab_program_id, processor!(process_instruction));
+
+    // Thread created by the initial program version
+    let (legacy_user_1, legacy_user_2) = (Keypair::new(), Pubkey::new_unique());
+    let (legacy_thread, legacy_bump) =
+        Thread::find_key(&legacy_user_1.pubkey(), &legacy_user_2, &jab_program_id);
+    let legacy_data = ThreadV0 {
+        tag: Tag::Thread,
+        msg_count: 3,
+        user_1: legacy_user_1.pubkey(),
+        user_2: legacy_user_2,
+        last_message_time: 0,
+        bump: legacy_bump,
+    }
+    .try_to_vec()
+    .unwrap();
+    program_test.add_account(
+        legacy_thread,
+        Account {
+            lamports: Rent::default().minimum_balance(legacy_data.len()),
+            data: legacy_data,
+            owner: jab_program_id,
+            ..Account::default()
+        },
+    );
+
+    // Group created by the initial program version, seeded with its raw name
+    let (legacy_group, legacy_group_bump) = GroupThread::find_key_v1(
+        "Legacy".to_string(),
+        legacy_user_1.pubkey(),
+        &jab_program_id,
+    )
+    .unwrap();
+    let legacy_data = GroupThreadV0 {
+        tag: Tag::GroupThread,
+        bump: legacy_group_bump,
+        visible: true,
+        owner: legacy_user_1.pubkey(),
+        last_message_time: 0,
+        destination_wallet: legacy_user_1.pubkey(),
+        msg_count: 1,
+        lamports_per_message: 0,
+        media_enabled: true,
+        admin_only: false,
+        group_pic_hash: "".to_string(),
+        group_name: "Legacy".to_string(),
+        admins: vec![legacy_user_2],
+    };
+    let mut legacy_data = legacy_data.try_to_vec().unwrap();
+    legacy_data.resize(GROUP_THREAD_V0_LEN, 0);
+    program_test.add_account(
+        legacy_group,
+        Account {
+            lamports: Rent::default().minimum_balance(legacy_data.len()),
+            data: legacy_data,
+            owner: jab_program_id,
+            ..Account::default()
+        },
+    );
+
+    // Messages sent by the initial program version in the legacy thread and group
+    let (legacy_message, _) =
+        Message::find_key(0, &legacy_user_1.pubkey(), &legacy_user_2, &jab_program_id);
+    let (legacy_group_message, _) =
+        Message::find_key(0, &legacy_group, &legacy_group, &jab_program_id);
+    for key in &[legacy_message, legacy_group_message] {
+        let legacy_data = MessageV0 {
+            tag: Tag::Message,
+            kind: MessageType::UnencryptedText,
+            timestamp: 0,
+            sender: legacy_user_1.pubkey(),
+            replies_to: Pubkey::default(),
+            likes_count: 0,
+            dislikes_count: 0,
+            msg: "Legacy".as_bytes().to_vec(),
+        }
+        .try_to_vec()
+        .unwrap();
+        program_test.add_account(
+            *key,
+            Account {
+                lamports: Rent::default().minimum_balance(legacy_data.len()),
+                data: legacy_data,
+                owner: jab_program_id,
+                ..Account::default()
+            },
+        );
+    }
+
+    // Profile and subscription created by the initial program version
+    let legacy_subscriber = Keypair::new();
+    let (legacy_profile, legacy_profile_bump) =
+        Profile::find_key(&legacy_subscriber.pubkey(), &jab_program_id);
+    let legacy_data = ProfileV0 {
+        tag: Tag::Profile,
+        bump: legacy_profile_bump,
+        picture_hash: "Legacy".to_string(),
+        display_domain_name: "Legacy".to_string(),
+        bio: "Legacy".to_string(),
+        lamports_per_message: 0,
+        allow_dm: true,
+        tips_sent: 0,
+        tips_received: 2,
+    };
+    let mut legacy_data = legacy_data.try_to_vec().unwrap();
+    legacy_data.resize(PROFILE_V0_LEN, 0);
+    program_test.add_account(
+        legacy_profile,
+        Account {
+            lamports: Rent::default().minimum_balance(legacy_data.len()),
+            data: legacy_data,
+            owner: jab_program_id,
+            ..Account::default()
+        },
+    );
+
+    let (legacy_subscription, _) = Subscription::find_key(
+        &legacy_subscriber.pubkey(),
+        &legacy_user_1.pubkey(),
+        &jab_program_id,
+    );
+    let legacy_data = SubscriptionV0 {
+        subscriber: legacy_subscriber.pubkey(),
+        subscribed_to: legacy_subscription,
+    }
+    .try_to_vec()
+    .unwrap();
+    program_test.add_account(
+        legacy_subscription,
+        Account {
+            lamports: Rent::default().minimum_balance(legacy_data.len()),
+            data: legacy_data,
+            owner: jab_program_id,
+            ..Account::default()
+        },
+    );
+
+    // The program data account holding the upgrade authority of the program
+    let upgrade_authority = Keypair::new();
+    let (program_data, _) =
+        Pubkey::find_program_address(&[jab_program_id.as_ref()], &bpf_loader_upgradeable::ID);
+    let mut program_data_header = 3u32.to_le_bytes().to_vec();
+    program_data_header.extend_from_slice(&0u64.to_le_bytes());
+    program_data_header.push(1);
+    program_data_header.extend_from_slice(upgrade_authority.pubkey().as_ref());
+    program_test.add_account(
+        program_data,
+        Account {
+            lamports: Rent::default().minimum_balance(program_data_header.len()),
+            data: program_data_header,
+            owner: bpf_loader_upgradeable::ID,
+            ..Account::default()
+        },
+    );
 
     // Create test context
     let mut prg_test_ctx = program_test.start_with_context().await;
 
+    // Init config
+    let (vault, _) = Config::find_vault_key(&jab_program_id);
+    let (config, _) = Config::find_key(&jab_program_id);
+
+    let payer = prg_test_ctx.payer.pubkey();
+    let init_config_ix = |upgrade_authority: &Pubkey| {
+        init_config(
+            jab_program_id,
+            init_config::Accounts {
+                system_program: &system_program::ID,
+                fee_payer: &payer,
+                config: &config,
+                upgrade_authority,
+                program_data: &program_data,
+                vault: &vault,
+            },
+            init_config::Params {
+                admin: payer,
+                fee_bps: 200,
+                fee_authority: payer,
+                referrer_share_bps: 2_000,
+            },
+        )
+    };
+
+    // Only the upgrade authority of the program can initialize the config
+    let not_authority = Keypair::new();
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![init_config_ix(&not_authority.pubkey())],
+        vec![&not_authority],
+    )
+    .await
+    .is_err());
+
+    let mut unsigned_init_config_ix = init_config_ix(&upgrade_authority.pubkey());
+    unsigned_init_config_ix.accounts[3].is_signer = false;
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![unsigned_init_config_ix], vec![])
+            .await
+            .is_err()
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![init_config_ix(&upgrade_authority.pubkey())],
+        vec![&upgrade_authority],
+    )
+    .await
+    .unwrap();
+
+    // The vault is funded with the rent exempt minimum
+    assert_eq!(
+        prg_test_ctx.banks_client.get_balance(vault).await.unwrap(),
+        Rent::default().minimum_balance(0)
+    );
+
+    // Update config
+    let update_config_ix = update_config(
+        jab_program_id,
+        update_config::Accounts {
+            admin: &prg_test_ctx.payer.pubkey(),
+            config: &config,
+            system_program: None,
+            spl_token_program: None,
+            associated_token_program: None,
+            rent_sysvar: None,
+            vault: None,
+            mint: None,
+            vault_token_account: None,
+        },
+        update_config::Params {
+            admin: prg_test_ctx.payer.pubkey(),
+            fee_bps: 100,
+            fee_authority: prg_test_ctx.payer.pubkey(),
+            referrer_share_bps: 5_000,
+            paused: false,
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![update_config_ix], vec![])
+        .await
+        .unwrap();
+
+    let config_data = prg_test_ctx
+        .banks_client
+        .get_account(config)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let config_data = Config::deserialize(&mut &config_data[..]).unwrap();
+    assert_eq!(config_data.fee_bps, 100);
+
+    // Register referrer
+    let referrer_wallet = Keypair::new();
+    let (referrer, _) = Referrer::find_key(&referrer_wallet.pubkey(), &jab_program_id);
+
+    let fund_referrer_ix = system_instruction::transfer(
+        &prg_test_ctx.payer.pubkey(),
+        &referrer_wallet.pubkey(),
+        100_000_000,
+    );
+    let register_referrer_ix = register_referrer(
+        jab_program_id,
+        register_referrer::Accounts {
+            system_program: &system_program::ID,
+            wallet: &referrer_wallet.pubkey(),
+            referrer: &referrer,
+        },
+        register_referrer::Params {},
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![fund_referrer_ix, register_referrer_ix],
+        vec![&referrer_wallet],
+    )
+    .await
+    .unwrap();
+
+    // The payer registers as a referrer to check that it cannot refer its own payments
+    let (payer_referrer, _) = Referrer::find_key(&prg_test_ctx.payer.pubkey(), &jab_program_id);
+    let register_payer_referrer_ix = register_referrer(
+        jab_program_id,
+        register_referrer::Accounts {
+            system_program: &system_program::ID,
+            wallet: &prg_test_ctx.payer.pubkey(),
+            referrer: &payer_referrer,
+        },
+        register_referrer::Params {},
+    );
+    sign_send_instructions(&mut prg_test_ctx, vec![register_payer_referrer_ix], vec![])
+        .await
+        .unwrap();
+
     // Create receiver
     let receiver_account = Keypair::new();
 
@@ -59,24 +366,46 @@ async fn test_jab() {
     .unwrap();
 
     // Set user profile
+    let set_profile_ix = |escrow_period: i64, price_mint: Option<Pubkey>| {
+        set_user_profile(
+            jab_program_id,
+            set_user_profile::Accounts {
+                system_program: &system_program::ID,
+                profile_owner: &receiver_account.pubkey(),
+                profile: &profile_account,
+            },
+            set_user_profile::Params {
+                picture_hash: "Receiver".to_string(),
+                display_domain_name: "Test".to_string(),
+                bio: "I receive message".to_string(),
+                lamports_per_message: 2_000_000_000,
+                price_mint,
+                price_amount: 0,
+                allow_dm: true,
+                escrow_enabled: true,
+                escrow_period,
+                edit_window: None,
+            },
+        )
+    };
+
+    // The escrow period must be positive and escrow is not supported for token prices
+    for ix in [
+        set_profile_ix(0, None),
+        set_profile_ix(-1, None),
+        set_profile_ix(MAX_ESCROW_PERIOD + 1, None),
+        set_profile_ix(1, Some(Pubkey::new_unique())),
+    ] {
+        assert!(
+            sign_send_instructions(&mut prg_test_ctx, vec![ix], vec![&receiver_account])
+                .await
+                .is_err()
+        );
+    }
 
-    let set_profile_ix = set_user_profile(
-        jab_program_id,
-        set_user_profile::Accounts {
-            profile_owner: &receiver_account.pubkey(),
-            profile: &profile_account,
-        },
-        set_user_profile::Params {
-            picture_hash: "Receiver".to_string(),
-            display_domain_name: "Test".to_string(),
-            bio: "I receive message".to_string(),
-            lamports_per_message: 2_000_000_000,
-            allow_dm: true,
-        },
-    );
     sign_send_instructions(
         &mut prg_test_ctx,
-        vec![set_profile_ix],
+        vec![set_profile_ix(1, None)],
         vec![&receiver_account],
     )
     .await
@@ -106,6 +435,22 @@ async fn test_jab() {
         .await
         .unwrap();
 
+    // The creator cannot accept the thread before sending the request
+    let self_accept_ix = accept_thread(
+        jab_program_id,
+        accept_thread::Accounts {
+            receiver: &prg_test_ctx.payer.pubkey(),
+            thread: &thread_account,
+        },
+        accept_thread::Params {},
+    );
+
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![self_accept_ix], vec![])
+            .await
+            .is_err()
+    );
+
     // Send message
     let (message_account, _) = Message::find_key(
         0,
@@ -114,234 +459,1772 @@ async fn test_jab() {
         &jab_program_id,
     );
 
-    let send_message_instruction = send_message(
-        jab_program_id,
-        send_message::Accounts {
-            system_program: &system_program::ID,
-            sender: &prg_test_ctx.payer.pubkey(),
-            receiver: &receiver_account.pubkey(),
-            thread: &thread_account,
-            receiver_profile: &profile_account,
-            message: &message_account,
-            sol_vault: &SOL_VAULT,
-        },
-        send_message::Params {
-            replies_to: Pubkey::default(),
-            kind: MessageType::UnencryptedText,
-            message: "Test JKnsfdjbgdfuigjbn sdjknfsdjkfndsfjkn"
-                .to_string()
-                .as_bytes()
-                .to_vec(),
-        },
+    let payer_key = prg_test_ctx.payer.pubkey();
+    let send_message_instruction = |kind: MessageType| {
+        send_message(
+            jab_program_id,
+            send_message::Accounts {
+                system_program: &system_program::ID,
+                sender: &payer_key,
+                receiver: &receiver_account.pubkey(),
+                thread: &thread_account,
+                receiver_profile: &profile_account,
+                message: &message_account,
+                sol_vault: &vault,
+                config: &config,
+                block: &Block::find_key(&receiver_account.pubkey(), &payer_key, &jab_program_id).0,
+                contact: &Contact::find_key(
+                    &receiver_account.pubkey(),
+                    &payer_key,
+                    &jab_program_id,
+                )
+                .0,
+                escrow: &Escrow::find_key(&message_account, &jab_program_id).0,
+                reply_escrow: &Escrow::find_key(&Pubkey::default(), &jab_program_id).0,
+                spl_token_program: None,
+                token_source: None,
+                token_destination: None,
+                vault_token_account: None,
+                referrer: Some(&referrer_wallet.pubkey()),
+                referrer_stats: Some(&referrer),
+            },
+            send_message::Params {
+                replies_to: Pubkey::default(),
+                kind,
+                message: "Test JKnsfdjbgdfuigjbn sdjknfsdjkfndsfjkn"
+                    .to_string()
+                    .as_bytes()
+                    .to_vec(),
+            },
+        )
+    };
+
+    // Tombstones cannot be sent
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![send_message_instruction(MessageType::Deleted)],
+        vec![],
+    )
+    .await
+    .is_err());
+
+    // Senders cannot refer themselves
+    let mut self_referral_ix = send_message_instruction(MessageType::UnencryptedText);
+    let referrer_index = self_referral_ix.accounts.len() - 2;
+    self_referral_ix.accounts[referrer_index].pubkey = payer_key;
+    self_referral_ix.accounts[referrer_index + 1].pubkey = payer_referrer;
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![self_referral_ix], vec![])
+            .await
+            .is_err()
     );
 
-    sign_send_instructions(&mut prg_test_ctx, vec![send_message_instruction], vec![])
-        .await
-        .unwrap();
+    // The price of the receiver cannot be skipped by passing an empty profile account
+    let mut wrong_profile_ix = send_message_instruction(MessageType::UnencryptedText);
+    wrong_profile_ix.accounts[4].pubkey = Pubkey::new_unique();
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![wrong_profile_ix], vec![])
+            .await
+            .is_err()
+    );
 
-    ////
-    // Test groups instruction
-    ////
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![send_message_instruction(MessageType::UnencryptedText)],
+        vec![],
+    )
+    .await
+    .unwrap();
 
-    // Create group
+    let referrer_data = prg_test_ctx
+        .banks_client
+        .get_account(referrer)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let referrer_data = Referrer::deserialize(&mut &referrer_data[..]).unwrap();
+    assert_eq!(referrer_data.referral_count, 1);
+    assert_eq!(referrer_data.lamports_earned, 10_000_000);
 
-    let (group_thread, _) = GroupThread::find_key(
-        "group_name".to_string(),
-        prg_test_ctx.payer.pubkey(),
-        &jab_program_id,
-    );
+    // Refund escrow
+    let (escrow, _) = Escrow::find_key(&message_account, &jab_program_id);
+    let escrow_data = prg_test_ctx
+        .banks_client
+        .get_account(escrow)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let escrow_data = Escrow::deserialize(&mut &escrow_data[..]).unwrap();
+    assert_eq!(escrow_data.sender, prg_test_ctx.payer.pubkey());
+    assert_eq!(escrow_data.receiver, receiver_account.pubkey());
 
-    let create_group_thread_ix = create_group_thread(
+    let refund_escrow_ix = refund_escrow(
         jab_program_id,
-        create_group_thread::Accounts {
-            system_program: &system_program::ID,
-            group_thread: &group_thread,
-            fee_payer: &prg_test_ctx.payer.pubkey(),
-        },
-        create_group_thread::Params {
-            visible: true,
-            group_name: "group_name".to_string(),
-            destination_wallet: prg_test_ctx.payer.pubkey(),
-            lamports_per_message: 1_000_000,
-            admins: vec![receiver_account.pubkey()],
-            owner: prg_test_ctx.payer.pubkey(),
-            media_enabled: true,
-            admin_only: false,
+        refund_escrow::Accounts {
+            sender: &prg_test_ctx.payer.pubkey(),
+            escrow: &escrow,
         },
+        refund_escrow::Params {},
     );
 
-    sign_send_instructions(&mut prg_test_ctx, vec![create_group_thread_ix], vec![])
+    // The payment can only be refunded after the escrow period
+    let mut clock = prg_test_ctx
+        .banks_client
+        .get_sysvar::<Clock>()
         .await
         .unwrap();
+    clock.unix_timestamp += 2;
+    prg_test_ctx.set_sysvar(&clock);
 
-    // Edit group
-    let edit_group_thread_ix = edit_group_thread(
+    sign_send_instructions(&mut prg_test_ctx, vec![refund_escrow_ix], vec![])
+        .await
+        .unwrap();
+
+    assert!(prg_test_ctx
+        .banks_client
+        .get_account(escrow)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Accept thread
+    let accept_thread_ix = accept_thread(
         jab_program_id,
-        edit_group_thread::Accounts {
-            group_owner: &prg_test_ctx.payer.pubkey(),
-            group_thread: &group_thread,
-        },
-        edit_group_thread::Params {
-            visible: false,
-            destination_wallet: receiver_account.pubkey(),
-            lamports_per_message: 2 * 1_000_000,
-            owner: prg_test_ctx.payer.pubkey(),
-            media_enabled: false,
-            admin_only: false,
-            group_pic_hash: "".to_string(),
+        accept_thread::Accounts {
+            receiver: &receiver_account.pubkey(),
+            thread: &thread_account,
         },
+        accept_thread::Params {},
     );
 
-    sign_send_instructions(&mut prg_test_ctx, vec![edit_group_thread_ix], vec![])
-        .await
-        .unwrap();
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![accept_thread_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
 
-    // Send message
+    let thread_data = prg_test_ctx
+        .banks_client
+        .get_account(thread_account)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let thread_data = Thread::deserialize(&mut &thread_data[..]).unwrap();
+    assert!(thread_data.state == ThreadState::Accepted);
+    assert_eq!(thread_data.requester, prg_test_ctx.payer.pubkey());
 
-    let (group_message, _) = Message::find_key(0, &group_thread, &group_thread, &jab_program_id);
+    // Add and remove contact
+    let (contact, _) = Contact::find_key(
+        &receiver_account.pubkey(),
+        &prg_test_ctx.payer.pubkey(),
+        &jab_program_id,
+    );
+    let (reverse_contact, _) = Contact::find_key(
+        &prg_test_ctx.payer.pubkey(),
+        &receiver_account.pubkey(),
+        &jab_program_id,
+    );
 
-    let send_group_message_ix = send_message_group(
+    let add_contact_ix = add_contact(
         jab_program_id,
-        send_message_group::Accounts {
+        add_contact::Accounts {
             system_program: &system_program::ID,
-            sender: &prg_test_ctx.payer.pubkey(),
-            group_thread: &group_thread,
-            destination_wallet: &receiver_account.pubkey(),
-            message: &group_message,
-            sol_vault: &SOL_VAULT,
+            owner: &receiver_account.pubkey(),
+            contact: &contact,
+            reverse_contact: &reverse_contact,
         },
-        send_message_group::Params {
-            kind: MessageType::UnencryptedText,
-            message: "Coucou les gars".to_string().as_bytes().to_vec(),
-            group_name: "group_name".to_string(),
-            admin_index: None,
-            replies_to: Pubkey::default(),
+        add_contact::Params {
+            contact: prg_test_ctx.payer.pubkey(),
         },
     );
 
-    sign_send_instructions(&mut prg_test_ctx, vec![send_group_message_ix], vec![])
-        .await
-        .unwrap();
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![add_contact_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
 
-    // Add admin to group
-    let add_admin_ix = add_admin_to_group(
+    let add_reverse_contact_ix = add_contact(
         jab_program_id,
-        add_admin_to_group::Accounts {
-            group_thread: &group_thread,
-            group_owner: &prg_test_ctx.payer.pubkey(),
+        add_contact::Accounts {
+            system_program: &system_program::ID,
+            owner: &prg_test_ctx.payer.pubkey(),
+            contact: &reverse_contact,
+            reverse_contact: &contact,
         },
-        add_admin_to_group::Params {
-            admin_address: receiver_account.pubkey(),
+        add_contact::Params {
+            contact: receiver_account.pubkey(),
         },
     );
 
-    sign_send_instructions(&mut prg_test_ctx, vec![add_admin_ix], vec![])
+    sign_send_instructions(&mut prg_test_ctx, vec![add_reverse_contact_ix], vec![])
         .await
         .unwrap();
 
-    // Remove admin from group
-    let remove_admin_ix = remove_admin_from_group(
+    let contact_data = prg_test_ctx
+        .banks_client
+        .get_account(contact)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let contact_data = Contact::deserialize(&mut &contact_data[..]).unwrap();
+    assert!(contact_data.mutual);
+
+    let remove_contact_ix = remove_contact(
         jab_program_id,
-        remove_admin_from_group::Accounts {
-            group_thread: &group_thread,
-            group_owner: &prg_test_ctx.payer.pubkey(),
-        },
-        remove_admin_from_group::Params {
-            admin_address: receiver_account.pubkey(),
-            admin_index: 1,
+        remove_contact::Accounts {
+            owner: &receiver_account.pubkey(),
+            contact: &contact,
+            reverse_contact: &reverse_contact,
         },
+        remove_contact::Params {},
     );
 
-    sign_send_instructions(&mut prg_test_ctx, vec![remove_admin_ix], vec![])
-        .await
-        .unwrap();
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![remove_contact_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
 
-    // Create group index
+    assert!(prg_test_ctx
+        .banks_client
+        .get_account(contact)
+        .await
+        .unwrap()
+        .is_none());
+    let reverse_contact_data = prg_test_ctx
+        .banks_client
+        .get_account(reverse_contact)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let reverse_contact_data = Contact::deserialize(&mut &reverse_contact_data[..]).unwrap();
+    assert!(!reverse_contact_data.mutual);
 
-    let (group_index, _) = GroupThreadIndex::find_key(
-        "group name".to_string(),
-        group_thread,
-        prg_test_ctx.payer.pubkey(),
+    // Block and unblock user
+    let (block, _) = Block::find_key(
+        &receiver_account.pubkey(),
+        &prg_test_ctx.payer.pubkey(),
         &jab_program_id,
     );
-    let create_group_index_ix = create_group_index(
+
+    let block_user_ix = block_user(
         jab_program_id,
-        create_group_index::Accounts {
+        block_user::Accounts {
             system_program: &system_program::ID,
-            group_thread_index: &group_index,
-            fee_payer: &prg_test_ctx.payer.pubkey(),
+            blocker: &receiver_account.pubkey(),
+            block: &block,
         },
-        create_group_index::Params {
-            group_name: "group name".to_string(),
-            group_thread_key: group_thread,
-            owner: prg_test_ctx.payer.pubkey(),
+        block_user::Params {
+            blocked: prg_test_ctx.payer.pubkey(),
         },
     );
 
-    sign_send_instructions(&mut prg_test_ctx, vec![create_group_index_ix], vec![])
-        .await
-        .unwrap();
-
-    let (group_index_2, _) = GroupThreadIndex::find_key(
-        "group name".to_string(),
-        group_thread,
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![block_user_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    let block_data = prg_test_ctx
+        .banks_client
+        .get_account(block)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let block_data = Block::deserialize(&mut &block_data[..]).unwrap();
+    assert_eq!(block_data.blocked, prg_test_ctx.payer.pubkey());
+
+    let unblock_user_ix = unblock_user(
+        jab_program_id,
+        unblock_user::Accounts {
+            blocker: &receiver_account.pubkey(),
+            block: &block,
+        },
+        unblock_user::Params {},
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![unblock_user_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    assert!(prg_test_ctx
+        .banks_client
+        .get_account(block)
+        .await
+        .unwrap()
+        .is_none());
+
+    // React to message
+    let (reaction, _) = Reaction::find_key(
+        &message_account,
+        &prg_test_ctx.payer.pubkey(),
+        &jab_program_id,
+    );
+
+    let react_ix = react_to_message(
+        jab_program_id,
+        react_to_message::Accounts {
+            system_program: &system_program::ID,
+            reactor: &prg_test_ctx.payer.pubkey(),
+            thread: &thread_account,
+            message: &message_account,
+            reaction: &reaction,
+            group_member: None,
+            group_moderation: None,
+            group_admin: None,
+        },
+        react_to_message::Params {
+            message_index: 0,
+            reaction: Some(ReactionType::Like),
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![react_ix], vec![])
+        .await
+        .unwrap();
+
+    // Switch reaction
+    let react_ix = react_to_message(
+        jab_program_id,
+        react_to_message::Accounts {
+            system_program: &system_program::ID,
+            reactor: &prg_test_ctx.payer.pubkey(),
+            thread: &thread_account,
+            message: &message_account,
+            reaction: &reaction,
+            group_member: None,
+            group_moderation: None,
+            group_admin: None,
+        },
+        react_to_message::Params {
+            message_index: 0,
+            reaction: Some(ReactionType::Dislike),
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![react_ix], vec![])
+        .await
+        .unwrap();
+
+    let message_data = prg_test_ctx
+        .banks_client
+        .get_account(message_account)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let message = Message::deserialize(&mut message_data.as_slice()).unwrap();
+    assert_eq!(message.likes_count, 0);
+    assert_eq!(message.dislikes_count, 1);
+
+    // Remove reaction
+    let react_ix = react_to_message(
+        jab_program_id,
+        react_to_message::Accounts {
+            system_program: &system_program::ID,
+            reactor: &prg_test_ctx.payer.pubkey(),
+            thread: &thread_account,
+            message: &message_account,
+            reaction: &reaction,
+            group_member: None,
+            group_moderation: None,
+            group_admin: None,
+        },
+        react_to_message::Params {
+            message_index: 0,
+            reaction: None,
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![react_ix], vec![])
+        .await
+        .unwrap();
+
+    ////
+    // Test groups instruction
+    ////
+
+    // Create group
+
+    let group_id = Keypair::new().pubkey().to_bytes();
+    let (group_thread, _) =
+        GroupThread::find_key(group_id, prg_test_ctx.payer.pubkey(), &jab_program_id);
+
+    let create_group_thread_ix = create_group_thread(
+        jab_program_id,
+        create_group_thread::Accounts {
+            system_program: &system_program::ID,
+            group_thread: &group_thread,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+        },
+        create_group_thread::Params {
+            visible: true,
+            group_id,
+            group_name: "A group name longer than the 32 bytes of a seed".to_string(),
+            destination_wallet: prg_test_ctx.payer.pubkey(),
+            lamports_per_message: 1_000_000,
+            price_mint: None,
+            price_amount: 0,
+            owner: prg_test_ctx.payer.pubkey(),
+            media_enabled: true,
+            admin_only: false,
+            members_only: false,
+            private: false,
+            storage_mode: StorageMode::Accounts,
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![create_group_thread_ix], vec![])
+        .await
+        .unwrap();
+
+    // Add admin to group
+    let add_admin_ix = add_admin_to_group(
+        jab_program_id,
+        add_admin_to_group::Accounts {
+            system_program: &system_program::ID,
+            group_thread: &group_thread,
+            group_owner: &prg_test_ctx.payer.pubkey(),
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        add_admin_to_group::Params {
+            admin_address: receiver_account.pubkey(),
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![add_admin_ix], vec![])
+        .await
+        .unwrap();
+
+    // Edit group
+    let edit_group_thread_ix = edit_group_thread(
+        jab_program_id,
+        edit_group_thread::Accounts {
+            editor: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        edit_group_thread::Params {
+            visible: Some(false),
+            destination_wallet: Some(receiver_account.pubkey()),
+            lamports_per_message: Some(2 * 1_000_000),
+            price_mint: None,
+            price_amount: None,
+            media_enabled: Some(false),
+            admin_only: None,
+            members_only: Some(true),
+            private: None,
+            edit_window: Some(60 * 60),
+            group_pic_hash: None,
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![edit_group_thread_ix], vec![])
+        .await
+        .unwrap();
+
+    // The edit window cannot be negative
+    let edit_group_thread_ix = edit_group_thread(
+        jab_program_id,
+        edit_group_thread::Accounts {
+            editor: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        edit_group_thread::Params {
+            visible: None,
+            destination_wallet: None,
+            lamports_per_message: None,
+            price_mint: None,
+            price_amount: None,
+            media_enabled: None,
+            admin_only: None,
+            members_only: None,
+            private: None,
+            edit_window: Some(-1),
+            group_pic_hash: None,
+        },
+    );
+
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![edit_group_thread_ix], vec![])
+            .await
+            .is_err()
+    );
+
+    // Admins can edit the group picture
+    let edit_group_pic_ix = edit_group_thread(
+        jab_program_id,
+        edit_group_thread::Accounts {
+            editor: &receiver_account.pubkey(),
+            group_thread: &group_thread,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        edit_group_thread::Params {
+            visible: None,
+            destination_wallet: None,
+            lamports_per_message: None,
+            price_mint: None,
+            price_amount: None,
+            media_enabled: None,
+            admin_only: None,
+            members_only: None,
+            private: None,
+            edit_window: None,
+            group_pic_hash: Some("group_pic".to_string()),
+        },
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![edit_group_pic_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    // Rename the group, the address stays the same
+    let rename_group_ix = rename_group(
+        jab_program_id,
+        rename_group::Accounts {
+            editor: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        rename_group::Params {
+            group_name: "Renamed group".to_string(),
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![rename_group_ix], vec![])
+        .await
+        .unwrap();
+
+    let group_thread_data = prg_test_ctx
+        .banks_client
+        .get_account(group_thread)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let group_thread_data = GroupThread::deserialize(&mut &group_thread_data[..]).unwrap();
+    assert_eq!(group_thread_data.group_name, "Renamed group");
+    assert_eq!(group_thread_data.key(&jab_program_id), group_thread);
+
+    // Transfer the group ownership and back
+    let propose_group_owner_ix = propose_group_owner(
+        jab_program_id,
+        propose_group_owner::Accounts {
+            group_owner: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+        },
+        propose_group_owner::Params {
+            new_owner: Some(receiver_account.pubkey()),
+        },
+    );
+    let accept_group_owner_ix = accept_group_owner(
+        jab_program_id,
+        accept_group_owner::Accounts {
+            new_owner: &receiver_account.pubkey(),
+            group_thread: &group_thread,
+        },
+        accept_group_owner::Params {},
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![propose_group_owner_ix, accept_group_owner_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    let group_thread_data = prg_test_ctx
+        .banks_client
+        .get_account(group_thread)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let group_thread_data = GroupThread::deserialize(&mut &group_thread_data[..]).unwrap();
+    assert_eq!(group_thread_data.owner, receiver_account.pubkey());
+    assert_eq!(group_thread_data.pending_owner, None);
+    assert_eq!(group_thread_data.group_pic_hash, "group_pic");
+    assert!(group_thread_data.members_only);
+
+    let propose_group_owner_ix = propose_group_owner(
+        jab_program_id,
+        propose_group_owner::Accounts {
+            group_owner: &receiver_account.pubkey(),
+            group_thread: &group_thread,
+        },
+        propose_group_owner::Params {
+            new_owner: Some(prg_test_ctx.payer.pubkey()),
+        },
+    );
+    let accept_group_owner_ix = accept_group_owner(
+        jab_program_id,
+        accept_group_owner::Accounts {
+            new_owner: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+        },
+        accept_group_owner::Params {},
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![propose_group_owner_ix, accept_group_owner_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    // Send message
+
+    let (group_message, _) = Message::find_group_key(
+        &group_thread,
+        &prg_test_ctx.payer.pubkey(),
+        0,
+        &jab_program_id,
+    );
+
+    let send_group_message_ix = send_message_group(
+        jab_program_id,
+        send_message_group::Accounts {
+            system_program: &system_program::ID,
+            sender: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            destination_wallet: &receiver_account.pubkey(),
+            message: &group_message,
+            sol_vault: &vault,
+            config: &config,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_moderation: &GroupModeration::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            spl_token_program: None,
+            token_source: None,
+            token_destination: None,
+            vault_token_account: None,
+            referrer: None,
+            referrer_stats: None,
+        },
+        send_message_group::Params {
+            kind: MessageType::UnencryptedText,
+            message: "Coucou les gars".to_string().as_bytes().to_vec(),
+            replies_to: Pubkey::default(),
+            nonce: 0,
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![send_group_message_ix], vec![])
+        .await
+        .unwrap();
+
+    // The group thread is passed read only and left untouched
+    let group_thread_data = prg_test_ctx
+        .banks_client
+        .get_account(group_thread)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let group_thread_data = GroupThread::deserialize(&mut &group_thread_data[..]).unwrap();
+    assert_eq!(group_thread_data.msg_count, 0);
+
+    // Join group
+    let (group_member, _) =
+        GroupMember::find_key(&group_thread, &receiver_account.pubkey(), &jab_program_id);
+    let (group_moderation, _) =
+        GroupModeration::find_key(&group_thread, &receiver_account.pubkey(), &jab_program_id);
+
+    let join_group_ix = join_group(
+        jab_program_id,
+        join_group::Accounts {
+            system_program: &system_program::ID,
+            member: &receiver_account.pubkey(),
+            group_thread: &group_thread,
+            group_member: &group_member,
+            group_moderation: &group_moderation,
+        },
+        join_group::Params {},
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![join_group_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    let group_member_data = prg_test_ctx
+        .banks_client
+        .get_account(group_member)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let group_member_data = GroupMember::deserialize(&mut &group_member_data[..]).unwrap();
+    assert_eq!(group_member_data.group_thread, group_thread);
+    assert_eq!(group_member_data.member, receiver_account.pubkey());
+
+    // Leave group
+    let leave_group_ix = leave_group(
+        jab_program_id,
+        leave_group::Accounts {
+            member: &receiver_account.pubkey(),
+            group_member: &group_member,
+            group_thread: &group_thread,
+        },
+        leave_group::Params {},
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![leave_group_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    assert!(prg_test_ctx
+        .banks_client
+        .get_account(group_member)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Create invite
+    let invite_code = Keypair::new();
+    let (invite, _) = Invite::find_key(&group_thread, &invite_code.pubkey(), &jab_program_id);
+
+    let create_invite_ix = |expires_at: i64, max_uses: u32| {
+        create_invite(
+            jab_program_id,
+            create_invite::Accounts {
+                system_program: &system_program::ID,
+                creator: &payer,
+                group_thread: &group_thread,
+                invite: &invite,
+                group_member: &GroupMember::find_key(&group_thread, &payer, &jab_program_id).0,
+                group_admin: &GroupAdmin::find_key(&group_thread, &payer, &jab_program_id).0,
+            },
+            create_invite::Params {
+                code_key: invite_code.pubkey(),
+                expires_at,
+                max_uses,
+            },
+        )
+    };
+
+    // Invites that are already expired or cannot be used are rejected
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![create_invite_ix(0, 1)], vec![])
+            .await
+            .is_err()
+    );
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![create_invite_ix(i64::MAX, 0)],
+        vec![]
+    )
+    .await
+    .is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![create_invite_ix(i64::MAX, 1)],
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    // Redeem invite
+    let redeem_invite_ix = redeem_invite(
+        jab_program_id,
+        redeem_invite::Accounts {
+            system_program: &system_program::ID,
+            member: &receiver_account.pubkey(),
+            group_thread: &group_thread,
+            invite: &invite,
+            invite_code: &invite_code.pubkey(),
+            group_member: &group_member,
+            group_moderation: &group_moderation,
+        },
+        redeem_invite::Params {},
+    );
+
+    // Redeeming requires a signature from the invite code
+    let mut unsigned_redeem_ix = redeem_invite_ix.clone();
+    unsigned_redeem_ix.accounts[4].is_signer = false;
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![unsigned_redeem_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![redeem_invite_ix],
+        vec![&receiver_account, &invite_code],
+    )
+    .await
+    .unwrap();
+
+    let invite_data = prg_test_ctx
+        .banks_client
+        .get_account(invite)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let invite_data = Invite::deserialize(&mut &invite_data[..]).unwrap();
+    assert_eq!(invite_data.uses, 1);
+    assert!(prg_test_ctx
+        .banks_client
+        .get_account(group_member)
+        .await
+        .unwrap()
+        .is_some());
+
+    // Revoke invite, the rent goes back to its creator
+    let revoke_invite_ix = revoke_invite(
+        jab_program_id,
+        revoke_invite::Accounts {
+            revoker: &receiver_account.pubkey(),
+            group_thread: &group_thread,
+            invite: &invite,
+            creator: &prg_test_ctx.payer.pubkey(),
+            group_member: &group_member,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        revoke_invite::Params {},
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![revoke_invite_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    assert!(prg_test_ctx
+        .banks_client
+        .get_account(invite)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Ban member
+    let ban_member_ix = ban_member(
+        jab_program_id,
+        ban_member::Accounts {
+            system_program: &system_program::ID,
+            moderator: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            group_moderation: &group_moderation,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            user: &receiver_account.pubkey(),
+            user_group_member: &group_member,
+            user_group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        ban_member::Params {
+            user: receiver_account.pubkey(),
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![ban_member_ix], vec![])
+        .await
+        .unwrap();
+
+    let group_moderation_data = prg_test_ctx
+        .banks_client
+        .get_account(group_moderation)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let group_moderation_data =
+        GroupModeration::deserialize(&mut &group_moderation_data[..]).unwrap();
+    assert!(group_moderation_data.banned);
+    assert_eq!(group_moderation_data.payer, prg_test_ctx.payer.pubkey());
+    // The membership of a banned user is closed
+    assert!(prg_test_ctx
+        .banks_client
+        .get_account(group_member)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Banned users cannot react to group messages
+    let banned_react_ix = react_to_message(
+        jab_program_id,
+        react_to_message::Accounts {
+            system_program: &system_program::ID,
+            reactor: &receiver_account.pubkey(),
+            thread: &group_thread,
+            message: &group_message,
+            reaction: &Reaction::find_key(
+                &group_message,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_member: Some(&group_member),
+            group_moderation: Some(&group_moderation),
+            group_admin: Some(
+                &GroupAdmin::find_key(&group_thread, &receiver_account.pubkey(), &jab_program_id).0,
+            ),
+        },
+        react_to_message::Params {
+            message_index: 0,
+            reaction: Some(ReactionType::Like),
+        },
+    );
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![banned_react_ix],
+        vec![&receiver_account]
+    )
+    .await
+    .is_err());
+
+    // Unban member
+    let unban_member_ix = unban_member(
+        jab_program_id,
+        unban_member::Accounts {
+            moderator: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            group_moderation: &group_moderation,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            moderation_payer: &prg_test_ctx.payer.pubkey(),
+        },
+        unban_member::Params {},
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![unban_member_ix], vec![])
+        .await
+        .unwrap();
+
+    assert!(prg_test_ctx
+        .banks_client
+        .get_account(group_moderation)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Mute member
+    let mute_member_ix = mute_member(
+        jab_program_id,
+        mute_member::Accounts {
+            system_program: &system_program::ID,
+            moderator: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            group_moderation: &group_moderation,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            user_group_member: &group_member,
+            user_group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        mute_member::Params {
+            user: receiver_account.pubkey(),
+            muted_until: i64::MAX,
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![mute_member_ix], vec![])
+        .await
+        .unwrap();
+
+    let group_moderation_data = prg_test_ctx
+        .banks_client
+        .get_account(group_moderation)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let group_moderation_data =
+        GroupModeration::deserialize(&mut &group_moderation_data[..]).unwrap();
+    assert!(!group_moderation_data.banned);
+    assert_eq!(group_moderation_data.muted_until, i64::MAX);
+
+    // Moderators cannot act on users with the same or more permissions
+    let (owner_moderation, _) =
+        GroupModeration::find_key(&group_thread, &prg_test_ctx.payer.pubkey(), &jab_program_id);
+    let mute_owner_ix = mute_member(
+        jab_program_id,
+        mute_member::Accounts {
+            system_program: &system_program::ID,
+            moderator: &receiver_account.pubkey(),
+            group_thread: &group_thread,
+            group_moderation: &owner_moderation,
+            group_member: &group_member,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            user_group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            user_group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        mute_member::Params {
+            user: prg_test_ctx.payer.pubkey(),
+            muted_until: i64::MAX,
+        },
+    );
+
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![mute_owner_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .is_err());
+
+    // Grant and revoke group permissions
+    let payer = prg_test_ctx.payer.pubkey();
+    let grant_group_permissions_ix = |role: GroupRole| {
+        grant_group_permissions(
+            jab_program_id,
+            grant_group_permissions::Accounts {
+                system_program: &system_program::ID,
+                group_owner: &payer,
+                group_thread: &group_thread,
+                group_member: &group_member,
+            },
+            grant_group_permissions::Params {
+                user: receiver_account.pubkey(),
+                role,
+                permissions: GroupPermission::FEE_EXEMPT,
+            },
+        )
+    };
+
+    // Admins are only added with a `GroupAdmin` account
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![grant_group_permissions_ix(GroupRole::Admin)],
+        vec![],
+    )
+    .await
+    .is_err());
+
+    let revoke_group_permissions_ix = revoke_group_permissions(
+        jab_program_id,
+        revoke_group_permissions::Accounts {
+            group_owner: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            group_member: &group_member,
+        },
+        revoke_group_permissions::Params {
+            user: receiver_account.pubkey(),
+            permissions: GroupPermission::BAN,
+        },
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![
+            grant_group_permissions_ix(GroupRole::Moderator),
+            revoke_group_permissions_ix,
+        ],
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let group_member_data = prg_test_ctx
+        .banks_client
+        .get_account(group_member)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let group_member_data = GroupMember::deserialize(&mut &group_member_data[..]).unwrap();
+    assert_eq!(group_member_data.role, GroupRole::Moderator);
+    assert_eq!(
+        group_member_data.permissions,
+        (GroupRole::Moderator.permissions() | GroupPermission::FEE_EXEMPT) & !GroupPermission::BAN
+    );
+
+    // Remove admin from group
+    let remove_admin_ix = remove_admin_from_group(
+        jab_program_id,
+        remove_admin_from_group::Accounts {
+            group_thread: &group_thread,
+            group_owner: &prg_test_ctx.payer.pubkey(),
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        remove_admin_from_group::Params {
+            admin_address: receiver_account.pubkey(),
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![remove_admin_ix], vec![])
+        .await
+        .unwrap();
+
+    // Create group index
+
+    let (group_index, _) = GroupThreadIndex::find_key(
+        "group name".to_string(),
+        group_thread,
+        prg_test_ctx.payer.pubkey(),
+        &jab_program_id,
+    );
+    let create_group_index_ix = create_group_index(
+        jab_program_id,
+        create_group_index::Accounts {
+            system_program: &system_program::ID,
+            group_thread_index: &group_index,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            owner: &prg_test_ctx.payer.pubkey(),
+        },
+        create_group_index::Params {
+            group_name: "group name".to_string(),
+            group_thread_key: group_thread,
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![create_group_index_ix], vec![])
+        .await
+        .unwrap();
+
+    let (group_index_2, _) = GroupThreadIndex::find_key(
+        "group name".to_string(),
+        group_thread,
         receiver_account.pubkey(),
         &jab_program_id,
     );
 
-    let create_group_index_ix = create_group_index(
+    let mut create_group_index_ix = create_group_index(
+        jab_program_id,
+        create_group_index::Accounts {
+            system_program: &system_program::ID,
+            group_thread_index: &group_index_2,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            owner: &receiver_account.pubkey(),
+        },
+        create_group_index::Params {
+            group_name: "group name".to_string(),
+            group_thread_key: group_thread,
+        },
+    );
+
+    // The indexed user must sign
+    create_group_index_ix.accounts[3].is_signer = false;
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![create_group_index_ix.clone()],
+        vec![],
+    )
+    .await
+    .is_err());
+
+    create_group_index_ix.accounts[3].is_signer = true;
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![create_group_index_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    // Edit message
+    let edit_message_ix = edit_message(
+        jab_program_id,
+        edit_message::Accounts {
+            system_program: &system_program::ID,
+            sender: &prg_test_ctx.payer.pubkey(),
+            receiver: &receiver_account.pubkey(),
+            message: &message_account,
+            receiver_profile: &profile_account,
+        },
+        edit_message::Params {
+            message_index: 0,
+            message: "Edited".to_string().as_bytes().to_vec(),
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![edit_message_ix], vec![])
+        .await
+        .unwrap();
+
+    let message_data = prg_test_ctx
+        .banks_client
+        .get_account(message_account)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let message = Message::deserialize(&mut message_data.as_slice()).unwrap();
+    assert_eq!(message.msg, "Edited".as_bytes());
+    assert_ne!(message.edited_at, 0);
+
+    // Only the sender can edit a message
+    let (sender_profile_key, _) = Profile::find_key(&prg_test_ctx.payer.pubkey(), &jab_program_id);
+    let edit_message_ix = edit_message(
+        jab_program_id,
+        edit_message::Accounts {
+            system_program: &system_program::ID,
+            sender: &receiver_account.pubkey(),
+            receiver: &prg_test_ctx.payer.pubkey(),
+            message: &message_account,
+            receiver_profile: &sender_profile_key,
+        },
+        edit_message::Params {
+            message_index: 0,
+            message: "Not mine".to_string().as_bytes().to_vec(),
+        },
+    );
+
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![edit_message_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .is_err());
+
+    // The receiver sets the edit window, it cannot be negative
+    let set_edit_window_ix = |edit_window: Option<i64>| {
+        set_user_profile(
+            jab_program_id,
+            set_user_profile::Accounts {
+                system_program: &system_program::ID,
+                profile_owner: &receiver_account.pubkey(),
+                profile: &profile_account,
+            },
+            set_user_profile::Params {
+                picture_hash: "Receiver".to_string(),
+                display_domain_name: "Test".to_string(),
+                bio: "I receive message".to_string(),
+                lamports_per_message: 2_000_000_000,
+                price_mint: None,
+                price_amount: 0,
+                allow_dm: true,
+                escrow_enabled: true,
+                escrow_period: 1,
+                edit_window,
+            },
+        )
+    };
+
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![set_edit_window_ix(Some(-1))],
+        vec![&receiver_account],
+    )
+    .await
+    .is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![set_edit_window_ix(Some(0))],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    // Edits are rejected once the window has expired
+    let mut clock = prg_test_ctx
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap();
+    clock.unix_timestamp += 1;
+    prg_test_ctx.set_sysvar(&clock);
+
+    let edit_message_ix = edit_message(
+        jab_program_id,
+        edit_message::Accounts {
+            system_program: &system_program::ID,
+            sender: &prg_test_ctx.payer.pubkey(),
+            receiver: &receiver_account.pubkey(),
+            message: &message_account,
+            receiver_profile: &profile_account,
+        },
+        edit_message::Params {
+            message_index: 0,
+            message: "Too late".to_string().as_bytes().to_vec(),
+        },
+    );
+
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![edit_message_ix], vec![])
+            .await
+            .is_err()
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![set_edit_window_ix(None)],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    // Edit group message
+    let edit_group_message_ix = edit_group_message(
+        jab_program_id,
+        edit_group_message::Accounts {
+            system_program: &system_program::ID,
+            sender: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            message: &group_message,
+        },
+        edit_group_message::Params {
+            nonce: 0,
+            message: "Coucou les gars, edited with a longer message"
+                .to_string()
+                .as_bytes()
+                .to_vec(),
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![edit_group_message_ix], vec![])
+        .await
+        .unwrap();
+
+    // Delete message
+    let delete_message_ix = delete_message(
+        jab_program_id,
+        delete_message::Accounts {
+            sender: &prg_test_ctx.payer.pubkey(),
+            receiver: &receiver_account.pubkey(),
+            message: &message_account,
+            thread: &thread_account,
+        },
+        delete_message::Params { message_index: 0 },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![delete_message_ix], vec![])
+        .await
+        .unwrap();
+
+    // The message account is closed
+    let message_account_info = prg_test_ctx
+        .banks_client
+        .get_account(message_account)
+        .await
+        .unwrap();
+    assert!(message_account_info.is_none());
+
+    // Delete group message, moderators can delete it but the rent goes back to the sender
+    let delete_group_message_ix = |sender: &Pubkey| {
+        delete_group_message(
+            jab_program_id,
+            delete_group_message::Accounts {
+                group_thread: &group_thread,
+                message: &group_message,
+                fee_payer: &receiver_account.pubkey(),
+                sender,
+                group_member: &group_member,
+                group_admin: &GroupAdmin::find_key(
+                    &group_thread,
+                    &receiver_account.pubkey(),
+                    &jab_program_id,
+                )
+                .0,
+                legacy_group_thread: None,
+            },
+            delete_group_message::Params { nonce: 0 },
+        )
+    };
+
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![delete_group_message_ix(&receiver_account.pubkey())],
+        vec![&receiver_account],
+    )
+    .await
+    .is_err());
+
+    let payer_key = prg_test_ctx.payer.pubkey();
+    let message_lamports = prg_test_ctx
+        .banks_client
+        .get_balance(group_message)
+        .await
+        .unwrap();
+    let sender_lamports = prg_test_ctx
+        .banks_client
+        .get_balance(payer_key)
+        .await
+        .unwrap();
+    let moderator_lamports = prg_test_ctx
+        .banks_client
+        .get_balance(receiver_account.pubkey())
+        .await
+        .unwrap();
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![delete_group_message_ix(&payer_key)],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(group_message)
+        .await
+        .unwrap();
+    assert!(account.is_none());
+    // The sender also pays the transaction fee
+    let sender_refund = prg_test_ctx
+        .banks_client
+        .get_balance(payer_key)
+        .await
+        .unwrap()
+        - sender_lamports;
+    assert!(sender_refund > 0 && sender_refund <= message_lamports);
+    assert_eq!(
+        prg_test_ctx
+            .banks_client
+            .get_balance(receiver_account.pubkey())
+            .await
+            .unwrap(),
+        moderator_lamports
+    );
+
+    // Bucketed group
+    let bucket_group_id = Keypair::new().pubkey().to_bytes();
+    let (bucket_group, _) = GroupThread::find_key(
+        bucket_group_id,
+        prg_test_ctx.payer.pubkey(),
+        &jab_program_id,
+    );
+    let (message_bucket, _) = MessageBucket::find_key(&bucket_group, 0, &jab_program_id);
+
+    let create_bucket_group_ix = create_group_thread(
         jab_program_id,
-        create_group_index::Accounts {
+        create_group_thread::Accounts {
             system_program: &system_program::ID,
-            group_thread_index: &group_index_2,
+            group_thread: &bucket_group,
             fee_payer: &prg_test_ctx.payer.pubkey(),
         },
-        create_group_index::Params {
-            group_name: "group name".to_string(),
-            group_thread_key: group_thread,
-            owner: receiver_account.pubkey(),
+        create_group_thread::Params {
+            visible: true,
+            group_id: bucket_group_id,
+            group_name: "Bucketed group".to_string(),
+            destination_wallet: prg_test_ctx.payer.pubkey(),
+            lamports_per_message: 0,
+            price_mint: None,
+            price_amount: 0,
+            owner: prg_test_ctx.payer.pubkey(),
+            media_enabled: true,
+            admin_only: false,
+            members_only: false,
+            private: false,
+            storage_mode: StorageMode::Buckets,
         },
     );
+    let send_bucket_messages_ix = (0..2)
+        .map(|nonce| {
+            send_message_group(
+                jab_program_id,
+                send_message_group::Accounts {
+                    system_program: &system_program::ID,
+                    sender: &prg_test_ctx.payer.pubkey(),
+                    group_thread: &bucket_group,
+                    destination_wallet: &prg_test_ctx.payer.pubkey(),
+                    message: &message_bucket,
+                    sol_vault: &vault,
+                    config: &config,
+                    group_member: &GroupMember::find_key(
+                        &bucket_group,
+                        &prg_test_ctx.payer.pubkey(),
+                        &jab_program_id,
+                    )
+                    .0,
+                    group_admin: &GroupAdmin::find_key(
+                        &bucket_group,
+                        &prg_test_ctx.payer.pubkey(),
+                        &jab_program_id,
+                    )
+                    .0,
+                    group_moderation: &GroupModeration::find_key(
+                        &bucket_group,
+                        &prg_test_ctx.payer.pubkey(),
+                        &jab_program_id,
+                    )
+                    .0,
+                    spl_token_program: None,
+                    token_source: None,
+                    token_destination: None,
+                    vault_token_account: None,
+                    referrer: None,
+                    referrer_stats: None,
+                },
+                send_message_group::Params {
+                    kind: MessageType::UnencryptedText,
+                    message: "ok".to_string().as_bytes().to_vec(),
+                    replies_to: Pubkey::default(),
+                    nonce,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
 
-    sign_send_instructions(&mut prg_test_ctx, vec![create_group_index_ix], vec![])
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        [vec![create_bucket_group_ix], send_bucket_messages_ix].concat(),
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let bucket_data = prg_test_ctx
+        .banks_client
+        .get_account(message_bucket)
         .await
-        .unwrap();
+        .unwrap()
+        .unwrap()
+        .data;
+    let bucket_data = MessageBucket::deserialize(&mut &bucket_data[..]).unwrap();
+    assert_eq!(bucket_data.live_count, 2);
+    assert_eq!(bucket_data.messages.len(), 2);
 
-    // Delete message
-    let delete_message_ix = delete_message(
+    // Bucket messages cannot be reacted to or edited
+    let react_bucket_ix = react_to_message(
         jab_program_id,
-        delete_message::Accounts {
-            sender: &prg_test_ctx.payer.pubkey(),
-            receiver: &receiver_account.pubkey(),
-            message: &message_account,
+        react_to_message::Accounts {
+            system_program: &system_program::ID,
+            reactor: &prg_test_ctx.payer.pubkey(),
+            thread: &bucket_group,
+            message: &message_bucket,
+            reaction: &Reaction::find_key(
+                &message_bucket,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_member: None,
+            group_moderation: None,
+            group_admin: None,
+        },
+        react_to_message::Params {
+            message_index: 0,
+            reaction: Some(ReactionType::Like),
         },
-        delete_message::Params { message_index: 0 },
+    );
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![react_bucket_ix], vec![])
+            .await
+            .is_err()
     );
 
-    sign_send_instructions(&mut prg_test_ctx, vec![delete_message_ix], vec![])
-        .await
-        .unwrap();
-
-    // Delete group message
-    let delete_group_message_ix = delete_group_message(
+    let edit_bucket_ix = edit_group_message(
         jab_program_id,
-        delete_group_message::Accounts {
-            group_thread: &group_thread,
-            message: &group_message,
-            fee_payer: &prg_test_ctx.payer.pubkey(),
+        edit_group_message::Accounts {
+            system_program: &system_program::ID,
+            sender: &prg_test_ctx.payer.pubkey(),
+            group_thread: &bucket_group,
+            message: &message_bucket,
         },
-        delete_group_message::Params {
-            message_index: 0,
-            admin_index: Some(0),
-            owner: prg_test_ctx.payer.pubkey(),
-            group_name: "group_name".to_string(),
+        edit_group_message::Params {
+            nonce: 0,
+            message: "edited".to_string().as_bytes().to_vec(),
         },
     );
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![edit_bucket_ix], vec![])
+            .await
+            .is_err()
+    );
+
+    // Deleting every message of a bucket closes it
+    let delete_bucket_messages_ix = (0..2)
+        .map(|slot| {
+            delete_bucket_message(
+                jab_program_id,
+                delete_bucket_message::Accounts {
+                    group_thread: &bucket_group,
+                    message_bucket: &message_bucket,
+                    fee_payer: &prg_test_ctx.payer.pubkey(),
+                    bucket_payer: &prg_test_ctx.payer.pubkey(),
+                    group_member: &GroupMember::find_key(
+                        &bucket_group,
+                        &prg_test_ctx.payer.pubkey(),
+                        &jab_program_id,
+                    )
+                    .0,
+                    group_admin: &GroupAdmin::find_key(
+                        &bucket_group,
+                        &prg_test_ctx.payer.pubkey(),
+                        &jab_program_id,
+                    )
+                    .0,
+                },
+                delete_bucket_message::Params {
+                    bucket_index: 0,
+                    slot,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
 
-    sign_send_instructions(&mut prg_test_ctx, vec![delete_group_message_ix], vec![])
+    sign_send_instructions(&mut prg_test_ctx, delete_bucket_messages_ix, vec![])
         .await
         .unwrap();
 
+    assert!(prg_test_ctx
+        .banks_client
+        .get_account(message_bucket)
+        .await
+        .unwrap()
+        .is_none());
+
     // Sent tip
 
     let mint = Keypair::new();
@@ -429,29 +2312,126 @@ async fn test_jab() {
         .await
         .unwrap();
 
-    let tip_ix = send_tip(
+    let tip_ix = |amount: u64| {
+        send_tip(
+            jab_program_id,
+            send_tip::Accounts {
+                spl_token_program: &spl_token::ID,
+                system_program: &system_program::ID,
+                sender_profile: &sender_profile,
+                sender: &payer,
+                receiver_profile: &profile_account,
+                receiver: &receiver_account.pubkey(),
+                token_source: &sender_token_acc,
+                token_destination: &receiver_token_acc,
+                config: &config,
+                vault_token_account: &get_associated_token_address(&vault, &mint.pubkey()),
+                referrer: None,
+                referrer_stats: None,
+            },
+            send_tip::Params { amount },
+        )
+    };
+
+    // Payments in the mint are rejected until the admin creates the vault token account
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![tip_ix(10 * 1_000_000)], vec![])
+            .await
+            .is_err()
+    );
+
+    let create_vault_token_acc_ix = update_config(
         jab_program_id,
-        send_tip::Accounts {
-            spl_token_program: &spl_token::ID,
-            sender_profile: &sender_profile,
-            sender: &prg_test_ctx.payer.pubkey(),
-            receiver_profile: &profile_account,
-            receiver: &receiver_account.pubkey(),
-            token_source: &sender_token_acc,
-            token_destination: &receiver_token_acc,
+        update_config::Accounts {
+            admin: &prg_test_ctx.payer.pubkey(),
+            config: &config,
+            system_program: Some(&system_program::ID),
+            spl_token_program: Some(&spl_token::ID),
+            associated_token_program: Some(&spl_associated_token_account::ID),
+            rent_sysvar: Some(&solana_program::sysvar::rent::ID),
+            vault: Some(&vault),
+            mint: Some(&mint.pubkey()),
+            vault_token_account: Some(&get_associated_token_address(&vault, &mint.pubkey())),
         },
-        send_tip::Params {
-            amount: 10 * 1_000_000,
+        update_config::Params {
+            admin: prg_test_ctx.payer.pubkey(),
+            fee_bps: 100,
+            fee_authority: prg_test_ctx.payer.pubkey(),
+            referrer_share_bps: 5_000,
+            paused: false,
         },
     );
-    sign_send_instructions(&mut prg_test_ctx, vec![tip_ix], vec![])
+    sign_send_instructions(&mut prg_test_ctx, vec![create_vault_token_acc_ix], vec![])
+        .await
+        .unwrap();
+
+    // Tippers cannot refer themselves either
+    let mut self_referral_tip_ix = tip_ix(12 * 1_000_000);
+    self_referral_tip_ix
+        .accounts
+        .push(AccountMeta::new(sender_token_acc, false));
+    self_referral_tip_ix
+        .accounts
+        .push(AccountMeta::new(payer_referrer, false));
+    assert!(
+        sign_send_instructions(&mut prg_test_ctx, vec![self_referral_tip_ix], vec![])
+            .await
+            .is_err()
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![tip_ix(11 * 1_000_000)], vec![])
         .await
         .unwrap();
 
+    // Withdraw fees
+    let vault_token_acc = get_associated_token_address(&vault, &mint.pubkey());
+    let withdraw_sol_ix = withdraw_fees(
+        jab_program_id,
+        withdraw_fees::Accounts {
+            system_program: &system_program::ID,
+            spl_token_program: &spl_token::ID,
+            fee_authority: &prg_test_ctx.payer.pubkey(),
+            config: &config,
+            vault: &vault,
+            source: &vault,
+            recipients: &[prg_test_ctx.payer.pubkey(), receiver_account.pubkey()],
+        },
+        withdraw_fees::Params {
+            mint: None,
+            amount: 1_000_000,
+            shares_bps: vec![5_000, 5_000],
+        },
+    );
+    let withdraw_tokens_ix = withdraw_fees(
+        jab_program_id,
+        withdraw_fees::Accounts {
+            system_program: &system_program::ID,
+            spl_token_program: &spl_token::ID,
+            fee_authority: &prg_test_ctx.payer.pubkey(),
+            config: &config,
+            vault: &vault,
+            source: &vault_token_acc,
+            recipients: &[sender_token_acc, receiver_token_acc],
+        },
+        withdraw_fees::Params {
+            mint: Some(mint.pubkey()),
+            amount: 100_000,
+            shares_bps: vec![2_500, 7_500],
+        },
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![withdraw_sol_ix, withdraw_tokens_ix],
+        vec![],
+    )
+    .await
+    .unwrap();
+
     //
     // Create subscription
     //
-    let subscribed_to = Keypair::new().pubkey();
+    let subscribed_to = receiver_account.pubkey();
     let (subscription_key, _) = Subscription::find_key(
         &prg_test_ctx.payer.pubkey(),
         &subscribed_to,
@@ -464,6 +2444,7 @@ async fn test_jab() {
             subscription: &subscription_key,
             subscriber: &prg_test_ctx.payer.pubkey(),
             system_program: &system_program::ID,
+            subscribed_to_profile: &profile_account,
         },
         create_subscription::Params { subscribed_to },
     );
@@ -471,4 +2452,428 @@ async fn test_jab() {
     sign_send_instructions(&mut prg_test_ctx, vec![create_sub_ix], vec![])
         .await
         .unwrap();
+
+    let profile_data = prg_test_ctx
+        .banks_client
+        .get_account(profile_account)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let profile = Profile::deserialize(&mut profile_data.as_slice()).unwrap();
+    assert_eq!(profile.subscriber_count, 1);
+
+    //
+    // Delete subscription
+    //
+    let delete_sub_ix = delete_subscription(
+        jab_program_id,
+        delete_subscription::Accounts {
+            subscription: &subscription_key,
+            subscriber: &prg_test_ctx.payer.pubkey(),
+            subscribed_to_profile: &profile_account,
+        },
+        delete_subscription::Params {},
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![delete_sub_ix], vec![])
+        .await
+        .unwrap();
+
+    let profile_data = prg_test_ctx
+        .banks_client
+        .get_account(profile_account)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let profile = Profile::deserialize(&mut profile_data.as_slice()).unwrap();
+    assert_eq!(profile.subscriber_count, 0);
+
+    // Subscribing to a legacy profile grows it to the current layout
+    let (subscription_key, _) = Subscription::find_key(
+        &prg_test_ctx.payer.pubkey(),
+        &legacy_subscriber.pubkey(),
+        &jab_program_id,
+    );
+    let create_sub_ix = create_subscription(
+        jab_program_id,
+        create_subscription::Accounts {
+            subscription: &subscription_key,
+            subscriber: &prg_test_ctx.payer.pubkey(),
+            system_program: &system_program::ID,
+            subscribed_to_profile: &legacy_profile,
+        },
+        create_subscription::Params {
+            subscribed_to: legacy_subscriber.pubkey(),
+        },
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![create_sub_ix], vec![])
+        .await
+        .unwrap();
+
+    let profile_data = prg_test_ctx
+        .banks_client
+        .get_account(legacy_profile)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(profile_data.len(), MAX_PROFILE_LEN);
+    let profile = Profile::deserialize(&mut profile_data.as_slice()).unwrap();
+    assert_eq!(profile.tips_received, 2);
+    assert_eq!(profile.subscriber_count, 1);
+
+    // Delete a legacy subscription
+    let delete_sub_ix = delete_subscription(
+        jab_program_id,
+        delete_subscription::Accounts {
+            subscription: &legacy_subscription,
+            subscriber: &legacy_subscriber.pubkey(),
+            subscribed_to_profile: &legacy_profile,
+        },
+        delete_subscription::Params {},
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![delete_sub_ix],
+        vec![&legacy_subscriber],
+    )
+    .await
+    .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(legacy_subscription)
+        .await
+        .unwrap();
+    assert!(account.is_none());
+
+    //
+    // Close accounts
+    //
+    let close_thread_ix = close_thread(
+        jab_program_id,
+        close_thread::Accounts {
+            participant: &prg_test_ctx.payer.pubkey(),
+            thread: &thread_account,
+        },
+        close_thread::Params {},
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![close_thread_ix], vec![])
+        .await
+        .unwrap();
+
+    let close_group_index_ix = close_group_index(
+        jab_program_id,
+        close_group_index::Accounts {
+            owner: &prg_test_ctx.payer.pubkey(),
+            group_thread_index: &group_index,
+        },
+        close_group_index::Params {},
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![close_group_index_ix], vec![])
+        .await
+        .unwrap();
+
+    let close_group_thread_ix = close_group_thread(
+        jab_program_id,
+        close_group_thread::Accounts {
+            group_owner: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+        },
+        close_group_thread::Params {},
+    );
+
+    // The group cannot be closed while it has members or moderations
+    let group_thread_data = prg_test_ctx
+        .banks_client
+        .get_account(group_thread)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let group_thread_data = GroupThread::deserialize(&mut &group_thread_data[..]).unwrap();
+    assert_eq!(group_thread_data.linked_account_count, 2);
+
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![close_group_thread_ix.clone()],
+        vec![],
+    )
+    .await
+    .is_err());
+
+    let leave_group_ix = leave_group(
+        jab_program_id,
+        leave_group::Accounts {
+            member: &receiver_account.pubkey(),
+            group_member: &group_member,
+            group_thread: &group_thread,
+        },
+        leave_group::Params {},
+    );
+    let unmute_member_ix = mute_member(
+        jab_program_id,
+        mute_member::Accounts {
+            system_program: &system_program::ID,
+            moderator: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            group_moderation: &group_moderation,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            user_group_member: &group_member,
+            user_group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &receiver_account.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+        },
+        mute_member::Params {
+            user: receiver_account.pubkey(),
+            muted_until: 0,
+        },
+    );
+    let unban_member_ix = unban_member(
+        jab_program_id,
+        unban_member::Accounts {
+            moderator: &prg_test_ctx.payer.pubkey(),
+            group_thread: &group_thread,
+            group_moderation: &group_moderation,
+            group_member: &GroupMember::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            group_admin: &GroupAdmin::find_key(
+                &group_thread,
+                &prg_test_ctx.payer.pubkey(),
+                &jab_program_id,
+            )
+            .0,
+            moderation_payer: &prg_test_ctx.payer.pubkey(),
+        },
+        unban_member::Params {},
+    );
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![unmute_member_ix, unban_member_ix, leave_group_ix],
+        vec![&receiver_account],
+    )
+    .await
+    .unwrap();
+
+    sign_send_instructions(&mut prg_test_ctx, vec![close_group_thread_ix], vec![])
+        .await
+        .unwrap();
+
+    // Profiles cannot be closed while they have subscribers
+    let close_legacy_profile_ix = close_profile(
+        jab_program_id,
+        close_profile::Accounts {
+            profile_owner: &legacy_subscriber.pubkey(),
+            profile: &legacy_profile,
+        },
+        close_profile::Params {},
+    );
+
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![close_legacy_profile_ix],
+        vec![&legacy_subscriber],
+    )
+    .await
+    .is_err());
+
+    let close_profile_ix = close_profile(
+        jab_program_id,
+        close_profile::Accounts {
+            profile_owner: &prg_test_ctx.payer.pubkey(),
+            profile: &sender_profile,
+        },
+        close_profile::Params {},
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![close_profile_ix], vec![])
+        .await
+        .unwrap();
+
+    // Migrate the legacy thread
+    let migrate_account_ix = migrate_account(
+        jab_program_id,
+        migrate_account::Accounts {
+            system_program: &system_program::ID,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            account: &legacy_thread,
+            group_owner: None,
+            group_admins: &[],
+        },
+        migrate_account::Params {},
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![migrate_account_ix], vec![])
+        .await
+        .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(legacy_thread)
+        .await
+        .unwrap()
+        .unwrap();
+    let thread = Thread::deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(thread.msg_count, 3);
+    assert_eq!(thread.open_msg_count, 3);
+    assert!(thread.state == ThreadState::Accepted);
+
+    // Migrate the legacy group, its admins become group admin accounts
+    let (legacy_group_admin, _) =
+        GroupAdmin::find_key(&legacy_group, &legacy_user_2, &jab_program_id);
+    let migrate_account_ix = migrate_account(
+        jab_program_id,
+        migrate_account::Accounts {
+            system_program: &system_program::ID,
+            fee_payer: &prg_test_ctx.payer.pubkey(),
+            account: &legacy_group,
+            group_owner: Some(&legacy_user_1.pubkey()),
+            group_admins: &[legacy_group_admin],
+        },
+        migrate_account::Params {},
+    );
+
+    sign_send_instructions(&mut prg_test_ctx, vec![migrate_account_ix], vec![])
+        .await
+        .unwrap();
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(legacy_group)
+        .await
+        .unwrap()
+        .unwrap();
+    let group = GroupThread::deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(group.seed_version, SeedVersion::V1);
+    assert_eq!(group.key(&jab_program_id), legacy_group);
+    assert_eq!(group.linked_account_count, 1);
+    assert!(
+        GroupThread::find_key_v1("L".repeat(33), legacy_user_1.pubkey(), &jab_program_id).is_none()
+    );
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(legacy_group_admin)
+        .await
+        .unwrap()
+        .unwrap();
+    let group_admin = GroupAdmin::deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(group_admin.admin, legacy_user_2);
+
+    // Legacy messages are deleted without being migrated first
+    let delete_legacy_message_ix = delete_message(
+        jab_program_id,
+        delete_message::Accounts {
+            sender: &legacy_user_1.pubkey(),
+            receiver: &legacy_user_2,
+            message: &legacy_message,
+            thread: &legacy_thread,
+        },
+        delete_message::Params { message_index: 0 },
+    );
+    let delete_legacy_group_message_ix = |legacy_group_thread: Option<&Pubkey>| {
+        delete_group_message(
+            jab_program_id,
+            delete_group_message::Accounts {
+                group_thread: &legacy_group,
+                message: &legacy_group_message,
+                fee_payer: &legacy_user_1.pubkey(),
+                sender: &legacy_user_1.pubkey(),
+                group_member: &GroupMember::find_key(
+                    &legacy_group,
+                    &legacy_user_1.pubkey(),
+                    &jab_program_id,
+                )
+                .0,
+                group_admin: &GroupAdmin::find_key(
+                    &legacy_group,
+                    &legacy_user_1.pubkey(),
+                    &jab_program_id,
+                )
+                .0,
+                legacy_group_thread,
+            },
+            delete_group_message::Params { nonce: 0 },
+        )
+    };
+
+    // Deleting a message keyed by the legacy counter requires the writable group thread
+    assert!(sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![delete_legacy_group_message_ix(None)],
+        vec![&legacy_user_1],
+    )
+    .await
+    .is_err());
+
+    sign_send_instructions(
+        &mut prg_test_ctx,
+        vec![
+            delete_legacy_message_ix,
+            delete_legacy_group_message_ix(Some(&legacy_group)),
+        ],
+        vec![&legacy_user_1],
+    )
+    .await
+    .unwrap();
+
+    for key in &[legacy_message, legacy_group_message] {
+        assert!(prg_test_ctx
+            .banks_client
+            .get_account(*key)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(legacy_thread)
+        .await
+        .unwrap()
+        .unwrap();
+    let thread = Thread::deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(thread.open_msg_count, 2);
+    let account = prg_test_ctx
+        .banks_client
+        .get_account(legacy_group)
+        .await
+        .unwrap()
+        .unwrap();
+    let group = GroupThread::deserialize(&mut &account.data[..]).unwrap();
+    assert_eq!(group.open_msg_count, 0);
+
+    for closed_account in [thread_account, group_index, group_thread, sender_profile] {
+        let account = prg_test_ctx
+            .banks_client
+            .get_account(closed_account)
+            .await
+            .unwrap();
+        assert!(account.is_none());
+    }
 }